@@ -4,6 +4,9 @@ extern crate my_project;
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use my_project::utilities::data_loader::read_candles_from_csv;
 
+use my_project::indicators::pattern_recognition::{
+    cdl2crows, cdl3blackcrows, cdlabandonedbaby, cdldoji, cdlengulfing, PatternInput, PatternType,
+};
 use my_project::indicators::{
     acosc::{acosc, AcoscInput},
     ad::{ad, AdInput},
@@ -1087,5 +1090,49 @@ fn benchmark_indicators(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, benchmark_indicators);
+fn benchmark_patterns(c: &mut Criterion) {
+    let candles =
+        read_candles_from_csv("src/data/bitfinex btc-usd 100,000 candles ends 09-01-24.csv")
+            .expect("Failed to load candles");
+
+    let mut group = c.benchmark_group("Pattern Benchmarks");
+    group.measurement_time(Duration::new(8, 0));
+    group.warm_up_time(Duration::new(4, 0));
+
+    // CDL2CROWS
+    group.bench_function(BenchmarkId::new("CDL2CROWS", 0), |b| {
+        let input = PatternInput::with_default_candles(&candles, PatternType::Cdl2Crows);
+        b.iter(|| cdl2crows(black_box(&input)).expect("Failed to calculate CDL2CROWS"))
+    });
+
+    // CDL3BLACKCROWS
+    group.bench_function(BenchmarkId::new("CDL3BLACKCROWS", 0), |b| {
+        let input = PatternInput::with_default_candles(&candles, PatternType::Cdl3BlackCrows);
+        b.iter(|| cdl3blackcrows(black_box(&input)).expect("Failed to calculate CDL3BLACKCROWS"))
+    });
+
+    // CDLENGULFING
+    group.bench_function(BenchmarkId::new("CDLENGULFING", 0), |b| {
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlEngulfing);
+        b.iter(|| cdlengulfing(black_box(&input)).expect("Failed to calculate CDLENGULFING"))
+    });
+
+    // CDLDOJI
+    group.bench_function(BenchmarkId::new("CDLDOJI", 0), |b| {
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlDoji);
+        b.iter(|| cdldoji(black_box(&input)).expect("Failed to calculate CDLDOJI"))
+    });
+
+    // CDLABANDONEDBABY
+    group.bench_function(BenchmarkId::new("CDLABANDONEDBABY", 0), |b| {
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlAbandonedBaby);
+        b.iter(|| {
+            cdlabandonedbaby(black_box(&input)).expect("Failed to calculate CDLABANDONEDBABY")
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_indicators, benchmark_patterns);
 criterion_main!(benches);
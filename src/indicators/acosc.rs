@@ -10,30 +10,48 @@
 ///   a rate of change or “momentum” of the AC oscillator.
 ///
 /// ## Parameters
-/// - None. The AC Oscillator uses fixed settings (5- and 34-period simple moving averages).
-///   You can provide data via [`AcoscData::Candles`] or [`AcoscData::Slices`].
+/// - **change_period**: Lookback of the `change` output, `change[i] = osc[i] - osc[i - change_period]`.
+///   Defaults to 1. With the default, the first output bar is measured against zero as
+///   before; longer lookbacks leave the first `change_period` outputs as `NaN`.
+/// - The oscillator itself uses fixed settings (5- and 34-period simple moving averages).
+///   You can provide data via [`AcoscData::Candles`], [`AcoscData::Slices`] or
+///   [`AcoscData::View`] (see [`AcoscInput::from_view`]).
 ///
 /// ## Errors
 /// - **CandleFieldError**: Failed to retrieve the required candle fields (`high`/`low`).
 /// - **LengthMismatch**: `high` and `low` slices have different lengths.
+/// - **InvalidChangePeriod**: `change_period` is zero.
 /// - **NotEnoughData**: Insufficient number of data points to compute the AC oscillator
 ///   (requires at least 39 data points).
 ///
 /// ## Returns
 /// - **`Ok(AcoscOutput)`** on success, containing:
 ///   - `osc`: A `Vec<f64>` of AC oscillator values.
-///   - `change`: A `Vec<f64>` of rate-of-change values.
+///   - `change`: A `Vec<f64>` of `change_period`-bar differences of `osc`.
 /// - **`Err(AcoscError)`** otherwise.
-use crate::utilities::data_loader::Candles;
+use crate::utilities::data_loader::{CandleView, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
+use crate::utilities::series::rolling_sum;
 
 #[derive(Debug, Clone)]
 pub enum AcoscData<'a> {
     Candles { candles: &'a Candles },
     Slices { high: &'a [f64], low: &'a [f64] },
+    View { view: CandleView<'a> },
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct AcoscParams {}
+#[derive(Debug, Clone)]
+pub struct AcoscParams {
+    pub change_period: Option<usize>,
+}
+
+impl Default for AcoscParams {
+    fn default() -> Self {
+        Self {
+            change_period: Some(1),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct AcoscInput<'a> {
@@ -56,12 +74,23 @@ impl<'a> AcoscInput<'a> {
         }
     }
 
+    pub fn from_view(view: impl Into<CandleView<'a>>, params: AcoscParams) -> Self {
+        Self {
+            data: AcoscData::View { view: view.into() },
+            params,
+        }
+    }
+
     pub fn with_default_candles(candles: &'a Candles) -> Self {
         Self {
             data: AcoscData::Candles { candles },
             params: AcoscParams::default(),
         }
     }
+
+    fn get_change_period(&self) -> usize {
+        self.params.change_period.unwrap_or(1)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -82,6 +111,9 @@ pub enum AcoscError {
     )]
     LengthMismatch { high_len: usize, low_len: usize },
 
+    #[error("acosc: Invalid change period: {change_period}")]
+    InvalidChangePeriod { change_period: usize },
+
     #[error(
         "acosc: Not enough data points to calculate AC oscillator: required={required}, actual={actual}"
     )]
@@ -97,6 +129,7 @@ pub fn acosc(input: &AcoscInput) -> Result<AcoscOutput, AcoscError> {
             (high, low)
         }
         AcoscData::Slices { high, low } => (*high, *low),
+        AcoscData::View { view } => (view.high, view.low),
     };
 
     if high_prices.len() != low_prices.len() {
@@ -106,6 +139,11 @@ pub fn acosc(input: &AcoscInput) -> Result<AcoscOutput, AcoscError> {
         });
     }
 
+    let change_period = input.get_change_period();
+    if change_period == 0 {
+        return Err(AcoscError::InvalidChangePeriod { change_period });
+    }
+
     let len = low_prices.len();
     const PERIOD_SMA5: usize = 5;
     const PERIOD_SMA34: usize = 34;
@@ -114,102 +152,41 @@ pub fn acosc(input: &AcoscInput) -> Result<AcoscOutput, AcoscError> {
     const REQUIRED_LENGTH: usize = PERIOD_SMA34 + PERIOD_SMA5;
 
     if len < REQUIRED_LENGTH {
+        not_enough_valid_data("acosc", REQUIRED_LENGTH, len);
         return Err(AcoscError::NotEnoughData {
             required: REQUIRED_LENGTH,
             actual: len,
         });
     }
 
+    let medprice: Vec<f64> = high_prices
+        .iter()
+        .zip(low_prices)
+        .map(|(h, l)| (h + l) * 0.5)
+        .collect();
+    let sum_sma5 = rolling_sum(&medprice, PERIOD_SMA5);
+    let sum_sma34 = rolling_sum(&medprice, PERIOD_SMA34);
+    let ao: Vec<f64> = sum_sma5
+        .iter()
+        .zip(&sum_sma34)
+        .map(|(s5, s34)| s5 * INV_PERIOD_SMA5 - s34 * INV_PERIOD_SMA34)
+        .collect();
+    let sum_sma5_ao = rolling_sum(&ao, PERIOD_SMA5);
+
     let mut osc = vec![f64::NAN; len];
     let mut change = vec![f64::NAN; len];
-
-    let mut queue_sma5 = [0.0; PERIOD_SMA5];
-    let mut queue_sma34 = [0.0; PERIOD_SMA34];
-    let mut queue_sma5_ao = [0.0; PERIOD_SMA5];
-
-    let mut sum_sma5 = 0.0;
-    let mut sum_sma34 = 0.0;
-    let mut sum_sma5_ao = 0.0;
-
-    let mut idx_sma5 = 0;
-    let mut idx_sma34 = 0;
-    let mut idx_sma5_ao = 0;
-
-    for i in 0..PERIOD_SMA34 {
-        let medprice = (high_prices[i] + low_prices[i]) * 0.5;
-
-        sum_sma34 += medprice;
-        queue_sma34[i] = medprice;
-
-        if i < PERIOD_SMA5 {
-            sum_sma5 += medprice;
-            queue_sma5[i] = medprice;
-        }
-    }
-
-    for i in PERIOD_SMA34..(PERIOD_SMA34 + PERIOD_SMA5 - 1) {
-        let medprice = (high_prices[i] + low_prices[i]) * 0.5;
-
-        sum_sma34 += medprice - queue_sma34[idx_sma34];
-        queue_sma34[idx_sma34] = medprice;
-        idx_sma34 += 1;
-        if idx_sma34 == PERIOD_SMA34 {
-            idx_sma34 = 0;
-        }
-        let sma34 = sum_sma34 * INV_PERIOD_SMA34;
-
-        sum_sma5 += medprice - queue_sma5[idx_sma5];
-        queue_sma5[idx_sma5] = medprice;
-        idx_sma5 += 1;
-        if idx_sma5 == PERIOD_SMA5 {
-            idx_sma5 = 0;
-        }
-        let sma5 = sum_sma5 * INV_PERIOD_SMA5;
-
-        let ao = sma5 - sma34;
-
-        sum_sma5_ao += ao;
-        queue_sma5_ao[idx_sma5_ao] = ao;
-        idx_sma5_ao += 1;
-    }
-    if idx_sma5_ao == PERIOD_SMA5 {
-        idx_sma5_ao = 0;
-    }
-
     let mut prev_res = 0.0;
-
-    for i in (PERIOD_SMA34 + PERIOD_SMA5 - 1)..len {
-        let medprice = (high_prices[i] + low_prices[i]) * 0.5;
-
-        sum_sma34 += medprice - queue_sma34[idx_sma34];
-        queue_sma34[idx_sma34] = medprice;
-        idx_sma34 += 1;
-        if idx_sma34 == PERIOD_SMA34 {
-            idx_sma34 = 0;
-        }
-        let sma34 = sum_sma34 * INV_PERIOD_SMA34;
-
-        sum_sma5 += medprice - queue_sma5[idx_sma5];
-        queue_sma5[idx_sma5] = medprice;
-        idx_sma5 += 1;
-        if idx_sma5 == PERIOD_SMA5 {
-            idx_sma5 = 0;
-        }
-        let sma5 = sum_sma5 * INV_PERIOD_SMA5;
-
-        let ao = sma5 - sma34;
-
-        let old_ao = queue_sma5_ao[idx_sma5_ao];
-        sum_sma5_ao += ao - old_ao;
-        queue_sma5_ao[idx_sma5_ao] = ao;
-        idx_sma5_ao += 1;
-        if idx_sma5_ao == PERIOD_SMA5 {
-            idx_sma5_ao = 0;
-        }
-        let sma5_ao = sum_sma5_ao * INV_PERIOD_SMA5;
-
-        let res = ao - sma5_ao;
-        let mom = res - prev_res;
+    let first_out = REQUIRED_LENGTH - 1;
+
+    for i in first_out..len {
+        let res = ao[i] - sum_sma5_ao[i] * INV_PERIOD_SMA5;
+        let mom = if change_period == 1 {
+            res - prev_res
+        } else if i >= first_out + change_period {
+            res - osc[i - change_period]
+        } else {
+            f64::NAN
+        };
         prev_res = res;
 
         osc[i] = res;
@@ -356,6 +333,95 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_acosc_change_period() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let default = acosc(&AcoscInput::with_default_candles(&candles)).expect("default");
+        let one = acosc(&AcoscInput::from_candles(
+            &candles,
+            AcoscParams {
+                change_period: Some(1),
+            },
+        ))
+        .expect("change_period=1");
+        assert_eq!(one.change.len(), default.change.len());
+        for (a, b) in one.change.iter().zip(&default.change) {
+            assert!((a.is_nan() && b.is_nan()) || a == b);
+        }
+
+        let period = 5;
+        let five = acosc(&AcoscInput::from_candles(
+            &candles,
+            AcoscParams {
+                change_period: Some(period),
+            },
+        ))
+        .expect("change_period=5");
+        let first_out = 38;
+        assert!(five.change[..first_out + period].iter().all(|v| v.is_nan()));
+        let i = five.change.len() - 1;
+        assert!((five.change[i] - (five.osc[i] - five.osc[i - period])).abs() < 1e-9);
+
+        let sign_flips = |values: &[f64]| {
+            values[first_out + period..]
+                .windows(2)
+                .filter(|w| (w[0] > 0.0) != (w[1] > 0.0))
+                .count()
+        };
+        assert!(
+            sign_flips(&five.change) < sign_flips(&one.change),
+            "longer change period should flip sign less often"
+        );
+
+        let zero = AcoscInput::from_candles(
+            &candles,
+            AcoscParams {
+                change_period: Some(0),
+            },
+        );
+        assert!(matches!(
+            acosc(&zero),
+            Err(AcoscError::InvalidChangePeriod { .. })
+        ));
+    }
+
+    #[test]
+    fn test_acosc_from_candle_view() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let view = CandleView {
+            open: &candles.open,
+            high: &candles.high,
+            low: &candles.low,
+            close: &candles.close,
+            volume: &candles.volume,
+        };
+        let view_result = acosc(&AcoscInput::from_view(view, AcoscParams::default()))
+            .expect("Failed acosc from view");
+        let candle_result = acosc(&AcoscInput::from_view(&candles, AcoscParams::default()))
+            .expect("Failed acosc from converted candles");
+        let expected = acosc(&AcoscInput::with_default_candles(&candles)).expect("Failed acosc");
+
+        for i in 0..expected.osc.len() {
+            for (got, want) in [
+                (view_result.osc[i], expected.osc[i]),
+                (view_result.change[i], expected.change[i]),
+                (candle_result.osc[i], expected.osc[i]),
+            ] {
+                assert!(
+                    (got.is_nan() && want.is_nan()) || got == want,
+                    "acosc view mismatch at {}: {} vs {}",
+                    i,
+                    got,
+                    want
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_acosc_accuracy_nan_check() {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
@@ -20,6 +20,7 @@
 /// - **`Ok(AdOutput)`** on success, containing a `Vec<f64>` with the cumulative AD line.
 /// - **`Err(AdError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum AdData<'a> {
@@ -128,6 +129,7 @@ pub fn ad(input: &AdInput) -> Result<AdOutput, AdError> {
 
     let size = high.len();
     if size < 1 {
+        not_enough_valid_data("ad", 1, size);
         return Err(AdError::NotEnoughData { len: size });
     }
 
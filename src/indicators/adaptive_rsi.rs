@@ -0,0 +1,146 @@
+/// # Adaptive RSI
+///
+/// An Ehlers-style RSI whose lookback follows the market's dominant cycle. The MESA
+/// dominant cycle period is measured with the Hilbert transform from
+/// [`ht_dcperiod`](crate::indicators::ht_dcperiod::ht_dcperiod) (the same cycle
+/// measurement `mama` adapts to), rounded to whole bars and clamped to
+/// `[MIN_LOOKBACK, MAX_LOOKBACK]`. Each bar then gets a Cutler-style RSI over that many
+/// price changes:
+///
+/// \[ RSI[i] = 100 * gains / (gains + losses) \]
+///
+/// where `gains` and `losses` are the summed up and down moves over the bar's lookback.
+/// A window with no movement reads 50.
+///
+/// ## Errors
+/// - **DominantCycle**: adaptive_rsi: The dominant cycle could not be computed (empty,
+///   all-`NaN`, or fewer than 32 valid data points).
+///
+/// ## Returns
+/// - **`Ok(Vec<f64>)`** matching the input length, with leading `NaN`s until both the
+///   dominant cycle and the first RSI window are available.
+/// - **`Err(AdaptiveRsiError)`** otherwise.
+use crate::indicators::ht_dcperiod::{
+    ht_dcperiod, HtDcPeriodError, HtDcPeriodInput, HtDcPeriodParams,
+};
+use crate::utilities::data_loader::{source_type, Candles};
+use thiserror::Error;
+
+/// Shortest RSI lookback the dominant cycle is clamped to.
+pub const MIN_LOOKBACK: usize = 2;
+/// Longest RSI lookback the dominant cycle is clamped to.
+pub const MAX_LOOKBACK: usize = 50;
+
+#[derive(Debug, Error)]
+pub enum AdaptiveRsiError {
+    #[error("adaptive_rsi: {0}")]
+    DominantCycle(#[from] HtDcPeriodError),
+}
+
+#[inline]
+pub fn adaptive_rsi(candles: &Candles, source: &str) -> Result<Vec<f64>, AdaptiveRsiError> {
+    let data = source_type(candles, source);
+    let cycle = ht_dcperiod(&HtDcPeriodInput::from_slice(data, HtDcPeriodParams))?.values;
+    let len = data.len();
+
+    let mut gains = vec![0.0; len + 1];
+    let mut losses = vec![0.0; len + 1];
+    for i in 1..len {
+        let change = data[i] - data[i - 1];
+        let (gain, loss) = if change.is_nan() {
+            (0.0, 0.0)
+        } else {
+            (change.max(0.0), (-change).max(0.0))
+        };
+        gains[i + 1] = gains[i] + gain;
+        losses[i + 1] = losses[i] + loss;
+    }
+
+    let mut out = vec![f64::NAN; len];
+    for i in 0..len {
+        if cycle[i].is_nan() {
+            continue;
+        }
+        let lookback = (cycle[i].round() as usize).clamp(MIN_LOOKBACK, MAX_LOOKBACK);
+        if i < lookback {
+            continue;
+        }
+        let up = gains[i + 1] - gains[i + 1 - lookback];
+        let down = losses[i + 1] - losses[i + 1 - lookback];
+        out[i] = if up + down == 0.0 {
+            50.0
+        } else {
+            100.0 * up / (up + down)
+        };
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::data_loader::read_candles_from_csv;
+
+    #[test]
+    fn test_adaptive_rsi_on_btc() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let values = adaptive_rsi(&candles, "close").expect("Failed to calculate adaptive RSI");
+        assert_eq!(values.len(), candles.close.len());
+
+        let first_valid = values
+            .iter()
+            .position(|v| !v.is_nan())
+            .expect("adaptive RSI never warmed up");
+        assert!(first_valid >= 32);
+        for (i, &v) in values.iter().enumerate().skip(first_valid) {
+            assert!(
+                v.is_finite() && (0.0..=100.0).contains(&v),
+                "Adaptive RSI out of range at {}: {}",
+                i,
+                v
+            );
+        }
+    }
+
+    #[test]
+    fn test_adaptive_rsi_matches_fixed_window() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let values = adaptive_rsi(&candles, "close").unwrap();
+        let cycle = ht_dcperiod(&HtDcPeriodInput::from_candles(
+            &candles,
+            "close",
+            HtDcPeriodParams,
+        ))
+        .unwrap()
+        .values;
+
+        let i = 5000;
+        let lookback = (cycle[i].round() as usize).clamp(MIN_LOOKBACK, MAX_LOOKBACK);
+        let (mut up, mut down) = (0.0, 0.0);
+        for j in (i + 1 - lookback)..=i {
+            let change = candles.close[j] - candles.close[j - 1];
+            up += change.max(0.0);
+            down += (-change).max(0.0);
+        }
+        assert!((values[i] - 100.0 * up / (up + down)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_adaptive_rsi_not_enough_data() {
+        let candles = Candles::new(
+            (0..10).collect(),
+            vec![1.0; 10],
+            vec![1.0; 10],
+            vec![1.0; 10],
+            vec![1.0; 10],
+            vec![1.0; 10],
+        );
+        assert!(matches!(
+            adaptive_rsi(&candles, "close"),
+            Err(AdaptiveRsiError::DominantCycle(_))
+        ));
+    }
+}
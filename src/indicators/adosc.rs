@@ -29,6 +29,7 @@
 /// println!("ADOSC values: {:?}", output.values);
 /// ```
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum AdoscData<'a> {
@@ -164,6 +165,7 @@ pub fn adosc(input: &AdoscInput) -> Result<AdoscOutput, AdoscError> {
                 return Err(AdoscError::NoCandlesAvailable);
             }
             if long > candles.close.len() {
+                not_enough_valid_data("adosc", long, candles.close.len());
                 return Err(AdoscError::NotEnoughData {
                     required: long,
                     have: candles.close.len(),
@@ -192,6 +194,7 @@ pub fn adosc(input: &AdoscInput) -> Result<AdoscOutput, AdoscError> {
             }
             let len = close.len();
             if long > len {
+                not_enough_valid_data("adosc", long, len);
                 return Err(AdoscError::NotEnoughData {
                     required: long,
                     have: len,
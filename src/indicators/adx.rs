@@ -20,6 +20,7 @@
 ///   Values before the ADX is fully formed remain `NaN`.
 /// - **`Err(AdxError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum AdxData<'a> {
@@ -124,6 +125,7 @@ pub fn adx(input: &AdxInput) -> Result<AdxOutput, AdxError> {
         });
     }
     if len < period + 1 {
+        not_enough_valid_data("adx", period + 1, len);
         return Err(AdxError::NotEnoughData {
             needed: period + 1,
             found: len,
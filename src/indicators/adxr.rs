@@ -20,6 +20,7 @@
 ///   The final values are the computed ADXR values for each data point.
 /// - **`Err(AdxrError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum AdxrData<'a> {
@@ -146,6 +147,7 @@ pub fn adxr(input: &AdxrInput) -> Result<AdxrOutput, AdxrError> {
     }
 
     if len < period + 1 {
+        not_enough_valid_data("adxr", period + 1, len);
         return Err(AdxrError::NotEnoughData {
             period,
             needed: period + 1,
@@ -29,6 +29,7 @@
 ///   the jaw, teeth, and lips, each shifted according to their respective offsets.
 /// - **`Err(AlligatorError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::series::shift_forward;
 
 #[derive(Debug, Clone)]
 pub enum AlligatorData<'a> {
@@ -239,18 +240,12 @@ pub fn alligator(input: &AlligatorInput) -> Result<AlligatorOutput, AlligatorErr
                 if i == jaw_period - 1 {
                     jaw_smma_val = jaw_sum / (jaw_period as f64);
                     jaw_ready = true;
-                    let shifted_index = i + jaw_offset;
-                    if shifted_index < len {
-                        jaw[shifted_index] = jaw_smma_val;
-                    }
+                    jaw[i] = jaw_smma_val;
                 }
             }
         } else {
             jaw_smma_val = (jaw_smma_val * jaw_scale + data_point) * jaw_inv_period;
-            let shifted_index = i + jaw_offset;
-            if shifted_index < len {
-                jaw[shifted_index] = jaw_smma_val;
-            }
+            jaw[i] = jaw_smma_val;
         }
 
         if !teeth_ready {
@@ -259,18 +254,12 @@ pub fn alligator(input: &AlligatorInput) -> Result<AlligatorOutput, AlligatorErr
                 if i == teeth_period - 1 {
                     teeth_smma_val = teeth_sum / (teeth_period as f64);
                     teeth_ready = true;
-                    let shifted_index = i + teeth_offset;
-                    if shifted_index < len {
-                        teeth[shifted_index] = teeth_smma_val;
-                    }
+                    teeth[i] = teeth_smma_val;
                 }
             }
         } else {
             teeth_smma_val = (teeth_smma_val * teeth_scale + data_point) * teeth_inv_period;
-            let shifted_index = i + teeth_offset;
-            if shifted_index < len {
-                teeth[shifted_index] = teeth_smma_val;
-            }
+            teeth[i] = teeth_smma_val;
         }
 
         if !lips_ready {
@@ -279,22 +268,20 @@ pub fn alligator(input: &AlligatorInput) -> Result<AlligatorOutput, AlligatorErr
                 if i == lips_period - 1 {
                     lips_smma_val = lips_sum / (lips_period as f64);
                     lips_ready = true;
-                    let shifted_index = i + lips_offset;
-                    if shifted_index < len {
-                        lips[shifted_index] = lips_smma_val;
-                    }
+                    lips[i] = lips_smma_val;
                 }
             }
         } else {
             lips_smma_val = (lips_smma_val * lips_scale + data_point) * lips_inv_period;
-            let shifted_index = i + lips_offset;
-            if shifted_index < len {
-                lips[shifted_index] = lips_smma_val;
-            }
+            lips[i] = lips_smma_val;
         }
     }
 
-    Ok(AlligatorOutput { jaw, teeth, lips })
+    Ok(AlligatorOutput {
+        jaw: shift_forward(&jaw, jaw_offset),
+        teeth: shift_forward(&teeth, teeth_offset),
+        lips: shift_forward(&lips, lips_offset),
+    })
 }
 
 #[cfg(test)]
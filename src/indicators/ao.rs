@@ -30,7 +30,10 @@
 /// let output = ao(&input).unwrap();
 /// println!("Awesome Oscillator values: {:?}", output.values);
 /// ```
+use crate::indicators::utility_functions::crossover_signals;
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
+use crate::utilities::series::rolling_sum;
 
 #[derive(Debug, Clone)]
 pub enum AoData<'a> {
@@ -134,38 +137,58 @@ pub fn ao(input: &AoInput) -> Result<AoOutput, AoError> {
 
     let len = data.len();
     if long > len {
+        not_enough_valid_data("ao", long, len);
         return Err(AoError::NotEnoughData {
             long,
             data_len: len,
         });
     }
 
-    let mut ao_values = vec![f64::NAN; len];
-
-    let mut short_sum = 0.0;
-    let mut long_sum = 0.0;
+    let short_sum = rolling_sum(data, short);
+    let long_sum = rolling_sum(data, long);
+    // A NaN input poisons every later value, not just the windows that contain it.
+    let poisoned_from = data.iter().position(|v| v.is_nan()).unwrap_or(len);
+    let ao_values = (0..len)
+        .map(|i| {
+            if i >= poisoned_from {
+                f64::NAN
+            } else {
+                short_sum[i] / (short as f64) - long_sum[i] / (long as f64)
+            }
+        })
+        .collect();
 
-    for i in 0..len {
-        let val = data[i];
-        short_sum += val;
-        long_sum += val;
+    Ok(AoOutput { values: ao_values })
+}
 
-        if i >= short {
-            short_sum -= data[i - short];
-        }
+/// Zero-line crosses of AO values: `1` where AO crosses above zero, `-1` where it
+/// crosses below, `0` otherwise (including `NaN` warmup bars). A bar landing exactly on
+/// zero is not a cross; the signal fires on the bar that leaves it.
+#[inline]
+pub fn ao_signals(values: &[f64]) -> Vec<i8> {
+    crossover_signals(values, &vec![0.0; values.len()])
+}
 
-        if i >= long {
-            long_sum -= data[i - long];
+/// Bill Williams' saucer setup. A bar counts as red when AO falls from the bar before
+/// and green when it rises. A bullish saucer (`1`) is two red bars followed by a green
+/// bar, all three above zero; a bearish saucer (`-1`) is two green bars followed by a
+/// red bar, all three below zero. The signal is reported on the third bar.
+#[inline]
+pub fn ao_saucer(values: &[f64]) -> Vec<i8> {
+    let mut signals = vec![0i8; values.len()];
+    for i in 3..values.len() {
+        let w = &values[i - 3..=i];
+        if w.iter().any(|v| v.is_nan()) {
+            continue;
         }
-
-        if i >= (long - 1) {
-            let short_sma = short_sum / (short as f64);
-            let long_sma = long_sum / (long as f64);
-            ao_values[i] = short_sma - long_sma;
+        let bars = &w[1..];
+        if bars.iter().all(|&v| v > 0.0) && w[1] < w[0] && w[2] < w[1] && w[3] > w[2] {
+            signals[i] = 1;
+        } else if bars.iter().all(|&v| v < 0.0) && w[1] > w[0] && w[2] > w[1] && w[3] < w[2] {
+            signals[i] = -1;
         }
     }
-
-    Ok(AoOutput { values: ao_values })
+    signals
 }
 
 #[cfg(test)]
@@ -369,4 +392,38 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_ao_nan_poisons_later_values() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let mut data = candles.hl2[..200].to_vec();
+        data[60] = f64::NAN;
+
+        let result = ao(&AoInput::from_slice(&data, AoParams::default())).expect("AO");
+        assert!(result.values[33..60].iter().all(|v| v.is_finite()));
+        assert!(result.values[60..].iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_ao_zero_cross_signals() {
+        let values = [f64::NAN, -2.0, -1.0, 0.5, 1.0, 0.0, -0.5, -0.2];
+        let signals = ao_signals(&values);
+        assert_eq!(signals, vec![0, 0, 0, 1, 0, 0, -1, 0]);
+    }
+
+    #[test]
+    fn test_ao_bullish_saucer() {
+        let values = [f64::NAN, 3.0, 2.5, 2.0, 2.4, 2.2, 1.8, 1.5, 1.7];
+        let signals = ao_saucer(&values);
+        assert_eq!(signals[4], 1);
+        assert_eq!(signals[8], 1);
+        assert_eq!(signals.iter().filter(|&&s| s != 0).count(), 2);
+
+        let crossing = [0.5, 0.3, -0.1, 0.2];
+        assert_eq!(ao_saucer(&crossing), vec![0, 0, 0, 0]);
+
+        let bearish = [-1.0, -1.5, -1.2, -0.9, -1.1];
+        assert_eq!(ao_saucer(&bearish), vec![0, 0, 0, 0, -1]);
+    }
 }
@@ -27,6 +27,7 @@
 ///   without a warm-up period.
 /// - **`Err(ApoError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum ApoData<'a> {
@@ -144,6 +145,7 @@ pub fn apo(input: &ApoInput) -> Result<ApoOutput, ApoError> {
     }
 
     if len < long {
+        not_enough_valid_data("apo", long, len);
         return Err(ApoError::NotEnoughData {
             needed: long,
             found: len,
@@ -23,6 +23,7 @@
 ///   - `aroon_down`: A `Vec<f64>` representing the Aroon Down values.
 /// - **`Err(AroonError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum AroonData<'a> {
@@ -142,6 +143,7 @@ pub fn aroon(input: &AroonInput) -> Result<AroonOutput, AroonError> {
     let length = input.get_length();
     let data_len = high.len();
     if data_len < length {
+        not_enough_valid_data("aroon", length, data_len);
         return Err(AroonError::NotEnoughData {
             data_len,
             required: length,
@@ -21,6 +21,7 @@
 /// - **`Ok(AroonOscOutput)`** on success, containing a `Vec<f64>` of the oscillator values.
 /// - **`Err(AroonOscError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum AroonOscData<'a> {
@@ -134,6 +135,7 @@ pub fn aroon_osc(input: &AroonOscInput) -> Result<AroonOscOutput, AroonOscError>
 
     let len = low.len();
     if len < length {
+        not_enough_valid_data("aroonosc", length, len);
         return Err(AroonOscError::NotEnoughData {
             required: length,
             found: len,
@@ -7,6 +7,8 @@
 /// ## Parameters
 /// - **length**: The number of periods used to compute the smoothed true range
 ///   (defaults to 14).
+/// - **range_mode**: How each bar's range is measured. `TrueRange` (default) includes
+///   gaps from the previous close; `HighLow` uses only `high - low`.
 ///
 /// ## Errors
 /// - **InvalidLength**: atr: The specified `length` is zero.
@@ -19,6 +21,7 @@
 /// - **`Ok(AtrOutput)`** on success, containing a `Vec<f64>` of length matching the input.
 /// - **`Err(AtrError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum AtrData<'a> {
@@ -35,14 +38,25 @@ pub enum AtrData<'a> {
 #[derive(Debug, Clone)]
 pub struct AtrParams {
     pub length: Option<usize>,
+    pub range_mode: Option<RangeMode>,
 }
 
 impl Default for AtrParams {
     fn default() -> Self {
-        Self { length: Some(14) }
+        Self {
+            length: Some(14),
+            range_mode: Some(RangeMode::TrueRange),
+        }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RangeMode {
+    #[default]
+    TrueRange,
+    HighLow,
+}
+
 #[derive(Debug, Clone)]
 pub struct AtrInput<'a> {
     pub data: AtrData<'a>,
@@ -81,6 +95,10 @@ impl<'a> AtrInput<'a> {
             .length
             .unwrap_or_else(|| AtrParams::default().length.unwrap())
     }
+
+    pub fn get_range_mode(&self) -> RangeMode {
+        self.params.range_mode.unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -143,12 +161,14 @@ pub fn atr(input: &AtrInput) -> Result<AtrOutput, AtrError> {
         return Err(AtrError::NoCandlesAvailable);
     }
     if length > len {
+        not_enough_valid_data("atr", length, len);
         return Err(AtrError::NotEnoughData {
             length,
             data_len: len,
         });
     }
 
+    let range_mode = input.get_range_mode();
     let mut atr_values = vec![f64::NAN; len];
 
     let alpha = 1.0 / length as f64;
@@ -157,8 +177,8 @@ pub fn atr(input: &AtrInput) -> Result<AtrOutput, AtrError> {
     let mut rma = f64::NAN;
 
     for i in 0..len {
-        let tr = if i == 0 {
-            high[0] - low[0]
+        let tr = if i == 0 || range_mode == RangeMode::HighLow {
+            high[i] - low[i]
         } else {
             let hl = high[i] - low[i];
             let hc = (high[i] - close[i - 1]).abs();
@@ -181,6 +201,26 @@ pub fn atr(input: &AtrInput) -> Result<AtrOutput, AtrError> {
     Ok(AtrOutput { values: atr_values })
 }
 
+/// ATR expressed as a fraction of the close price (`atr[i] / close[i]`).
+///
+/// Unlike `natr`, the result is not scaled by 100, which keeps it convenient for
+/// position sizing math.
+#[inline]
+pub fn atr_pct(input: &AtrInput) -> Result<Vec<f64>, AtrError> {
+    let atr_output = atr(input)?;
+    let close = match &input.data {
+        AtrData::Candles { candles } => candles.select_candle_field("close")?,
+        AtrData::Slices { close, .. } => *close,
+    };
+
+    Ok(atr_output
+        .values
+        .iter()
+        .zip(close.iter())
+        .map(|(&a, &c)| a / c)
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,12 +231,18 @@ mod tests {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
         let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
 
-        let partial_params = AtrParams { length: None };
+        let partial_params = AtrParams {
+            length: None,
+            ..AtrParams::default()
+        };
         let input_partial = AtrInput::from_candles(&candles, partial_params);
         let result_partial = atr(&input_partial).expect("Failed ATR with partial params");
         assert_eq!(result_partial.values.len(), candles.close.len());
 
-        let zero_and_none_params = AtrParams { length: Some(14) };
+        let zero_and_none_params = AtrParams {
+            length: Some(14),
+            ..AtrParams::default()
+        };
         let input_zero_and_none = AtrInput::from_candles(&candles, zero_and_none_params);
         let result_zero_and_none =
             atr(&input_zero_and_none).expect("Failed ATR with zero/none combo");
@@ -264,7 +310,10 @@ mod tests {
     fn test_atr_with_zero_length() {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
         let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
-        let zero_length_params = AtrParams { length: Some(0) };
+        let zero_length_params = AtrParams {
+            length: Some(0),
+            ..AtrParams::default()
+        };
         let input_zero_length = AtrInput::from_candles(&candles, zero_length_params);
         let result_zero_length = atr(&input_zero_length);
         assert!(result_zero_length.is_err());
@@ -276,6 +325,7 @@ mod tests {
         let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
         let too_long_params = AtrParams {
             length: Some(candles.close.len() + 10),
+            ..AtrParams::default()
         };
         let input_too_long = AtrInput::from_candles(&candles, too_long_params);
         let result_too_long = atr(&input_too_long);
@@ -287,7 +337,10 @@ mod tests {
         let high = [10.0];
         let low = [5.0];
         let close = [7.0];
-        let params = AtrParams { length: Some(14) };
+        let params = AtrParams {
+            length: Some(14),
+            ..AtrParams::default()
+        };
         let input = AtrInput::from_slices(&high, &low, &close, params);
         let result = atr(&input);
         assert!(result.is_err());
@@ -297,11 +350,17 @@ mod tests {
     fn test_atr_with_slice_data_reinput() {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
         let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
-        let first_params = AtrParams { length: Some(14) };
+        let first_params = AtrParams {
+            length: Some(14),
+            ..AtrParams::default()
+        };
         let first_input = AtrInput::from_candles(&candles, first_params);
         let first_result = atr(&first_input).expect("Failed ATR (first run)");
         assert_eq!(first_result.values.len(), candles.close.len());
-        let second_params = AtrParams { length: Some(5) };
+        let second_params = AtrParams {
+            length: Some(5),
+            ..AtrParams::default()
+        };
         let second_input = AtrInput::from_slices(
             &first_result.values,
             &first_result.values,
@@ -316,7 +375,10 @@ mod tests {
     fn test_atr_accuracy_nan_check() {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
         let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
-        let params = AtrParams { length: Some(14) };
+        let params = AtrParams {
+            length: Some(14),
+            ..AtrParams::default()
+        };
         let input = AtrInput::from_candles(&candles, params);
         let result = atr(&input).expect("Failed to calculate ATR");
         assert_eq!(result.values.len(), candles.close.len());
@@ -326,4 +388,77 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_atr_pct_matches_natr() {
+        use crate::indicators::natr::{natr, NatrInput, NatrParams};
+
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let input = AtrInput::from_candles(
+            &candles,
+            AtrParams {
+                length: Some(14),
+                ..AtrParams::default()
+            },
+        );
+        let pct = atr_pct(&input).expect("Failed to calculate ATR percent");
+        assert_eq!(pct.len(), candles.close.len());
+
+        let natr_input = NatrInput::from_candles(&candles, NatrParams { period: Some(14) });
+        let natr_result = natr(&natr_input).expect("Failed to calculate NATR");
+
+        for (i, (&p, &n)) in pct.iter().zip(natr_result.values.iter()).enumerate() {
+            if n.is_nan() {
+                assert!(p.is_nan(), "Expected NaN at index {}", i);
+            } else {
+                assert!(
+                    (p * 100.0 - n).abs() < 1e-9,
+                    "ATR percent mismatch at index {}: {} vs {}",
+                    i,
+                    p * 100.0,
+                    n
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_atr_high_low_mode_ignores_gaps() {
+        let high = [11.0, 21.0, 22.0, 23.0];
+        let low = [9.0, 19.0, 20.0, 21.0];
+        let close = [10.0, 20.0, 21.0, 22.0];
+        let tr_params = AtrParams {
+            length: Some(2),
+            ..AtrParams::default()
+        };
+        let hl_params = AtrParams {
+            length: Some(2),
+            range_mode: Some(RangeMode::HighLow),
+        };
+        let tr = atr(&AtrInput::from_slices(&high, &low, &close, tr_params)).unwrap();
+        let hl = atr(&AtrInput::from_slices(&high, &low, &close, hl_params)).unwrap();
+        assert_eq!(tr.values[1], 6.5);
+        assert_eq!(hl.values[1], 2.0);
+        assert!(hl.values[3] < tr.values[3]);
+
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let hl_params = AtrParams {
+            range_mode: Some(RangeMode::HighLow),
+            ..AtrParams::default()
+        };
+        let tr = atr(&AtrInput::with_default_candles(&candles)).unwrap();
+        let hl = atr(&AtrInput::from_candles(&candles, hl_params)).unwrap();
+        for i in 13..candles.close.len() {
+            assert!(
+                hl.values[i] <= tr.values[i] + 1e-9,
+                "HighLow ATR larger at {}",
+                i
+            );
+        }
+        let last = candles.close.len() - 1;
+        assert!(hl.values[last] < tr.values[last]);
+    }
 }
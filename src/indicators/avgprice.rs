@@ -158,6 +158,7 @@ mod tests {
             low: vec![90., 91., 92., 93., 94.],
             close: vec![105., 106., 107., 108., 109.],
             volume: vec![1000., 1000., 1000., 1000., 1000.],
+            timestamp_f64: vec![1., 2., 3., 4., 5.],
             hl2: vec![100., 101., 102., 103., 104.],
             hlc3: vec![100., 101., 102., 103., 104.],
             ohlc4: vec![100., 101., 102., 103., 104.],
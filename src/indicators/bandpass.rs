@@ -35,6 +35,7 @@
 /// [HighPass filter]: crate::indicators::highpass
 use crate::indicators::highpass::{highpass, HighPassError, HighPassInput, HighPassParams};
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::f64::consts::PI;
 
 #[derive(Debug, Clone)]
@@ -143,6 +144,7 @@ pub fn bandpass(input: &BandPassInput) -> Result<BandPassOutput, BandPassError>
     let bandwidth = input.get_bandwidth();
 
     if len == 0 || len < period {
+        not_enough_valid_data("bandpass", period, len);
         return Err(BandPassError::NotEnoughData {
             data_len: len,
             period,
@@ -0,0 +1,152 @@
+/// # Rolling Beta
+///
+/// Rolling beta measures how strongly an asset's returns move with a benchmark's returns:
+/// `cov(asset, benchmark) / var(benchmark)` over a trailing window of `period` one-bar
+/// [`simple_returns`]. A beta of 2 means the asset moved twice as much as the benchmark
+/// within the window, a beta of 0 means the two were unrelated, and a negative beta means
+/// they moved in opposite directions. Both inputs are price series aligned bar by bar.
+///
+/// ## Parameters
+/// - **period**: Number of returns in each window. Must be at least 2.
+///
+/// ## Errors
+/// - **EmptyData**: beta: Input data slice is empty.
+/// - **MismatchedLength**: beta: `asset` and `benchmark` have different lengths.
+/// - **InvalidPeriod**: beta: `period` is below 2 or leaves no room for a full window of
+///   returns (`period + 1` prices are needed).
+///
+/// ## Returns
+/// - **`Ok(Vec<f64>)`** matching the input length. The first `period` values are `NaN`,
+///   as are windows containing a `NaN` return or a flat benchmark (zero variance).
+/// - **`Err(BetaError)`** otherwise.
+use crate::utilities::returns::simple_returns;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum BetaError {
+    #[error("beta: Empty data provided.")]
+    EmptyData,
+    #[error("beta: Asset and benchmark have different lengths: asset = {asset_len}, benchmark = {benchmark_len}")]
+    MismatchedLength {
+        asset_len: usize,
+        benchmark_len: usize,
+    },
+    #[error("beta: Invalid period: period = {period}, data length = {data_len}")]
+    InvalidPeriod { period: usize, data_len: usize },
+}
+
+#[inline]
+pub fn rolling_beta(
+    asset: &[f64],
+    benchmark: &[f64],
+    period: usize,
+) -> Result<Vec<f64>, BetaError> {
+    let len = asset.len();
+    if len == 0 {
+        return Err(BetaError::EmptyData);
+    }
+    if benchmark.len() != len {
+        return Err(BetaError::MismatchedLength {
+            asset_len: len,
+            benchmark_len: benchmark.len(),
+        });
+    }
+    if period < 2 || period >= len {
+        return Err(BetaError::InvalidPeriod {
+            period,
+            data_len: len,
+        });
+    }
+
+    let asset_returns = simple_returns(asset);
+    let benchmark_returns = simple_returns(benchmark);
+
+    let mut output = vec![f64::NAN; len];
+    for i in period..len {
+        let start = i + 1 - period;
+        let x = &benchmark_returns[start..=i];
+        let y = &asset_returns[start..=i];
+        if x.iter().chain(y).any(|v| v.is_nan()) {
+            continue;
+        }
+
+        let mean_x = x.iter().sum::<f64>() / period as f64;
+        let mean_y = y.iter().sum::<f64>() / period as f64;
+        let mut cov = 0.0;
+        let mut var = 0.0;
+        for (&xv, &yv) in x.iter().zip(y) {
+            cov += (xv - mean_x) * (yv - mean_y);
+            var += (xv - mean_x) * (xv - mean_x);
+        }
+        if var > 0.0 {
+            output[i] = cov / var;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::data_loader::read_candles_from_csv;
+
+    fn prices_from_returns(returns: &[f64]) -> Vec<f64> {
+        let mut prices = vec![100.0];
+        for r in returns {
+            let last = *prices.last().unwrap();
+            prices.push(last * (1.0 + r));
+        }
+        prices
+    }
+
+    #[test]
+    fn test_rolling_beta_double_benchmark() {
+        let benchmark_returns: Vec<f64> =
+            (0..60).map(|i| 0.01 * ((i as f64) * 0.7).sin()).collect();
+        let asset_returns: Vec<f64> = benchmark_returns.iter().map(|r| 2.0 * r).collect();
+        let benchmark = prices_from_returns(&benchmark_returns);
+        let asset = prices_from_returns(&asset_returns);
+
+        let beta = rolling_beta(&asset, &benchmark, 20).expect("Failed to calculate beta");
+        assert_eq!(beta.len(), asset.len());
+        assert!(beta[..20].iter().all(|v| v.is_nan()));
+        for (i, b) in beta.iter().enumerate().skip(20) {
+            assert!((b - 2.0).abs() < 1e-9, "Beta at {} was {}", i, b);
+        }
+    }
+
+    #[test]
+    fn test_rolling_beta_self_is_one() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let beta =
+            rolling_beta(&candles.close, &candles.close, 30).expect("Failed to calculate beta");
+        assert!(beta[30..].iter().all(|b| (b - 1.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_rolling_beta_errors() {
+        let prices = [1.0, 2.0, 3.0, 4.0];
+        assert!(matches!(
+            rolling_beta(&[], &[], 2),
+            Err(BetaError::EmptyData)
+        ));
+        assert!(matches!(
+            rolling_beta(&prices, &prices[..3], 2),
+            Err(BetaError::MismatchedLength { .. })
+        ));
+        assert!(matches!(
+            rolling_beta(&prices, &prices, 1),
+            Err(BetaError::InvalidPeriod { .. })
+        ));
+        assert!(matches!(
+            rolling_beta(&prices, &prices, 4),
+            Err(BetaError::InvalidPeriod { .. })
+        ));
+
+        let flat = [5.0; 4];
+        let beta = rolling_beta(&prices, &flat, 2).expect("Failed to calculate beta");
+        assert!(beta.iter().all(|v| v.is_nan()));
+    }
+}
@@ -14,11 +14,15 @@ use crate::indicators::moving_averages::ma::{ma, MaData};
 /// - **matype**: The name of the moving average type (e.g., "sma", "ema"). Defaults to "sma".
 /// - **devtype**: The deviation calculation type (e.g., 0 => std dev, 1 => mean_ad, 2 => median_ad).
 ///   Defaults to 0 (standard deviation).
+/// - **ddof**: Delta degrees of freedom for the standard deviation (`devtype` 0 only):
+///   0 divides by `period` (population), 1 by `period - 1` (sample). Defaults to 0,
+///   the population deviation used so far.
 ///
 /// ## Errors
 /// - **EmptyData**: bollinger_bands: Input data slice is empty.
 /// - **InvalidPeriod**: bollinger_bands: `period` is zero or exceeds the data length.
 /// - **AllValuesNaN**: bollinger_bands: All input data values are `NaN`.
+/// - **InvalidDdof**: bollinger_bands: `ddof` is not smaller than `period`.
 ///
 /// ## Returns
 /// - **`Ok(BollingerBandsOutput)`** containing three `Vec<f64>` matching input length:
@@ -27,6 +31,7 @@ use crate::indicators::moving_averages::ma::{ma, MaData};
 ///   - `lower_band`: `middle_band - devdn * deviation`.
 /// - **`Err(BollingerBandsError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -52,6 +57,7 @@ pub struct BollingerBandsParams {
     pub devdn: Option<f64>,
     pub matype: Option<String>,
     pub devtype: Option<usize>,
+    pub ddof: Option<usize>,
 }
 
 impl Default for BollingerBandsParams {
@@ -62,6 +68,7 @@ impl Default for BollingerBandsParams {
             devdn: Some(2.0),
             matype: Some("sma".to_string()),
             devtype: Some(0),
+            ddof: Some(0),
         }
     }
 }
@@ -131,6 +138,12 @@ impl<'a> BollingerBandsInput<'a> {
             .devtype
             .unwrap_or_else(|| BollingerBandsParams::default().devtype.unwrap())
     }
+
+    pub fn get_ddof(&self) -> usize {
+        self.params
+            .ddof
+            .unwrap_or_else(|| BollingerBandsParams::default().ddof.unwrap())
+    }
 }
 
 #[derive(Debug, Error)]
@@ -145,6 +158,8 @@ pub enum BollingerBandsError {
     UnderlyingFunctionFailed(String),
     #[error("bollinger_bands: Not enough valid data for period: needed={needed}, valid={valid}")]
     NotEnoughValidData { needed: usize, valid: usize },
+    #[error("bollinger_bands: Invalid ddof: ddof = {ddof}, period = {period}")]
+    InvalidDdof { ddof: usize, period: usize },
 }
 
 #[inline]
@@ -171,6 +186,7 @@ pub fn bollinger_bands(
         None => return Err(BollingerBandsError::AllValuesNaN),
     };
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("bollinger_bands", period, data.len() - first_valid_idx);
         return Err(BollingerBandsError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -181,6 +197,15 @@ pub fn bollinger_bands(
     let devdn = input.get_devdn();
     let matype = input.get_matype();
     let devtype = input.get_devtype();
+    let ddof = input.get_ddof();
+    if ddof >= period {
+        return Err(BollingerBandsError::InvalidDdof { ddof, period });
+    }
+    let dev_scale = if devtype == 0 {
+        (period as f64 / (period - ddof) as f64).sqrt()
+    } else {
+        1.0
+    };
 
     let ma_data = match &input.data {
         BollingerBandsData::Candles { candles, source } => MaData::Candles { candles, source },
@@ -204,8 +229,9 @@ pub fn bollinger_bands(
 
     for i in (first_valid_idx + period - 1)..data.len() {
         middle_band[i] = middle[i];
-        upper_band[i] = middle[i] + devup * dev_values[i];
-        lower_band[i] = middle[i] - devdn * dev_values[i];
+        let dev = dev_scale * dev_values[i];
+        upper_band[i] = middle[i] + devup * dev;
+        lower_band[i] = middle[i] - devdn * dev;
     }
 
     Ok(BollingerBandsOutput {
@@ -231,6 +257,7 @@ mod tests {
             devdn: None,
             matype: Some("sma".to_string()),
             devtype: None,
+            ddof: None,
         };
         let input_partial =
             BollingerBandsInput::from_candles(&candles, "close", partial_params.clone());
@@ -479,4 +506,42 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_bollinger_bands_ddof() {
+        let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let run = |ddof| {
+            let params = BollingerBandsParams {
+                period: Some(8),
+                devup: Some(1.0),
+                devdn: Some(1.0),
+                ddof,
+                ..BollingerBandsParams::default()
+            };
+            bollinger_bands(&BollingerBandsInput::from_slice(&data, params))
+                .expect("Failed to calculate Bollinger Bands")
+        };
+
+        let population = run(None);
+        let sample = run(Some(1));
+        let last = data.len() - 1;
+        assert!((population.middle_band[last] - 5.0).abs() < 1e-12);
+        assert!((population.upper_band[last] - 7.0).abs() < 1e-9);
+        assert!((population.lower_band[last] - 3.0).abs() < 1e-9);
+
+        let sample_sd = (32.0_f64 / 7.0).sqrt();
+        assert!((sample.upper_band[last] - (5.0 + sample_sd)).abs() < 1e-9);
+        assert!((sample.lower_band[last] - (5.0 - sample_sd)).abs() < 1e-9);
+        assert_eq!(sample.middle_band[last], population.middle_band[last]);
+
+        let bad = BollingerBandsParams {
+            period: Some(8),
+            ddof: Some(8),
+            ..BollingerBandsParams::default()
+        };
+        assert!(matches!(
+            bollinger_bands(&BollingerBandsInput::from_slice(&data, bad)),
+            Err(BollingerBandsError::InvalidDdof { .. })
+        ));
+    }
 }
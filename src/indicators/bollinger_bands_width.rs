@@ -27,6 +27,7 @@ use crate::indicators::moving_averages::ma::{ma, MaData};
 ///   - `values[i] = (upper_band[i] - lower_band[i]) / middle_band[i]`
 /// - **`Err(BollingerBandsWidthError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -171,6 +172,11 @@ pub fn bollinger_bands_width(
         None => return Err(BollingerBandsWidthError::AllValuesNaN),
     };
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data(
+            "bollinger_bands_width",
+            period,
+            data.len() - first_valid_idx,
+        );
         return Err(BollingerBandsWidthError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
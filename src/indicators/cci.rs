@@ -3,17 +3,23 @@
 /// Commodity Channel Index is typically calculated as:
 ///
 /// ```text
-/// CCI_t = (price_t - SMA(price, period)) / (0.015 * MeanAbsoluteDeviation(price, period))
+/// CCI_t = (price_t - MA(price, period)) / (constant * MeanAbsoluteDeviation(price, period))
 /// ```
 ///
 /// where `price_t` is often the "typical price" ((H+L+C)/3), but it can be any data source.
+/// The mean absolute deviation is taken around the moving average value of each window.
 ///
 /// ## Parameters
 /// - **period**: Window size (number of data points). Defaults to 14.
-///  
+/// - **constant**: Scaling constant in the denominator. Defaults to 0.015 (Lambert).
+/// - **ma_type**: Moving average used for the centre line, any type supported by
+///   `ma(...)`. Defaults to `"sma"`.
+///
 /// ## Errors
 /// - **EmptyData**: cci: Input data slice is empty.
 /// - **InvalidPeriod**: cci: `period` is zero or exceeds the data length.
+/// - **InvalidConstant**: cci: `constant` is zero, negative, or not finite.
+/// - **MaError**: cci: Underlying error from the `ma(...)` function.
 /// - **NotEnoughValidData**: cci: Fewer than `period` valid (non-`NaN`) data points remain
 ///   after the first valid index.
 /// - **AllValuesNaN**: cci: All input data values are `NaN`.
@@ -22,7 +28,10 @@
 /// - **`Ok(CciOutput)`** on success, containing a `Vec<f64>` matching the input length,
 ///   with leading `NaN`s until the moving average window is filled.
 /// - **`Err(CciError)`** otherwise.
+use crate::indicators::moving_averages::ma::{ma, MaData};
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
+use std::error::Error;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -42,11 +51,17 @@ pub struct CciOutput {
 #[derive(Debug, Clone)]
 pub struct CciParams {
     pub period: Option<usize>,
+    pub constant: Option<f64>,
+    pub ma_type: Option<String>,
 }
 
 impl Default for CciParams {
     fn default() -> Self {
-        Self { period: Some(14) }
+        Self {
+            period: Some(14),
+            constant: Some(0.015),
+            ma_type: Some("sma".to_string()),
+        }
     }
 }
 
@@ -86,6 +101,19 @@ impl<'a> CciInput<'a> {
             .period
             .unwrap_or_else(|| CciParams::default().period.unwrap())
     }
+
+    pub fn get_constant(&self) -> f64 {
+        self.params
+            .constant
+            .unwrap_or_else(|| CciParams::default().constant.unwrap())
+    }
+
+    pub fn get_ma_type(&self) -> String {
+        self.params
+            .ma_type
+            .clone()
+            .unwrap_or_else(|| CciParams::default().ma_type.unwrap())
+    }
 }
 
 #[derive(Debug, Error)]
@@ -98,6 +126,10 @@ pub enum CciError {
     NotEnoughValidData { needed: usize, valid: usize },
     #[error("cci: All values are NaN.")]
     AllValuesNaN,
+    #[error("cci: Invalid constant: {constant}")]
+    InvalidConstant { constant: f64 },
+    #[error("cci: Moving average error: {0}")]
+    MaError(#[from] Box<dyn Error>),
 }
 
 #[inline]
@@ -125,50 +157,40 @@ pub fn cci(input: &CciInput) -> Result<CciOutput, CciError> {
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("cci", period, data.len() - first_valid_idx);
         return Err(CciError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
         });
     }
 
-    let mut cci_values = vec![f64::NAN; data.len()];
-    let inv_period = 1.0 / (period as f64);
-
-    let mut sum = 0.0;
-    for &val in &data[first_valid_idx..(first_valid_idx + period)] {
-        sum += val;
+    let constant = input.get_constant();
+    if !constant.is_finite() || constant <= 0.0 {
+        return Err(CciError::InvalidConstant { constant });
     }
-    let mut current_sma = sum * inv_period;
 
-    let mut sum_abs_dev = 0.0;
-    for &val in &data[first_valid_idx..(first_valid_idx + period)] {
-        sum_abs_dev += (val - current_sma).abs();
-    }
+    let ma_type = input.get_ma_type();
+    let centre = ma(&ma_type, MaData::Slice(data), period)?;
 
+    let mut cci_values = vec![f64::NAN; data.len()];
+    let inv_period = 1.0 / (period as f64);
     let first_output_idx = first_valid_idx + period - 1;
-    let first_price = data[first_output_idx];
-    cci_values[first_output_idx] = if sum_abs_dev == 0.0 {
-        0.0
-    } else {
-        (first_price - current_sma) / (0.015 * (sum_abs_dev * inv_period))
-    };
 
-    for i in (first_output_idx + 1)..data.len() {
-        let exiting_val = data[i - period];
-        let entering_val = data[i];
-
-        sum = sum - exiting_val + entering_val;
-        current_sma = sum * inv_period;
+    for i in first_output_idx..data.len() {
+        let current_ma = centre[i];
+        if current_ma.is_nan() {
+            continue;
+        }
 
-        sum_abs_dev = 0.0;
-        for &val in &data[(i - period + 1)..=i] {
-            sum_abs_dev += (val - current_sma).abs();
+        let mut sum_abs_dev = 0.0;
+        for &val in &data[(i + 1 - period)..=i] {
+            sum_abs_dev += (val - current_ma).abs();
         }
 
         cci_values[i] = if sum_abs_dev == 0.0 {
             0.0
         } else {
-            (entering_val - current_sma) / (0.015 * (sum_abs_dev * inv_period))
+            (data[i] - current_ma) / (constant * (sum_abs_dev * inv_period))
         };
     }
 
@@ -185,17 +207,26 @@ mod tests {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
         let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
 
-        let default_params = CciParams { period: None };
+        let default_params = CciParams {
+            period: None,
+            ..CciParams::default()
+        };
         let input_default = CciInput::from_candles(&candles, "close", default_params);
         let output_default = cci(&input_default).expect("Failed CCI with default params");
         assert_eq!(output_default.values.len(), candles.close.len());
 
-        let params_20 = CciParams { period: Some(20) };
+        let params_20 = CciParams {
+            period: Some(20),
+            ..CciParams::default()
+        };
         let input_20 = CciInput::from_candles(&candles, "hl2", params_20);
         let output_20 = cci(&input_20).expect("Failed CCI with period=20, source=hl2");
         assert_eq!(output_20.values.len(), candles.close.len());
 
-        let params_custom = CciParams { period: Some(9) };
+        let params_custom = CciParams {
+            period: Some(9),
+            ..CciParams::default()
+        };
         let input_custom = CciInput::from_candles(&candles, "hlc3", params_custom);
         let output_custom = cci(&input_custom).expect("Failed CCI fully custom");
         assert_eq!(output_custom.values.len(), candles.close.len());
@@ -277,7 +308,10 @@ mod tests {
     #[test]
     fn test_cci_with_zero_period() {
         let input_data = [10.0, 20.0, 30.0];
-        let params = CciParams { period: Some(0) };
+        let params = CciParams {
+            period: Some(0),
+            ..CciParams::default()
+        };
         let input = CciInput::from_slice(&input_data, params);
         let result = cci(&input);
 
@@ -294,7 +328,10 @@ mod tests {
     #[test]
     fn test_cci_with_period_exceeding_data_length() {
         let input_data = [10.0, 20.0, 30.0];
-        let params = CciParams { period: Some(10) };
+        let params = CciParams {
+            period: Some(10),
+            ..CciParams::default()
+        };
         let input = CciInput::from_slice(&input_data, params);
 
         let result = cci(&input);
@@ -304,7 +341,10 @@ mod tests {
     #[test]
     fn test_cci_very_small_data_set() {
         let input_data = [42.0];
-        let params = CciParams { period: Some(9) };
+        let params = CciParams {
+            period: Some(9),
+            ..CciParams::default()
+        };
         let input = CciInput::from_slice(&input_data, params);
 
         let result = cci(&input);
@@ -319,7 +359,10 @@ mod tests {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
         let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
 
-        let first_params = CciParams { period: Some(14) };
+        let first_params = CciParams {
+            period: Some(14),
+            ..CciParams::default()
+        };
         let first_input = CciInput::from_candles(&candles, "close", first_params);
         let first_result = cci(&first_input).expect("Failed to calculate first CCI");
         assert_eq!(
@@ -328,7 +371,10 @@ mod tests {
             "First CCI output length mismatch"
         );
 
-        let second_params = CciParams { period: Some(14) };
+        let second_params = CciParams {
+            period: Some(14),
+            ..CciParams::default()
+        };
         let second_input = CciInput::from_slice(&first_result.values, second_params);
         let second_result = cci(&second_input).expect("Failed to calculate second CCI");
         assert_eq!(
@@ -357,6 +403,7 @@ mod tests {
         let period = 14;
         let params = CciParams {
             period: Some(period),
+            ..CciParams::default()
         };
         let input = CciInput::from_candles(&candles, "close", params);
         let cci_result = cci(&input).expect("Failed to calculate CCI");
@@ -376,4 +423,59 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_cci_custom_constant_scales_output() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let default_input = CciInput::with_default_candles(&candles);
+        let default_output = cci(&default_input).expect("Failed CCI with default constant");
+
+        let params = CciParams {
+            constant: Some(0.01),
+            ..CciParams::default()
+        };
+        let input = CciInput::from_candles(&candles, "hlc3", params);
+        let output = cci(&input).expect("Failed CCI with constant 0.01");
+
+        for (i, (&scaled, &base)) in output.values.iter().zip(&default_output.values).enumerate() {
+            if base.is_nan() {
+                assert!(scaled.is_nan(), "Expected NaN at index {}", i);
+                continue;
+            }
+            assert!(
+                (scaled - base * 1.5).abs() < 1e-9,
+                "Expected 1.5x scaling at index {}: base {}, got {}",
+                i,
+                base,
+                scaled
+            );
+        }
+    }
+
+    #[test]
+    fn test_cci_ma_type_and_invalid_constant() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let params = CciParams {
+            ma_type: Some("ema".to_string()),
+            ..CciParams::default()
+        };
+        let input = CciInput::from_candles(&candles, "hlc3", params);
+        let ema_output = cci(&input).expect("Failed CCI with EMA centre line");
+        let default_output =
+            cci(&CciInput::with_default_candles(&candles)).expect("Failed default CCI");
+        let last = candles.close.len() - 1;
+        assert!(ema_output.values[last].is_finite());
+        assert!((ema_output.values[last] - default_output.values[last]).abs() > 1e-6);
+
+        let params = CciParams {
+            constant: Some(0.0),
+            ..CciParams::default()
+        };
+        let input = CciInput::from_candles(&candles, "hlc3", params);
+        assert!(matches!(cci(&input), Err(CciError::InvalidConstant { .. })));
+    }
 }
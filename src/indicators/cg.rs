@@ -25,13 +25,14 @@
 /// - **EmptyData**: cg: Input data slice is empty.
 /// - **InvalidPeriod**: cg: `period` is zero or exceeds the data length.
 /// - **AllValuesNaN**: cg: All input data values are `NaN`.
-/// - **NotEnoughValidData**: cg: Fewer than `period` valid (non-`NaN`) data points remain
-///   after the first valid index.
+/// - **NotEnoughValidData**: cg: Fewer than `period + 1` valid (non-`NaN`) data
+///   points remain after the first valid index.
 ///
 /// ## Returns
 /// - **`Ok(CgOutput)`** on success, containing a `Vec<f64>` matching input length,
 ///   with leading `NaN` until the warm-up period is reached.
 /// - **`Err(CgError)`** otherwise.
+use crate::indicators::error::{require_min_len, CommonError};
 use crate::utilities::data_loader::{source_type, Candles};
 use thiserror::Error;
 
@@ -110,6 +111,16 @@ pub enum CgError {
     NotEnoughValidData { needed: usize, valid: usize },
 }
 
+impl From<CommonError> for CgError {
+    fn from(err: CommonError) -> Self {
+        match err {
+            CommonError::NotEnoughValidData { needed, valid, .. } => {
+                CgError::NotEnoughValidData { needed, valid }
+            }
+        }
+    }
+}
+
 #[inline]
 pub fn cg(input: &CgInput) -> Result<CgOutput, CgError> {
     let data: &[f64] = match &input.data {
@@ -132,12 +143,7 @@ pub fn cg(input: &CgInput) -> Result<CgOutput, CgError> {
         Some(idx) => idx,
         None => return Err(CgError::AllValuesNaN),
     };
-    if (data.len() - first_valid_idx) < (period + 1) {
-        return Err(CgError::NotEnoughValidData {
-            needed: period + 1,
-            valid: data.len() - first_valid_idx,
-        });
-    }
+    require_min_len(data.len() - first_valid_idx, period + 1, "cg")?;
 
     let mut cg_values = vec![f64::NAN; data.len()];
 
@@ -278,6 +284,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cg_not_enough_valid_data() {
+        let data = [1.0; 10];
+        let input = CgInput::from_slice(&data, CgParams { period: Some(10) });
+        let err = cg(&input).expect_err("CG needs period + 1 points");
+        assert!(matches!(
+            err,
+            CgError::NotEnoughValidData {
+                needed: 11,
+                valid: 10
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "cg: Not enough valid data: needed = 11, valid = 10"
+        );
+    }
+
     #[test]
     fn test_cg_nan_check() {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
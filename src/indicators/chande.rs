@@ -28,6 +28,7 @@
 ///   with `NaN` values for indices where insufficient data is available.
 /// - **`Err(ChandeError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -182,6 +183,7 @@ pub fn chande(input: &ChandeInput) -> Result<ChandeOutput, ChandeError> {
         });
     }
     if period > len {
+        not_enough_valid_data("chande", period, len);
         return Err(ChandeError::NotEnoughData {
             needed: period,
             available: len,
@@ -33,7 +33,11 @@ use crate::indicators::utility_functions::{max_rolling, min_rolling, sum_rolling
 /// - **`Ok(ChopOutput)`** on success, containing a `Vec<f64>` with the same length as the input,
 ///   filled with leading `NaN` until the rolling window is fully available.
 /// - **`Err(ChopError)`** otherwise.
+///
+/// [`chop_regime`] bins the values into trending, choppy and neutral regimes, by default
+/// at the Fibonacci levels 38.2 and 61.8 (with `scalar` = 100).
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 use thiserror::Error;
 
@@ -121,6 +125,44 @@ pub struct ChopOutput {
     pub values: Vec<f64>,
 }
 
+impl ChopOutput {
+    /// [`chop_regime`] of the values at the default Fibonacci thresholds.
+    pub fn regime(&self) -> Vec<ChopRegime> {
+        chop_regime(&self.values, CHOP_TREND_THRESHOLD, CHOP_CHOPPY_THRESHOLD)
+    }
+}
+
+/// Default upper bound of the trending regime.
+pub const CHOP_TREND_THRESHOLD: f64 = 38.2;
+/// Default lower bound of the choppy regime.
+pub const CHOP_CHOPPY_THRESHOLD: f64 = 61.8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChopRegime {
+    Trending,
+    Choppy,
+    Neutral,
+}
+
+/// Classifies CHOP values: below `trend_thresh` is [`ChopRegime::Trending`], above
+/// `chop_thresh` is [`ChopRegime::Choppy`], and anything in between (including values
+/// equal to a threshold and `NaN` warmup bars) is [`ChopRegime::Neutral`].
+#[inline]
+pub fn chop_regime(values: &[f64], trend_thresh: f64, chop_thresh: f64) -> Vec<ChopRegime> {
+    values
+        .iter()
+        .map(|&v| {
+            if v < trend_thresh {
+                ChopRegime::Trending
+            } else if v > chop_thresh {
+                ChopRegime::Choppy
+            } else {
+                ChopRegime::Neutral
+            }
+        })
+        .collect()
+}
+
 #[derive(Debug, Error)]
 pub enum ChopError {
     #[error("chop: Empty data provided.")]
@@ -175,6 +217,7 @@ pub fn chop(input: &ChopInput) -> Result<ChopOutput, ChopError> {
         None => return Err(ChopError::AllValuesNaN),
     };
     if (len - first_valid_idx) < period {
+        not_enough_valid_data("chop", period, len - first_valid_idx);
         return Err(ChopError::NotEnoughValidData {
             needed: period,
             valid: len - first_valid_idx,
@@ -349,6 +392,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_chop_regime() {
+        let values = [70.0, 30.0, 50.0, f64::NAN, 61.8, 38.2];
+        let regimes = chop_regime(&values, CHOP_TREND_THRESHOLD, CHOP_CHOPPY_THRESHOLD);
+        assert_eq!(
+            regimes,
+            vec![
+                ChopRegime::Choppy,
+                ChopRegime::Trending,
+                ChopRegime::Neutral,
+                ChopRegime::Neutral,
+                ChopRegime::Neutral,
+                ChopRegime::Neutral,
+            ]
+        );
+        assert_eq!(chop_regime(&[50.0], 55.0, 60.0), vec![ChopRegime::Trending]);
+
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let output = chop(&ChopInput::with_default_candles(&candles)).expect("Failed CHOP");
+        let regimes = output.regime();
+        assert_eq!(regimes.len(), output.values.len());
+        assert_eq!(regimes[0], ChopRegime::Neutral);
+        assert!(regimes.contains(&ChopRegime::Trending));
+        assert!(regimes.contains(&ChopRegime::Choppy));
+    }
+
     #[test]
     fn test_chop_params_default() {
         let defaults = ChopParams::default();
@@ -21,6 +21,7 @@
 ///
 /// `Err(CkspError)` otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -146,6 +147,7 @@ pub fn cksp(input: &CkspInput) -> Result<CkspOutput, CkspError> {
         return Err(CkspError::NoData);
     }
     if p > size || q > size {
+        not_enough_valid_data("cksp", p.max(q), size);
         return Err(CkspError::NotEnoughData {
             p,
             q,
@@ -22,6 +22,7 @@
 ///   (leading `NaN`s until there's enough data to compute CMO).
 /// - **`Err(CmoError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -122,6 +123,7 @@ pub fn cmo(input: &CmoInput) -> Result<CmoOutput, CmoError> {
     };
 
     if (data_len - first_valid_idx) < period {
+        not_enough_valid_data("cmo", period, data_len - first_valid_idx);
         return Err(CmoError::NotEnoughValidData {
             needed: period,
             valid: data_len - first_valid_idx,
@@ -25,7 +25,8 @@ use crate::indicators::moving_averages::ma::{ma, MaData}; // <--- Adjust path as
 /// ## Errors
 /// - **EmptyData**: Input data slice is empty.
 /// - **AllValuesNaN**: All data values are `NaN`.
-/// - **NotEnoughValidData**: Not enough valid data to compute at least one output.
+/// - **NotEnoughValidData**: Fewer than `max(short, long) + ma_period` valid points, so no
+///   smoothed output could be produced.
 /// - **InvalidPeriod**: Zero or out-of-bounds short/long/MA periods.
 /// - **MaError**: Underlying error from the `ma(...)` function.
 ///
@@ -34,6 +35,7 @@ use crate::indicators::moving_averages::ma::{ma, MaData}; // <--- Adjust path as
 ///   with leading `NaN`s until the earliest valid index.
 /// - `Err(CoppockError)` otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::error::Error;
 use thiserror::Error;
 
@@ -185,9 +187,11 @@ pub fn coppock(input: &CoppockInput) -> Result<CoppockOutput, CoppockError> {
     };
 
     let largest_roc = short.max(long);
-    if (data_len - first_valid_idx) < largest_roc {
+    let needed = largest_roc + ma_p;
+    if (data_len - first_valid_idx) < needed {
+        not_enough_valid_data("coppock", needed, data_len - first_valid_idx);
         return Err(CoppockError::NotEnoughValidData {
-            needed: largest_roc,
+            needed,
             valid: data_len - first_valid_idx,
         });
     }
@@ -326,6 +330,50 @@ mod tests {
         assert!(result2.is_err(), "Expected error for short/long>data.len()");
     }
 
+    #[test]
+    fn test_coppock_nonstandard_roc_periods() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let default_input = CoppockInput::with_default_candles(&candles);
+        let default_result = coppock(&default_input).expect("Failed default Coppock");
+
+        let custom_params = CoppockParams {
+            short_roc_period: Some(6),
+            long_roc_period: Some(21),
+            ..CoppockParams::default()
+        };
+        let custom_input = CoppockInput::from_candles(&candles, "close", custom_params);
+        let custom_result = coppock(&custom_input).expect("Failed custom Coppock");
+        assert_eq!(custom_result.values.len(), default_result.values.len());
+
+        let warmup = 21 + 10;
+        for (i, &val) in custom_result.values.iter().enumerate().skip(warmup) {
+            assert!(val.is_finite(), "Expected finite value at index {}", i);
+        }
+        let last = custom_result.values.len() - 1;
+        assert!(
+            (custom_result.values[last] - default_result.values[last]).abs() > 1e-6,
+            "Expected nonstandard ROC periods to change the output"
+        );
+    }
+
+    #[test]
+    fn test_coppock_ma_period_counts_toward_needed_data() {
+        let data: Vec<f64> = (1..=20).map(|x| x as f64).collect();
+        let input = CoppockInput::from_slice(&data, CoppockParams::default());
+        match coppock(&input) {
+            Err(CoppockError::NotEnoughValidData { needed, valid }) => {
+                assert_eq!(needed, 24);
+                assert_eq!(valid, 20);
+            }
+            other => panic!(
+                "Expected NotEnoughValidData, got {:?}",
+                other.map(|o| o.values)
+            ),
+        }
+    }
+
     #[test]
     fn test_coppock_all_nan() {
         let data = [f64::NAN, f64::NAN, f64::NAN];
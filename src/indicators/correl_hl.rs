@@ -19,6 +19,7 @@
 ///   with leading `NaN`s until the rolling window is filled.
 /// - **`Err(CorrelHlError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -133,6 +134,7 @@ pub fn correl_hl(input: &CorrelHlInput) -> Result<CorrelHlOutput, CorrelHlError>
     };
 
     if (high.len() - first_valid_idx) < period {
+        not_enough_valid_data("correl_hl", period, high.len() - first_valid_idx);
         return Err(CorrelHlError::NotEnoughValidData {
             needed: period,
             valid: high.len() - first_valid_idx,
@@ -24,6 +24,7 @@
 ///   with leading `NaN` (for `real`, `imag`, `angle`) and `0.0` (for `state`) values until the correlation window is filled.
 /// - **`Err(CorrelationCycleError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -142,6 +143,7 @@ pub fn correlation_cycle(
     }
     let valid_count = data.iter().filter(|&&x| !x.is_nan()).count();
     if valid_count < period {
+        not_enough_valid_data("correlation_cycle", period, valid_count);
         return Err(CorrelationCycleError::NotEnoughValidData {
             needed: period,
             valid: valid_count,
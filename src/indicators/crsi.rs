@@ -0,0 +1,283 @@
+/// # Connors RSI (CRSI)
+///
+/// Connors RSI averages three short-term momentum readings into a single 0–100
+/// oscillator:
+///
+/// - **price RSI**: a Wilder RSI of the source over `rsi_period` bars.
+/// - **streak RSI**: a Wilder RSI over `streak_period` bars of the up/down streak, which
+///   counts consecutive rising bars as `1, 2, 3, ...`, consecutive falling bars as
+///   `-1, -2, -3, ...`, and resets to `0` on an unchanged bar.
+/// - **percent rank**: the percentage of the previous `rank_period` one-bar returns that
+///   are strictly below the current one-bar return.
+///
+/// ## Parameters
+/// - **rsi_period**: RSI period for the price component. Defaults to 3.
+/// - **streak_period**: RSI period for the streak component. Defaults to 2.
+/// - **rank_period**: Lookback of the percent rank. Defaults to 100.
+///
+/// ## Errors
+/// - **EmptyData**: crsi: Input data slice is empty.
+/// - **InvalidPeriod**: crsi: `rank_period` is zero, or the combined warmup
+///   `max(rsi_period, streak_period, rank_period + 1)` leaves no bar with a finite value.
+/// - **RsiError**: crsi: Error from either RSI component (e.g. an invalid period).
+///
+/// ## Returns
+/// - **`Ok(CrsiOutput)`** on success, containing `values` and the three components
+///   (`price_rsi`, `streak_rsi`, `percent_rank`), each matching the input length.
+///   `values` is `NaN` until all three components are available (`rank_period + 1`
+///   bars with the defaults).
+/// - **`Err(CrsiError)`** otherwise.
+use crate::indicators::rsi::{rsi, RsiError, RsiInput, RsiParams};
+use crate::utilities::data_loader::{Candles, SourceData};
+use thiserror::Error;
+
+pub type CrsiData<'a> = SourceData<'a>;
+
+#[derive(Debug, Clone)]
+pub struct CrsiOutput {
+    pub values: Vec<f64>,
+    pub price_rsi: Vec<f64>,
+    pub streak_rsi: Vec<f64>,
+    pub percent_rank: Vec<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CrsiParams {
+    pub rsi_period: Option<usize>,
+    pub streak_period: Option<usize>,
+    pub rank_period: Option<usize>,
+}
+
+impl Default for CrsiParams {
+    fn default() -> Self {
+        Self {
+            rsi_period: Some(3),
+            streak_period: Some(2),
+            rank_period: Some(100),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CrsiInput<'a> {
+    pub data: CrsiData<'a>,
+    pub params: CrsiParams,
+}
+
+impl<'a> CrsiInput<'a> {
+    pub fn from_candles(candles: &'a Candles, source: &'a str, params: CrsiParams) -> Self {
+        Self {
+            data: CrsiData::Candles { candles, source },
+            params,
+        }
+    }
+
+    pub fn from_slice(slice: &'a [f64], params: CrsiParams) -> Self {
+        Self {
+            data: CrsiData::Slice(slice),
+            params,
+        }
+    }
+
+    pub fn with_default_candles(candles: &'a Candles) -> Self {
+        Self {
+            data: CrsiData::Candles {
+                candles,
+                source: "close",
+            },
+            params: CrsiParams::default(),
+        }
+    }
+
+    pub fn get_rsi_period(&self) -> usize {
+        self.params
+            .rsi_period
+            .unwrap_or_else(|| CrsiParams::default().rsi_period.unwrap())
+    }
+
+    pub fn get_streak_period(&self) -> usize {
+        self.params
+            .streak_period
+            .unwrap_or_else(|| CrsiParams::default().streak_period.unwrap())
+    }
+
+    pub fn get_rank_period(&self) -> usize {
+        self.params
+            .rank_period
+            .unwrap_or_else(|| CrsiParams::default().rank_period.unwrap())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CrsiError {
+    #[error("crsi: Empty data provided.")]
+    EmptyData,
+    #[error("crsi: Invalid rank period: period = {period}, data length = {data_len}")]
+    InvalidPeriod { period: usize, data_len: usize },
+    #[error(transparent)]
+    RsiError(#[from] RsiError),
+}
+
+#[inline]
+fn up_down_streak(data: &[f64]) -> Vec<f64> {
+    let mut streak = vec![0.0_f64; data.len()];
+    for i in 1..data.len() {
+        streak[i] = if data[i] > data[i - 1] {
+            streak[i - 1].max(0.0) + 1.0
+        } else if data[i] < data[i - 1] {
+            streak[i - 1].min(0.0) - 1.0
+        } else {
+            0.0
+        };
+    }
+    streak
+}
+
+#[inline]
+fn return_percent_rank(data: &[f64], period: usize) -> Vec<f64> {
+    let len = data.len();
+    let mut returns = vec![f64::NAN; len];
+    for i in 1..len {
+        returns[i] = (data[i] - data[i - 1]) / data[i - 1];
+    }
+
+    let mut rank = vec![f64::NAN; len];
+    for i in (period + 1)..len {
+        let current = returns[i];
+        if current.is_nan() {
+            continue;
+        }
+        let below = returns[i - period..i]
+            .iter()
+            .filter(|&&r| r < current)
+            .count();
+        rank[i] = 100.0 * below as f64 / period as f64;
+    }
+    rank
+}
+
+#[inline]
+pub fn crsi(input: &CrsiInput) -> Result<CrsiOutput, CrsiError> {
+    let data: &[f64] = input.data.resolve();
+    let len = data.len();
+    if len == 0 {
+        return Err(CrsiError::EmptyData);
+    }
+
+    let rank_period = input.get_rank_period();
+    let warmup = input
+        .get_rsi_period()
+        .max(input.get_streak_period())
+        .max(rank_period + 1);
+    if rank_period == 0 || warmup >= len {
+        return Err(CrsiError::InvalidPeriod {
+            period: rank_period,
+            data_len: len,
+        });
+    }
+
+    let price_rsi = rsi(&RsiInput::from_slice(
+        data,
+        RsiParams {
+            period: Some(input.get_rsi_period()),
+        },
+    ))?
+    .values;
+
+    let streak = up_down_streak(data);
+    let streak_rsi = rsi(&RsiInput::from_slice(
+        &streak,
+        RsiParams {
+            period: Some(input.get_streak_period()),
+        },
+    ))?
+    .values;
+
+    let percent_rank = return_percent_rank(data, rank_period);
+
+    let values = price_rsi
+        .iter()
+        .zip(&streak_rsi)
+        .zip(&percent_rank)
+        .map(|((a, b), c)| (a + b + c) / 3.0)
+        .collect();
+
+    Ok(CrsiOutput {
+        values,
+        price_rsi,
+        streak_rsi,
+        percent_rank,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::data_loader::read_candles_from_csv;
+
+    #[test]
+    fn test_crsi_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = CrsiInput::with_default_candles(&candles);
+        let result = crsi(&input).expect("Failed to calculate CRSI");
+        assert_eq!(result.values.len(), candles.close.len());
+
+        // Reference: TA-Lib 0.4.0 TA_RSI(close, 3) and TA_RSI(streak, 2) averaged with an
+        // independently computed 100-bar percent rank of one-bar returns.
+        let expected_last_five = [
+            51.32038807278379,
+            37.55989304465696,
+            28.456074708130412,
+            58.719739272564816,
+            23.03823461672476,
+        ];
+        let start = result.values.len() - 5;
+        crate::assert_slice_close!(result.values[start..], expected_last_five, 1e-8);
+
+        let last = result.values.len() - 1;
+        assert!((result.price_rsi[last] - 18.456822003069828).abs() < 1e-8);
+        assert!((result.streak_rsi[last] - 38.65788184710445).abs() < 1e-8);
+        assert_eq!(result.percent_rank[last], 12.0);
+
+        assert!(result.values[..101].iter().all(|v| v.is_nan()));
+        assert!(result.values[101..]
+            .iter()
+            .all(|v| (0.0..=100.0).contains(v)));
+    }
+
+    #[test]
+    fn test_crsi_streak() {
+        let data = [1.0, 2.0, 3.0, 3.0, 2.0, 1.0, 0.5, 1.0];
+        assert_eq!(
+            up_down_streak(&data),
+            vec![0.0, 1.0, 2.0, 0.0, -1.0, -2.0, -3.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn test_crsi_invalid_rank_period() {
+        let data = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let params = CrsiParams {
+            rank_period: Some(5),
+            ..CrsiParams::default()
+        };
+        let input = CrsiInput::from_slice(&data, params);
+        assert!(matches!(crsi(&input), Err(CrsiError::InvalidPeriod { .. })));
+
+        let params = CrsiParams {
+            rank_period: Some(data.len() - 1),
+            ..CrsiParams::default()
+        };
+        let input = CrsiInput::from_slice(&data, params);
+        assert!(matches!(crsi(&input), Err(CrsiError::InvalidPeriod { .. })));
+
+        let data = [1.0, 2.0, 1.5, 3.0, 2.5, 4.0];
+        let params = CrsiParams {
+            rank_period: Some(data.len() - 2),
+            ..CrsiParams::default()
+        };
+        let result = crsi(&CrsiInput::from_slice(&data, params)).expect("Failed to calculate CRSI");
+        assert!(result.values[data.len() - 1].is_finite());
+    }
+}
@@ -22,6 +22,7 @@
 ///   valid data point).
 /// - **`Err(CviError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -129,6 +130,7 @@ pub fn cvi(input: &CviInput) -> Result<CviOutput, CviError> {
 
     let needed = 2 * period - 1;
     if (high.len() - first_valid_idx) < needed {
+        not_enough_valid_data("cvi", needed, high.len() - first_valid_idx);
         return Err(CviError::NotEnoughValidData {
             needed,
             valid: high.len() - first_valid_idx,
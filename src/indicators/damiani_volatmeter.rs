@@ -23,6 +23,7 @@
 ///   the input length, each with leading `NaN`s until the required lookback windows are satisfied.
 /// - **`Err(DamianiVolatmeterError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -210,6 +211,7 @@ pub fn damiani_volatmeter(
         .unwrap_or(&0);
 
     if (length - first_valid_idx) < needed {
+        not_enough_valid_data("damiani_volatmeter", needed, length - first_valid_idx);
         return Err(DamianiVolatmeterError::NotEnoughValidData {
             needed,
             valid: length - first_valid_idx,
@@ -21,6 +21,7 @@
 ///   The first few values will be `NaN` until enough points are available (2-pole filter).
 /// - **`Err(DecOscError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::f64::consts::PI;
 use thiserror::Error;
 
@@ -142,6 +143,7 @@ pub fn dec_osc(input: &DecOscInput) -> Result<DecOscOutput, DecOscError> {
     };
 
     if (len - first_valid_idx) < 2 {
+        not_enough_valid_data("dec_osc", 2, len - first_valid_idx);
         return Err(DecOscError::NotEnoughValidData {
             needed: 2,
             valid: len - first_valid_idx,
@@ -21,6 +21,7 @@
 ///   with leading `NaN`s until the filter can be computed.
 /// - **`Err(DecyclerError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::f64::consts::PI;
 use thiserror::Error;
 
@@ -130,6 +131,7 @@ pub fn decycler(input: &DecyclerInput) -> Result<DecyclerOutput, DecyclerError>
     };
 
     if (data.len() - first_valid_idx) < hp_period {
+        not_enough_valid_data("decycler", hp_period, data.len() - first_valid_idx);
         return Err(DecyclerError::NotEnoughValidData {
             needed: hp_period,
             valid: data.len() - first_valid_idx,
@@ -2,6 +2,7 @@ use crate::indicators::deviation::{deviation, DevInput, DevParams};
 use crate::indicators::moving_averages::ma::{ma, MaData};
 use crate::indicators::utility_functions::{max_rolling, min_rolling};
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 /// # DevStop
 ///
 /// A stop indicator that uses a volatility measure to determine stop levels.
@@ -200,6 +201,11 @@ pub fn devstop(input: &DevStopInput) -> Result<DevStopOutput, DevStopError> {
     };
 
     if (high.len() - first_valid_idx) < period || (low.len() - first_valid_idx) < period {
+        not_enough_valid_data(
+            "devstop",
+            period,
+            (high.len() - first_valid_idx).min(low.len() - first_valid_idx),
+        );
         return Err(DevStopError::NotEnoughValidData {
             needed: period,
             valid: (high.len() - first_valid_idx).min(low.len() - first_valid_idx),
@@ -288,6 +294,7 @@ pub fn devstop(input: &DevStopInput) -> Result<DevStopOutput, DevStopError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::utility_functions::stop_distance_pct;
     use crate::utilities::data_loader::read_candles_from_csv;
 
     #[test]
@@ -478,4 +485,29 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_devstop_stop_distance_pct() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = DevStopInput::with_default_candles(&candles);
+        let output = devstop(&input).expect("Failed devstop with default params");
+
+        let distance = stop_distance_pct(&output.values, &candles.close);
+        assert_eq!(distance.len(), candles.close.len());
+        let first_valid = output
+            .values
+            .iter()
+            .position(|v| !v.is_nan())
+            .expect("devstop never warmed up");
+        assert!(distance[..first_valid].iter().all(|d| d.is_nan()));
+        for (i, &d) in distance.iter().enumerate().skip(first_valid) {
+            assert!(
+                d.is_finite() && d > 0.0,
+                "Distance at {} is not positive and finite: {}",
+                i,
+                d
+            );
+        }
+    }
 }
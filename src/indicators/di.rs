@@ -19,6 +19,7 @@
 ///   with leading `NaN`s until the calculation window is filled.
 /// - **`Err(DiError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum DiData<'a> {
@@ -144,6 +145,7 @@ pub fn di(input: &DiInput) -> Result<DiOutput, DiError> {
     };
 
     if (n - first_idx) < period {
+        not_enough_valid_data("di", period, n - first_idx);
         return Err(DiError::NotEnoughValidData {
             needed: period,
             valid: n - first_idx,
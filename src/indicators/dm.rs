@@ -21,6 +21,7 @@
 ///   with leading `NaN`s until the smoothing window is filled.
 /// - **`Err(DmError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -130,6 +131,7 @@ pub fn dm(input: &DmInput) -> Result<DmOutput, DmError> {
     };
 
     if (high.len() - first_valid_idx) < period {
+        not_enough_valid_data("dm", period, high.len() - first_valid_idx);
         return Err(DmError::NotEnoughValidData {
             needed: period,
             valid: high.len() - first_valid_idx,
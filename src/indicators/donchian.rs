@@ -8,6 +8,9 @@
 /// ## Parameters
 /// - **period**: The lookback window size for computing the highest high and lowest low.
 ///   Defaults to 20.
+/// - **exclude_current**: When `true`, the bands at bar `i` cover `[i - period, i - 1]`
+///   instead of `[i - period + 1, i]`, so the current bar cannot define its own breakout.
+///   Defaults to `false`.
 ///
 /// ## Errors
 /// - **EmptyData**: donchian: Input data slice is empty.
@@ -21,9 +24,12 @@
 /// ## Returns
 /// - **`Ok(DonchianOutput)`** on success, containing `upperband`, `middleband`, and `lowerband`
 ///   vectors each matching the input length, with leading `NaN`s until the window is filled
-///   after the first valid index.
+///   after the first valid index. [`DonchianOutput::width`] derives the normalized
+///   channel width from these bands.
 /// - **`Err(DonchianError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
+use crate::utilities::series::shift_forward;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -39,14 +45,31 @@ pub struct DonchianOutput {
     pub lowerband: Vec<f64>,
 }
 
+impl DonchianOutput {
+    /// Channel width normalized by the middle band, `(upper - lower) / middle`. `NaN`
+    /// wherever the bands are (i.e. during warmup).
+    pub fn width(&self) -> Vec<f64> {
+        self.upperband
+            .iter()
+            .zip(&self.lowerband)
+            .zip(&self.middleband)
+            .map(|((upper, lower), middle)| (upper - lower) / middle)
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DonchianParams {
     pub period: Option<usize>,
+    pub exclude_current: Option<bool>,
 }
 
 impl Default for DonchianParams {
     fn default() -> Self {
-        Self { period: Some(20) }
+        Self {
+            period: Some(20),
+            exclude_current: Some(false),
+        }
     }
 }
 
@@ -83,6 +106,12 @@ impl<'a> DonchianInput<'a> {
             .period
             .unwrap_or_else(|| DonchianParams::default().period.unwrap())
     }
+
+    pub fn get_exclude_current(&self) -> bool {
+        self.params
+            .exclude_current
+            .unwrap_or_else(|| DonchianParams::default().exclude_current.unwrap())
+    }
 }
 
 #[derive(Debug, Error)]
@@ -141,6 +170,7 @@ pub fn donchian(input: &DonchianInput) -> Result<DonchianOutput, DonchianError>
     };
 
     if (high.len() - first_valid_idx) < period {
+        not_enough_valid_data("donchian", period, high.len() - first_valid_idx);
         return Err(DonchianError::NotEnoughValidData {
             needed: period,
             valid: high.len() - first_valid_idx,
@@ -215,6 +245,12 @@ pub fn donchian(input: &DonchianInput) -> Result<DonchianOutput, DonchianError>
         }
     }
 
+    if input.get_exclude_current() {
+        upperband = shift_forward(&upperband, 1);
+        middleband = shift_forward(&middleband, 1);
+        lowerband = shift_forward(&lowerband, 1);
+    }
+
     Ok(DonchianOutput {
         upperband,
         middleband,
@@ -244,7 +280,10 @@ mod tests {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
         let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
 
-        let params = DonchianParams { period: Some(14) };
+        let params = DonchianParams {
+            period: Some(14),
+            ..DonchianParams::default()
+        };
         let input = DonchianInput::from_candles(&candles, params);
         let output = donchian(&input).expect("Failed Donchian with period=14");
 
@@ -258,7 +297,10 @@ mod tests {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
         let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
 
-        let params = DonchianParams { period: Some(20) };
+        let params = DonchianParams {
+            period: Some(20),
+            ..DonchianParams::default()
+        };
         let input = DonchianInput::from_candles(&candles, params);
         let output = donchian(&input).expect("Failed to calculate Donchian");
 
@@ -283,7 +325,10 @@ mod tests {
     fn test_donchian_with_zero_period() {
         let high_data = [10.0, 20.0, 30.0];
         let low_data = [5.0, 3.0, 2.0];
-        let params = DonchianParams { period: Some(0) };
+        let params = DonchianParams {
+            period: Some(0),
+            ..DonchianParams::default()
+        };
         let input = DonchianInput::from_slices(&high_data, &low_data, params);
 
         let result = donchian(&input);
@@ -297,7 +342,10 @@ mod tests {
     fn test_donchian_with_period_exceeding_data_length() {
         let high_data = [10.0, 20.0, 30.0];
         let low_data = [5.0, 3.0, 2.0];
-        let params = DonchianParams { period: Some(10) };
+        let params = DonchianParams {
+            period: Some(10),
+            ..DonchianParams::default()
+        };
         let input = DonchianInput::from_slices(&high_data, &low_data, params);
 
         let result = donchian(&input);
@@ -308,7 +356,10 @@ mod tests {
     fn test_donchian_very_small_data_set() {
         let high_data = [100.0];
         let low_data = [90.0];
-        let params = DonchianParams { period: Some(20) };
+        let params = DonchianParams {
+            period: Some(20),
+            ..DonchianParams::default()
+        };
         let input = DonchianInput::from_slices(&high_data, &low_data, params);
 
         let result = donchian(&input);
@@ -319,7 +370,10 @@ mod tests {
     fn test_donchian_mismatched_length() {
         let high_data = [10.0, 20.0, 30.0];
         let low_data = [5.0, 3.0];
-        let params = DonchianParams { period: Some(2) };
+        let params = DonchianParams {
+            period: Some(2),
+            ..DonchianParams::default()
+        };
         let input = DonchianInput::from_slices(&high_data, &low_data, params);
 
         let result = donchian(&input);
@@ -333,7 +387,10 @@ mod tests {
     fn test_donchian_all_nan_data() {
         let high_data = [f64::NAN, f64::NAN];
         let low_data = [f64::NAN, f64::NAN];
-        let params = DonchianParams { period: Some(2) };
+        let params = DonchianParams {
+            period: Some(2),
+            ..DonchianParams::default()
+        };
         let input = DonchianInput::from_slices(&high_data, &low_data, params);
 
         let result = donchian(&input);
@@ -347,7 +404,10 @@ mod tests {
     fn test_donchian_partial_computation() {
         let high_data = [f64::NAN, 3.0, 5.0, 8.0, 8.5, 9.0, 2.0, 1.0];
         let low_data = [f64::NAN, 2.0, 1.0, 4.0, 4.5, 1.0, 1.0, 0.5];
-        let params = DonchianParams { period: Some(3) };
+        let params = DonchianParams {
+            period: Some(3),
+            ..DonchianParams::default()
+        };
         let input = DonchianInput::from_slices(&high_data, &low_data, params);
 
         let output = donchian(&input).expect("Donchian calculation failed");
@@ -361,4 +421,49 @@ mod tests {
         assert!(!output.middleband[3].is_nan());
         assert!(!output.lowerband[3].is_nan());
     }
+
+    #[test]
+    fn test_donchian_width() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = DonchianInput::with_default_candles(&candles);
+        let output = donchian(&input).expect("Failed to calculate Donchian");
+        let width = output.width();
+
+        assert_eq!(width.len(), candles.close.len());
+        assert!(width[..19].iter().all(|v| v.is_nan()));
+        assert!(width[19..].iter().all(|&w| w.is_finite() && w >= 0.0));
+
+        let last = width.len() - 1;
+        let expected = (output.upperband[last] - output.lowerband[last]) / output.middleband[last];
+        assert!((width[last] - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_donchian_exclude_current() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let included = donchian(&DonchianInput::with_default_candles(&candles))
+            .expect("Failed to calculate Donchian");
+        let excluded = donchian(&DonchianInput::from_candles(
+            &candles,
+            DonchianParams {
+                exclude_current: Some(true),
+                ..DonchianParams::default()
+            },
+        ))
+        .expect("Failed to calculate Donchian excluding the current bar");
+
+        assert!(excluded.upperband[..20].iter().all(|v| v.is_nan()));
+        for i in 20..candles.close.len() {
+            assert_eq!(excluded.upperband[i], included.upperband[i - 1]);
+            assert_eq!(excluded.lowerband[i], included.lowerband[i - 1]);
+            let prior_high = candles.high[i - 20..i]
+                .iter()
+                .cloned()
+                .fold(f64::MIN, f64::max);
+            assert_eq!(excluded.upperband[i], prior_high);
+        }
+    }
 }
@@ -19,6 +19,7 @@
 /// - **`Ok(DpoOutput)`** on success, containing a `Vec<f64>` matching the input length,
 ///   with leading `NaN`s until the DPO window is filled.
 /// - **`Err(DpoError)`** otherwise.
+use crate::indicators::error::{require_min_len, CommonError};
 use crate::utilities::data_loader::{source_type, Candles};
 
 #[derive(Debug, Clone)]
@@ -98,6 +99,16 @@ pub enum DpoError {
     AllValuesNaN,
 }
 
+impl From<CommonError> for DpoError {
+    fn from(err: CommonError) -> Self {
+        match err {
+            CommonError::NotEnoughValidData { needed, valid, .. } => {
+                DpoError::NotEnoughValidData { needed, valid }
+            }
+        }
+    }
+}
+
 #[inline]
 pub fn dpo(input: &DpoInput) -> Result<DpoOutput, DpoError> {
     let data: &[f64] = match &input.data {
@@ -122,12 +133,7 @@ pub fn dpo(input: &DpoInput) -> Result<DpoOutput, DpoError> {
         None => return Err(DpoError::AllValuesNaN),
     };
 
-    if (data.len() - first_valid_idx) < period {
-        return Err(DpoError::NotEnoughValidData {
-            needed: period,
-            valid: data.len() - first_valid_idx,
-        });
-    }
+    require_min_len(data.len() - first_valid_idx, period, "dpo")?;
 
     let back = period / 2 + 1;
     let mut dpo_values = vec![f64::NAN; data.len()];
@@ -22,6 +22,7 @@
 ///   with leading `NaN`s until the indicator can be fully calculated.
 /// - **`Err(DtiError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -153,6 +154,7 @@ pub fn dti(input: &DtiInput) -> Result<DtiOutput, DtiError> {
             });
         }
         if (len - first_valid_idx) < period {
+            not_enough_valid_data("dti", period, len - first_valid_idx);
             return Err(DtiError::NotEnoughValidData {
                 needed: period,
                 valid: len - first_valid_idx,
@@ -22,6 +22,7 @@
 ///   with leading `NaN`s until the DX window is filled.
 /// - **`Err(DxError)`** otherwise.
 use crate::utilities::data_loader::{read_candles_from_csv, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -149,6 +150,7 @@ pub fn dx(input: &DxInput) -> Result<DxOutput, DxError> {
         None => return Err(DxError::AllValuesNaN),
     };
     if (len - first_valid_idx) < period {
+        not_enough_valid_data("dx", period, len - first_valid_idx);
         return Err(DxError::NotEnoughValidData {
             needed: period,
             valid: len - first_valid_idx,
@@ -20,6 +20,7 @@
 ///   since EFI requires at least two points to compute the initial price difference.
 /// - **`Err(EfiError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -136,6 +137,7 @@ pub fn efi(input: &EfiInput) -> Result<EfiOutput, EfiError> {
     let first_valid_idx = first_valid_idx.unwrap();
 
     if (len - first_valid_idx) < 2 {
+        not_enough_valid_data("efi", 2, len - first_valid_idx);
         return Err(EfiError::NotEnoughValidData {
             needed: 2,
             valid: len - first_valid_idx,
@@ -158,6 +160,7 @@ pub fn efi(input: &EfiInput) -> Result<EfiOutput, EfiError> {
     };
 
     if (len - start_idx) < period {
+        not_enough_valid_data("efi", period, len - start_idx);
         return Err(EfiError::NotEnoughValidData {
             needed: period,
             valid: len - start_idx,
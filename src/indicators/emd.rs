@@ -25,6 +25,7 @@
 ///   with leading `NaN`s until the computation can begin.
 /// - **`Err(EmdError)`** otherwise.
 use crate::utilities::data_loader::{read_candles_from_csv, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -181,6 +182,7 @@ pub fn emd(input: &EmdInput) -> Result<EmdOutput, EmdError> {
         None => return Err(EmdError::AllValuesNaN),
     };
     if (len - first_valid_idx) < needed {
+        not_enough_valid_data("emd", needed, len - first_valid_idx);
         return Err(EmdError::NotEnoughValidData {
             needed,
             valid: len - first_valid_idx,
@@ -18,6 +18,7 @@
 ///   with leading `NaN`s before the first valid EMV calculation.
 /// - **`Err(EmvError)`** otherwise.
 use crate::utilities::data_loader::{read_candles_from_csv, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -137,6 +138,7 @@ pub fn emv(input: &EmvInput) -> Result<EmvOutput, EmvError> {
         }
     }
     if valid_count < 2 {
+        not_enough_valid_data("emv", 2, valid_count);
         return Err(EmvError::NotEnoughData { valid: valid_count });
     }
 
@@ -1,5 +1,6 @@
 use crate::indicators::moving_averages::ma::{ma, MaData};
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 /// # Elder Ray Index (ERI)
 ///
 /// The Elder-Ray Index (ERI) measures bullish and bearish pressure using an MA (e.g., EMA) as a baseline.
@@ -162,6 +163,7 @@ pub fn eri(input: &EriInput) -> Result<EriOutput, EriError> {
     };
 
     if (source_data.len() - first_valid_idx) < period {
+        not_enough_valid_data("eri", period, source_data.len() - first_valid_idx);
         return Err(EriError::NotEnoughValidData {
             needed: period,
             valid: source_data.len() - first_valid_idx,
@@ -0,0 +1,158 @@
+/// # Indicator Error
+///
+/// A single error type wrapping the per-indicator errors, for generic code that runs
+/// several indicators and returns `Result<_, IndicatorError>`, e.g. a batch runner
+/// computing a feature set. Every wrapped error converts with `?`, and the message is
+/// the indicator's own (so it keeps its `name:` prefix).
+///
+/// Each indicator still returns its own error type; this enum is only an umbrella over
+/// them.
+///
+/// [`CommonError`] holds failures shared by many indicators, with one message format.
+/// [`require_min_len`] is the warmup-length guard that produces it; indicators convert it
+/// into their own `NotEnoughValidData` variant with a `From` impl, so their public error
+/// enums keep the same shape.
+use crate::indicators::adx::AdxError;
+use crate::indicators::atr::AtrError;
+use crate::indicators::bollinger_bands::BollingerBandsError;
+use crate::indicators::cci::CciError;
+use crate::indicators::cmo::CmoError;
+use crate::indicators::ema::EmaError;
+use crate::indicators::macd::MacdError;
+use crate::indicators::mfi::MfiError;
+use crate::indicators::mom::MomError;
+use crate::indicators::obv::ObvError;
+use crate::indicators::pattern_recognition::PatternError;
+use crate::indicators::roc::RocError;
+use crate::indicators::rsi::RsiError;
+use crate::indicators::sma::SmaError;
+use crate::indicators::stddev::StdDevError;
+use crate::indicators::stoch::StochError;
+use crate::indicators::willr::WillrError;
+use crate::indicators::wma::WmaError;
+use crate::utilities::diagnostics::not_enough_valid_data;
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum CommonError {
+    #[error("{name}: Not enough valid data: needed = {needed}, valid = {valid}")]
+    NotEnoughValidData {
+        name: String,
+        needed: usize,
+        valid: usize,
+    },
+}
+
+/// Checks that `len` points cover an indicator's `needed` lookback, returning
+/// [`CommonError::NotEnoughValidData`] (and reporting it through
+/// [`not_enough_valid_data`]) when they do not. `name` is the indicator's error prefix,
+/// e.g. `"cg"`.
+#[inline]
+pub fn require_min_len(len: usize, needed: usize, name: &str) -> Result<(), CommonError> {
+    if len >= needed {
+        return Ok(());
+    }
+    not_enough_valid_data(name, needed, len);
+    Err(CommonError::NotEnoughValidData {
+        name: name.to_string(),
+        needed,
+        valid: len,
+    })
+}
+
+#[derive(Debug, Error)]
+pub enum IndicatorError {
+    #[error(transparent)]
+    Adx(#[from] AdxError),
+    #[error(transparent)]
+    Atr(#[from] AtrError),
+    #[error(transparent)]
+    BollingerBands(#[from] BollingerBandsError),
+    #[error(transparent)]
+    Cci(#[from] CciError),
+    #[error(transparent)]
+    Cmo(#[from] CmoError),
+    #[error(transparent)]
+    Ema(#[from] EmaError),
+    #[error(transparent)]
+    Macd(#[from] MacdError),
+    #[error(transparent)]
+    Mfi(#[from] MfiError),
+    #[error(transparent)]
+    Mom(#[from] MomError),
+    #[error(transparent)]
+    Obv(#[from] ObvError),
+    #[error(transparent)]
+    Pattern(#[from] PatternError),
+    #[error(transparent)]
+    Roc(#[from] RocError),
+    #[error(transparent)]
+    Rsi(#[from] RsiError),
+    #[error(transparent)]
+    Sma(#[from] SmaError),
+    #[error(transparent)]
+    StdDev(#[from] StdDevError),
+    #[error(transparent)]
+    Stoch(#[from] StochError),
+    #[error(transparent)]
+    Willr(#[from] WillrError),
+    #[error(transparent)]
+    Wma(#[from] WmaError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::rsi::{rsi, RsiInput, RsiParams};
+    use crate::indicators::sma::{sma, SmaInput, SmaParams};
+    use crate::utilities::data_loader::read_candles_from_csv;
+
+    fn sma_then_rsi(data: &[f64], period: usize) -> Result<Vec<f64>, IndicatorError> {
+        let smoothed = sma(&SmaInput::from_slice(
+            data,
+            SmaParams {
+                period: Some(period),
+            },
+        ))?
+        .values;
+        Ok(rsi(&RsiInput::from_slice(&smoothed, RsiParams::default()))?.values)
+    }
+
+    #[test]
+    fn test_indicator_error_from_sma_error() {
+        let data = [1.0, 2.0, 3.0];
+        let err = sma_then_rsi(&data, 10).expect_err("SMA period exceeds data length");
+        assert!(matches!(err, IndicatorError::Sma(_)));
+
+        let message = err.to_string();
+        assert!(message.starts_with("sma:"), "Unexpected message: {message}");
+    }
+
+    #[test]
+    fn test_require_min_len() {
+        assert_eq!(require_min_len(5, 5, "cg"), Ok(()));
+        assert_eq!(require_min_len(6, 5, "cg"), Ok(()));
+
+        let err = require_min_len(4, 5, "cg").expect_err("4 points cannot cover 5");
+        assert_eq!(
+            err,
+            CommonError::NotEnoughValidData {
+                name: "cg".to_string(),
+                needed: 5,
+                valid: 4,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "cg: Not enough valid data: needed = 5, valid = 4"
+        );
+    }
+
+    #[test]
+    fn test_indicator_error_pipeline_ok() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let values = sma_then_rsi(&candles.close, 5).expect("Failed to run pipeline");
+        assert_eq!(values.len(), candles.close.len());
+    }
+}
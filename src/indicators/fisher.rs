@@ -14,10 +14,12 @@
 /// - **AllValuesNaN**: fisher: All input data values are `NaN`.
 ///
 /// ## Returns
-/// - **`Ok(FisherOutput)`** on success, containing `fisher` and `signal` vectors matching
-///   the input length, with leading `NaN`s until the transform window is filled.
+/// - **`Ok(FisherOutput)`** on success, containing `fisher` and `trigger` vectors matching
+///   the input length, with leading `NaN`s until the transform window is filled. `trigger`
+///   is the previous bar's Fisher value (`0.0` on the first filled bar).
 /// - **`Err(FisherError)`** otherwise.
 use crate::utilities::data_loader::{read_candles_from_csv, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -29,7 +31,7 @@ pub enum FisherData<'a> {
 #[derive(Debug, Clone)]
 pub struct FisherOutput {
     pub fisher: Vec<f64>,
-    pub signal: Vec<f64>,
+    pub trigger: Vec<f64>,
 }
 
 #[derive(Debug, Clone)]
@@ -126,6 +128,7 @@ pub fn fisher(input: &FisherInput) -> Result<FisherOutput, FisherError> {
     };
 
     if (data_len - first_valid_idx) < period {
+        not_enough_valid_data("fisher", period, data_len - first_valid_idx);
         return Err(FisherError::NotEnoughValidData {
             needed: period,
             valid: data_len - first_valid_idx,
@@ -133,7 +136,7 @@ pub fn fisher(input: &FisherInput) -> Result<FisherOutput, FisherError> {
     }
 
     let mut fisher_vals = vec![f64::NAN; data_len];
-    let mut signal_vals = vec![f64::NAN; data_len];
+    let mut trigger_vals = vec![f64::NAN; data_len];
     let mut prev_fish = 0.0;
     let mut val1 = 0.0;
 
@@ -165,7 +168,7 @@ pub fn fisher(input: &FisherInput) -> Result<FisherOutput, FisherError> {
             val1 = -0.999;
         }
 
-        signal_vals[i] = prev_fish;
+        trigger_vals[i] = prev_fish;
         let new_fish = 0.5 * ((1.0 + val1) / (1.0 - val1)).ln() + 0.5 * prev_fish;
         fisher_vals[i] = new_fish;
         prev_fish = new_fish;
@@ -173,10 +176,22 @@ pub fn fisher(input: &FisherInput) -> Result<FisherOutput, FisherError> {
 
     Ok(FisherOutput {
         fisher: fisher_vals,
-        signal: signal_vals,
+        trigger: trigger_vals,
     })
 }
 
+/// Inverse Fisher Transform: `(e^(2x) - 1) / (e^(2x) + 1)`, i.e. `tanh(x)`.
+///
+/// Maps a Fisher value (or threshold) back to the normalized `-1..1` position within the
+/// `period` high/low range, which `((v + 1) / 2) * range + min` turns into an approximate
+/// price for overlay plots. Note that `fisher` also smooths its input and output, so this
+/// inverts the transform itself rather than reconstructing the exact price. Computed via
+/// `tanh` so large inputs saturate at ±1 instead of overflowing.
+#[inline]
+pub fn fisher_inverse(fisher_value: f64) -> f64 {
+    fisher_value.tanh()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,7 +214,7 @@ mod tests {
             "Fisher output length mismatch"
         );
         assert_eq!(
-            fisher_result.signal.len(),
+            fisher_result.trigger.len(),
             close_prices.len(),
             "Signal output length mismatch"
         );
@@ -211,7 +226,7 @@ mod tests {
             -0.026651419122953053,
             -0.2569225042442664,
         ];
-        let expected_last_five_signal = [
+        let expected_last_five_trigger = [
             -0.7742705746872902,
             -0.4720164683904261,
             -0.23467530106650444,
@@ -225,7 +240,7 @@ mod tests {
         );
         let start_index = fisher_result.fisher.len() - 5;
         let result_last_five_fisher = &fisher_result.fisher[start_index..];
-        let result_last_five_signal = &fisher_result.signal[start_index..];
+        let result_last_five_trigger = &fisher_result.trigger[start_index..];
 
         for (i, &value) in result_last_five_fisher.iter().enumerate() {
             let expected_value = expected_last_five_fisher[i];
@@ -238,8 +253,8 @@ mod tests {
             );
         }
 
-        for (i, &value) in result_last_five_signal.iter().enumerate() {
-            let expected_value = expected_last_five_signal[i];
+        for (i, &value) in result_last_five_trigger.iter().enumerate() {
+            let expected_value = expected_last_five_trigger[i];
             assert!(
                 (value - expected_value).abs() < 1e-1,
                 "Signal mismatch at index {}: expected {}, got {}",
@@ -257,8 +272,8 @@ mod tests {
                 i
             );
             assert!(
-                fisher_result.signal[i].is_nan(),
-                "Expected NaN in signal_values at index {}",
+                fisher_result.trigger[i].is_nan(),
+                "Expected NaN in trigger_values at index {}",
                 i
             );
         }
@@ -271,7 +286,7 @@ mod tests {
             "Default Fisher length mismatch"
         );
         assert_eq!(
-            default_result.signal.len(),
+            default_result.trigger.len(),
             close_prices.len(),
             "Default Signal length mismatch"
         );
@@ -285,13 +300,13 @@ mod tests {
         let input_default = FisherInput::from_candles(&candles, default_params);
         let output_default = fisher(&input_default).expect("Failed Fisher with default params");
         assert_eq!(output_default.fisher.len(), candles.close.len());
-        assert_eq!(output_default.signal.len(), candles.close.len());
+        assert_eq!(output_default.trigger.len(), candles.close.len());
 
         let params_period_14 = FisherParams { period: Some(14) };
         let input_period_14 = FisherInput::from_candles(&candles, params_period_14);
         let output_period_14 = fisher(&input_period_14).expect("Failed Fisher with period=14");
         assert_eq!(output_period_14.fisher.len(), candles.close.len());
-        assert_eq!(output_period_14.signal.len(), candles.close.len());
+        assert_eq!(output_period_14.trigger.len(), candles.close.len());
     }
 
     #[test]
@@ -302,7 +317,7 @@ mod tests {
         let input = FisherInput::from_slices(&high, &low, params);
         let output = fisher(&input).expect("Fisher calculation failed");
         assert_eq!(output.fisher.len(), 5);
-        assert_eq!(output.signal.len(), 5);
+        assert_eq!(output.trigger.len(), 5);
     }
 
     #[test]
@@ -365,11 +380,11 @@ mod tests {
 
         let second_params = FisherParams { period: Some(3) };
         let second_input =
-            FisherInput::from_slices(&first_result.fisher, &first_result.signal, second_params);
+            FisherInput::from_slices(&first_result.fisher, &first_result.trigger, second_params);
         let second_result = fisher(&second_input).expect("Failed second fisher transform");
 
         assert_eq!(first_result.fisher.len(), second_result.fisher.len());
-        assert_eq!(first_result.signal.len(), second_result.signal.len());
+        assert_eq!(first_result.trigger.len(), second_result.trigger.len());
     }
 
     #[test]
@@ -379,6 +394,37 @@ mod tests {
         let input = FisherInput::with_default_candles(&candles);
         let result = fisher(&input).expect("Failed default fisher transform");
         assert_eq!(result.fisher.len(), candles.close.len());
-        assert_eq!(result.signal.len(), candles.close.len());
+        assert_eq!(result.trigger.len(), candles.close.len());
+    }
+
+    #[test]
+    fn test_fisher_inverse_round_trip() {
+        for x in [-0.999_f64, -0.75, -0.2, 0.0, 0.33, 0.9, 0.999] {
+            let forward = 0.5 * ((1.0 + x) / (1.0 - x)).ln();
+            let back = fisher_inverse(forward);
+            assert!(
+                (back - x).abs() < 1e-12,
+                "Round trip failed for {}: got {}",
+                x,
+                back
+            );
+        }
+        assert_eq!(fisher_inverse(1000.0), 1.0);
+        assert_eq!(fisher_inverse(-1000.0), -1.0);
+    }
+
+    #[test]
+    fn test_fisher_trigger_lags_one_bar() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let params = FisherParams { period: Some(14) };
+        let result = fisher(&FisherInput::from_candles(&candles, params)).expect("fisher");
+
+        let first = result.fisher.iter().position(|v| !v.is_nan()).unwrap();
+        assert_eq!(first, 13);
+        assert_eq!(result.trigger[first], 0.0);
+        for i in (first + 1)..result.fisher.len() {
+            assert_eq!(result.trigger[i], result.fisher[i - 1]);
+        }
     }
 }
@@ -39,6 +39,7 @@
 ///   but does not propagate extra `NaN`s once enough valid data is available.
 /// - **`Err(GatorOscError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -277,6 +278,7 @@ pub fn gatorosc(input: &GatorOscInput) -> Result<GatorOscOutput, GatorOscError>
         .filter(|v| !v.is_nan())
         .count();
     if valid_data_count < needed {
+        not_enough_valid_data("gatorosc", needed, valid_data_count);
         return Err(GatorOscError::NotEnoughValidData);
     }
 
@@ -20,6 +20,7 @@
 ///   until the first valid index.
 /// - **`Err(HeikinAshiError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -131,6 +132,7 @@ pub fn heikin_ashi_candles(input: &HeikinAshiInput) -> Result<HeikinAshiOutput,
     };
 
     if (len - first_valid_idx) < 2 {
+        not_enough_valid_data("heikin_ashi_candles", 2, len - first_valid_idx);
         return Err(HeikinAshiError::NotEnoughValidData {
             needed: 2,
             valid: len - first_valid_idx,
@@ -171,9 +173,34 @@ pub fn heikin_ashi_candles(input: &HeikinAshiInput) -> Result<HeikinAshiOutput,
     })
 }
 
+/// Converts Heikin Ashi output into regular [`Candles`] so every indicator and pattern
+/// can run on HA bars directly.
+///
+/// `timestamps` must match the length of `ha` (usually `candles.timestamp` of the source
+/// data). Heikin Ashi has no volume of its own, so the volume column is filled with `NaN`.
+///
+/// # Panics
+/// Panics if `timestamps.len()` differs from the length of the HA series.
+pub fn heikin_ashi_to_candles(ha: &HeikinAshiOutput, timestamps: &[i64]) -> Candles {
+    assert_eq!(
+        timestamps.len(),
+        ha.close.len(),
+        "heikin_ashi_to_candles: timestamps and Heikin Ashi series differ in length"
+    );
+    Candles::new(
+        timestamps.to_vec(),
+        ha.open.clone(),
+        ha.high.clone(),
+        ha.low.clone(),
+        ha.close.clone(),
+        vec![f64::NAN; ha.close.len()],
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::rsi::{rsi, RsiInput, RsiParams};
     use crate::utilities::data_loader::read_candles_from_csv;
 
     #[test]
@@ -285,4 +312,35 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_heikin_ashi_to_candles_runs_rsi() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = HeikinAshiInput::from_candles(&candles);
+        let output = heikin_ashi_candles(&input).expect("Failed Heikin Ashi calculation");
+
+        let ha_candles = heikin_ashi_to_candles(&output, &candles.timestamp);
+        assert_eq!(ha_candles.close.len(), candles.close.len());
+        assert_eq!(ha_candles.timestamp, candles.timestamp);
+        assert_eq!(ha_candles.close, output.close);
+        let last = ha_candles.close.len() - 1;
+        let expected_hl2 = (output.high[last] + output.low[last]) / 2.0;
+        assert!((ha_candles.hl2[last] - expected_hl2).abs() < 1e-10);
+
+        let rsi_input =
+            RsiInput::from_candles(&ha_candles, "close", RsiParams { period: Some(14) });
+        let ha_rsi = rsi(&rsi_input).expect("Failed RSI on Heikin Ashi candles");
+        let direct_input = RsiInput::from_slice(&output.close, RsiParams { period: Some(14) });
+        let direct_rsi = rsi(&direct_input).expect("Failed RSI on Heikin Ashi close");
+        assert_eq!(ha_rsi.values.len(), candles.close.len());
+        for i in 14..ha_rsi.values.len() {
+            assert!(
+                ha_rsi.values[i].is_finite(),
+                "RSI should be finite at {}",
+                i
+            );
+            assert_eq!(ha_rsi.values[i], direct_rsi.values[i]);
+        }
+    }
 }
@@ -18,6 +18,7 @@
 ///   with leading `NaN`s until HT_DCPERIOD values can be calculated.
 /// - **`Err(HtDcPeriodError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -97,6 +98,7 @@ pub fn ht_dcperiod(input: &HtDcPeriodInput) -> Result<HtDcPeriodOutput, HtDcPeri
 
     let lookback = 32;
     if (data.len() - first_valid_idx) < lookback {
+        not_enough_valid_data("ht_dcperiod", lookback, data.len() - first_valid_idx);
         return Err(HtDcPeriodError::NotEnoughValidData {
             needed: lookback,
             valid: data.len() - first_valid_idx,
@@ -20,6 +20,7 @@
 /// - **`Ok(HtDcPhaseOutput)`** on success, containing a `Vec<f64>` of phase values.
 /// - **`Err(HtDcPhaseError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -105,6 +106,7 @@ pub fn ht_dcphase(input: &HtDcPhaseInput) -> Result<HtDcPhaseOutput, HtDcPhaseEr
 
     let lookback = 63;
     if (data.len() - first_valid_idx) < lookback {
+        not_enough_valid_data("ht_dcphase", lookback, data.len() - first_valid_idx);
         return Err(HtDcPhaseError::NotEnoughValidData);
     }
 
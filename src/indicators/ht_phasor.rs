@@ -19,6 +19,7 @@
 ///   matching the input length, with leading `NaN`s until the lookback window is filled.
 /// - **`Err(HtPhasorError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum HtPhasorData<'a> {
@@ -106,6 +107,7 @@ pub fn ht_phasor(input: &HtPhasorInput) -> Result<HtPhasorOutput, HtPhasorError>
 
     let lookback = 32;
     if (data.len() - first_valid_idx) < lookback {
+        not_enough_valid_data("ht_phasor", lookback, data.len() - first_valid_idx);
         return Err(HtPhasorError::NotEnoughValidData {
             needed: lookback,
             valid: data.len() - first_valid_idx,
@@ -146,6 +148,7 @@ pub fn ht_phasor(input: &HtPhasorInput) -> Result<HtPhasorOutput, HtPhasorError>
     let mut i1_for_even_prev2 = 0.0;
 
     if today + 2 >= data.len() {
+        not_enough_valid_data("ht_phasor", lookback, data.len() - first_valid_idx);
         return Err(HtPhasorError::NotEnoughValidData {
             needed: lookback,
             valid: data.len() - first_valid_idx,
@@ -21,6 +21,7 @@
 ///   (63 bars beyond the first valid data point).
 /// - **`Err(HtSineError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum HtSineData<'a> {
@@ -104,6 +105,7 @@ pub fn ht_sine(input: &HtSineInput) -> Result<HtSineOutput, HtSineError> {
 
     let lookback = 63;
     if (data.len() - first_valid_idx) < lookback {
+        not_enough_valid_data("ht_sine", lookback, data.len() - first_valid_idx);
         return Err(HtSineError::NotEnoughValidData);
     }
 
@@ -19,6 +19,7 @@
 ///   with leading `NaN`s until the HT_TRENDLINE can be computed.
 /// - **`Err(HtTrendlineError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::f64::consts::PI;
 use thiserror::Error;
 
@@ -104,6 +105,7 @@ pub fn ht_trendline(input: &HtTrendlineInput) -> Result<HtTrendlineOutput, HtTre
     };
 
     if (data.len() - first_valid_idx) < 64 {
+        not_enough_valid_data("ht_trendline", 64, data.len() - first_valid_idx);
         return Err(HtTrendlineError::NotEnoughData);
     }
 
@@ -19,6 +19,7 @@
 ///   with leading `NaN`s until the calculation window is filled.
 /// - **`Err(HtTrendModeError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -104,6 +105,7 @@ pub fn ht_trendmode(input: &HtTrendModeInput) -> Result<HtTrendModeOutput, HtTre
 
     let valid_count = data.len() - first_valid_idx;
     if valid_count < 63 {
+        not_enough_valid_data("ht_trendmode", 63, valid_count);
         return Err(HtTrendModeError::NotEnoughData);
     }
 
@@ -0,0 +1,300 @@
+/// # Ichimoku Cloud (Ichimoku Kinko Hyo)
+///
+/// Ichimoku combines several high/low midpoints into a trend, momentum and
+/// support/resistance overview:
+///
+/// - **tenkan** (conversion line): midpoint of the highest high and lowest low over `conversion` bars.
+/// - **kijun** (base line): the same midpoint over `base` bars.
+/// - **senkou_a** (leading span A): `(tenkan + kijun) / 2`, displaced forward by `displacement` bars.
+/// - **senkou_b** (leading span B): the midpoint over `span_b` bars, displaced forward by
+///   `displacement` bars.
+/// - **chikou** (lagging span): the close, displaced back by `displacement` bars.
+///
+/// All outputs keep the input length. The leading spans are shifted with
+/// [`shift_forward`], so `senkou_a[i]` is the value computed `displacement` bars before
+/// `i` and the projection past the last bar is dropped. The lagging span uses
+/// [`shift_back`], so its last `displacement` values are `NaN`.
+///
+/// ## Parameters
+/// - **conversion**: Window for the conversion line. Defaults to 9.
+/// - **base**: Window for the base line. Defaults to 26.
+/// - **span_b**: Window for leading span B. Defaults to 52.
+/// - **displacement**: Forward/backward shift of the spans. Defaults to 26.
+///
+/// ## Errors
+/// - **EmptyData**: ichimoku: Input data slice is empty.
+/// - **MismatchedLength**: ichimoku: High, low and close slices have different lengths.
+/// - **InvalidPeriod**: ichimoku: A window is zero or exceeds the data length.
+/// - **InvalidDisplacement**: ichimoku: `displacement` exceeds the data length.
+/// - **RollingError**: ichimoku: Error from the rolling max/min helpers (e.g. all `NaN`).
+///
+/// ## Returns
+/// - **`Ok(IchimokuOutput)`** on success, containing `tenkan`, `kijun`, `senkou_a`,
+///   `senkou_b` and `chikou`, each matching the input length.
+/// - **`Err(IchimokuError)`** otherwise.
+use crate::indicators::utility_functions::{max_rolling, min_rolling, RollingError};
+use crate::utilities::data_loader::Candles;
+use crate::utilities::series::{shift_back, shift_forward};
+use thiserror::Error;
+
+#[derive(Debug, Clone)]
+pub enum IchimokuData<'a> {
+    Candles {
+        candles: &'a Candles,
+    },
+    Slices {
+        high: &'a [f64],
+        low: &'a [f64],
+        close: &'a [f64],
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct IchimokuOutput {
+    pub tenkan: Vec<f64>,
+    pub kijun: Vec<f64>,
+    pub senkou_a: Vec<f64>,
+    pub senkou_b: Vec<f64>,
+    pub chikou: Vec<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IchimokuParams {
+    pub conversion: Option<usize>,
+    pub base: Option<usize>,
+    pub span_b: Option<usize>,
+    pub displacement: Option<usize>,
+}
+
+impl Default for IchimokuParams {
+    fn default() -> Self {
+        Self {
+            conversion: Some(9),
+            base: Some(26),
+            span_b: Some(52),
+            displacement: Some(26),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IchimokuInput<'a> {
+    pub data: IchimokuData<'a>,
+    pub params: IchimokuParams,
+}
+
+impl<'a> IchimokuInput<'a> {
+    pub fn from_candles(candles: &'a Candles, params: IchimokuParams) -> Self {
+        Self {
+            data: IchimokuData::Candles { candles },
+            params,
+        }
+    }
+
+    pub fn from_slices(
+        high: &'a [f64],
+        low: &'a [f64],
+        close: &'a [f64],
+        params: IchimokuParams,
+    ) -> Self {
+        Self {
+            data: IchimokuData::Slices { high, low, close },
+            params,
+        }
+    }
+
+    pub fn with_default_candles(candles: &'a Candles) -> Self {
+        Self {
+            data: IchimokuData::Candles { candles },
+            params: IchimokuParams::default(),
+        }
+    }
+
+    pub fn get_conversion(&self) -> usize {
+        self.params
+            .conversion
+            .unwrap_or_else(|| IchimokuParams::default().conversion.unwrap())
+    }
+
+    pub fn get_base(&self) -> usize {
+        self.params
+            .base
+            .unwrap_or_else(|| IchimokuParams::default().base.unwrap())
+    }
+
+    pub fn get_span_b(&self) -> usize {
+        self.params
+            .span_b
+            .unwrap_or_else(|| IchimokuParams::default().span_b.unwrap())
+    }
+
+    pub fn get_displacement(&self) -> usize {
+        self.params
+            .displacement
+            .unwrap_or_else(|| IchimokuParams::default().displacement.unwrap())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum IchimokuError {
+    #[error("ichimoku: Empty data provided.")]
+    EmptyData,
+    #[error("ichimoku: High, low and close slices have different lengths.")]
+    MismatchedLength,
+    #[error("ichimoku: Invalid {name} period: period = {period}, data length = {data_len}")]
+    InvalidPeriod {
+        name: &'static str,
+        period: usize,
+        data_len: usize,
+    },
+    #[error(
+        "ichimoku: Invalid displacement: displacement = {displacement}, data length = {data_len}"
+    )]
+    InvalidDisplacement {
+        displacement: usize,
+        data_len: usize,
+    },
+    #[error(transparent)]
+    RollingError(#[from] RollingError),
+}
+
+#[inline]
+fn midpoint(high: &[f64], low: &[f64], period: usize) -> Result<Vec<f64>, RollingError> {
+    let highest = max_rolling(high, period)?;
+    let lowest = min_rolling(low, period)?;
+    Ok(highest
+        .iter()
+        .zip(lowest.iter())
+        .map(|(h, l)| (h + l) / 2.0)
+        .collect())
+}
+
+#[inline]
+pub fn ichimoku(input: &IchimokuInput) -> Result<IchimokuOutput, IchimokuError> {
+    let (high, low, close) = match &input.data {
+        IchimokuData::Candles { candles } => (
+            candles.high.as_slice(),
+            candles.low.as_slice(),
+            candles.close.as_slice(),
+        ),
+        IchimokuData::Slices { high, low, close } => (*high, *low, *close),
+    };
+
+    let len = close.len();
+    if len == 0 {
+        return Err(IchimokuError::EmptyData);
+    }
+    if high.len() != len || low.len() != len {
+        return Err(IchimokuError::MismatchedLength);
+    }
+
+    let conversion = input.get_conversion();
+    let base = input.get_base();
+    let span_b = input.get_span_b();
+    for (name, period) in [
+        ("conversion", conversion),
+        ("base", base),
+        ("span_b", span_b),
+    ] {
+        if period == 0 || period > len {
+            return Err(IchimokuError::InvalidPeriod {
+                name,
+                period,
+                data_len: len,
+            });
+        }
+    }
+
+    let displacement = input.get_displacement();
+    if displacement > len {
+        return Err(IchimokuError::InvalidDisplacement {
+            displacement,
+            data_len: len,
+        });
+    }
+
+    let tenkan = midpoint(high, low, conversion)?;
+    let kijun = midpoint(high, low, base)?;
+    let span_a_raw: Vec<f64> = tenkan
+        .iter()
+        .zip(kijun.iter())
+        .map(|(t, k)| (t + k) / 2.0)
+        .collect();
+    let span_b_raw = midpoint(high, low, span_b)?;
+
+    Ok(IchimokuOutput {
+        senkou_a: shift_forward(&span_a_raw, displacement),
+        senkou_b: shift_forward(&span_b_raw, displacement),
+        chikou: shift_back(close, displacement),
+        tenkan,
+        kijun,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::data_loader::read_candles_from_csv;
+
+    #[test]
+    fn test_ichimoku_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = IchimokuInput::with_default_candles(&candles);
+        let output = ichimoku(&input).expect("Failed to calculate ichimoku");
+        let len = candles.close.len();
+
+        assert_eq!(output.tenkan.len(), len);
+        assert_eq!(output.chikou.len(), len);
+
+        // First tenkan bar: highest high 2812.0 (bar 3), lowest low 2551.0 (bar 8).
+        assert!(output.tenkan[7].is_nan());
+        assert!((output.tenkan[8] - 2681.5).abs() < 1e-8);
+        assert!(output.kijun[24].is_nan());
+        assert!((output.kijun[25] - 2608.0).abs() < 1e-8);
+
+        assert!((output.tenkan[len - 1] - 58749.0).abs() < 1e-8);
+        assert!((output.kijun[len - 1] - 60058.5).abs() < 1e-8);
+        assert!((output.senkou_a[len - 1] - 62944.75).abs() < 1e-8);
+        assert!((output.senkou_b[len - 1] - 61568.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_ichimoku_displacement() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let output =
+            ichimoku(&IchimokuInput::with_default_candles(&candles)).expect("ichimoku failed");
+        let len = candles.close.len();
+
+        let i = 200;
+        let expected_a = (output.tenkan[i - 26] + output.kijun[i - 26]) / 2.0;
+        assert!((output.senkou_a[i] - expected_a).abs() < 1e-8);
+        assert_eq!(output.chikou[i], candles.close[i + 26]);
+        assert!(output.chikou[len - 26..].iter().all(|v| v.is_nan()));
+        assert!(output.senkou_b[..26 + 51].iter().all(|v| v.is_nan()));
+        assert!(!output.senkou_b[26 + 51].is_nan());
+    }
+
+    #[test]
+    fn test_ichimoku_invalid_params() {
+        let data = [1.0, 2.0, 3.0];
+        let params = IchimokuParams {
+            conversion: Some(2),
+            base: Some(3),
+            span_b: Some(4),
+            displacement: Some(1),
+        };
+        let input = IchimokuInput::from_slices(&data, &data, &data, params);
+        assert!(matches!(
+            ichimoku(&input),
+            Err(IchimokuError::InvalidPeriod { name: "span_b", .. })
+        ));
+
+        let mismatched = IchimokuInput::from_slices(&data, &data[..2], &data, Default::default());
+        assert!(matches!(
+            ichimoku(&mismatched),
+            Err(IchimokuError::MismatchedLength)
+        ));
+    }
+}
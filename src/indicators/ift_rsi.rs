@@ -26,6 +26,7 @@
 use crate::indicators::rsi::{rsi, RsiError, RsiInput, RsiOutput, RsiParams};
 use crate::indicators::wma::{wma, WmaError, WmaInput, WmaOutput, WmaParams};
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum IftRsiData<'a> {
@@ -152,6 +153,7 @@ pub fn ift_rsi(input: &IftRsiInput) -> Result<IftRsiOutput, IftRsiError> {
     let needed = rsi_period.max(wma_period);
     let valid_points = data.len() - first_valid_idx;
     if valid_points < needed {
+        not_enough_valid_data("ift_rsi", needed, valid_points);
         return Err(IftRsiError::NotEnoughValidData {
             needed,
             valid: valid_points,
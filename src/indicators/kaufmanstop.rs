@@ -1,5 +1,6 @@
 use crate::indicators::moving_averages::ma::{ma, MaData};
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 /// # Kaufmanstop
 ///
 /// Perry Kaufman's Stop indicator computes an adaptive price stop based on the
@@ -173,6 +174,7 @@ pub fn kaufmanstop(input: &KaufmanstopInput) -> Result<KaufmanstopOutput, Kaufma
     };
 
     if (high.len() - first_valid_idx) < period || (low.len() - first_valid_idx) < period {
+        not_enough_valid_data("kaufmanstop", period, high.len() - first_valid_idx);
         return Err(KaufmanstopError::NotEnoughValidData {
             needed: period,
             valid: high.len() - first_valid_idx,
@@ -2,6 +2,7 @@ use crate::indicators::moving_averages::ma::{ma, MaData};
 use crate::indicators::utility_functions::{max_rolling, min_rolling, RollingError};
 use crate::utilities::data_loader::read_candles_from_csv;
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 /// # KDJ (Stochastic Oscillator with MA smoothing)
 ///
 /// KDJ is derived from the Stochastic Oscillator (K, D) with an additional line J,
@@ -186,6 +187,7 @@ pub fn kdj(input: &KdjInput) -> Result<KdjOutput, KdjError> {
     };
 
     if (high.len() - first_valid_idx) < fast_k_period {
+        not_enough_valid_data("kdj", fast_k_period, high.len() - first_valid_idx);
         return Err(KdjError::NotEnoughValidData {
             needed: fast_k_period,
             valid: high.len() - first_valid_idx,
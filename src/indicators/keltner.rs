@@ -1,15 +1,22 @@
 use crate::indicators::moving_averages::ma::{ma, MaData};
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 /// # Keltner Channels
 ///
 /// A volatility-based envelope indicator. The middle band is typically a moving average (MA) of a
 /// user-specified `source`, and the upper and lower bands are derived by adding or subtracting a
 /// multiple of an internally computed Average True Range (ATR).
 ///
+/// Chester Keltner's original channel used an SMA of the typical price (`"hlc3"` source) with
+/// bands one average high-low range away; pick [`KeltnerBandSource::TypicalRange`] with
+/// `ma_type = "sma"` for that variant.
+///
 /// ## Parameters
-/// - **period**: The lookback length for both the moving average and the ATR. Defaults to 20.
-/// - **multiplier**: The ATR multiplier for constructing upper/lower bands. Defaults to 2.0.
+/// - **period**: The lookback length for both the moving average and the band width. Defaults to 20.
+/// - **multiplier**: The band width multiplier for constructing upper/lower bands. Defaults to 2.0.
 /// - **ma_type**: The moving average type to use (e.g., `"ema"`, `"sma"`, `"wma"`, etc.). Defaults to `"ema"`.
+/// - **band_source**: What the band width measures: [`KeltnerBandSource::Atr`] (Wilder ATR, the
+///   default) or [`KeltnerBandSource::TypicalRange`] (SMA of `high - low`).
 ///
 /// ## Errors
 /// - **KeltnerEmptyData**: keltner: Input data is empty.
@@ -38,6 +45,7 @@ pub struct KeltnerParams {
     pub period: Option<usize>,
     pub multiplier: Option<f64>,
     pub ma_type: Option<String>,
+    pub band_source: Option<KeltnerBandSource>,
 }
 
 impl Default for KeltnerParams {
@@ -46,10 +54,21 @@ impl Default for KeltnerParams {
             period: Some(20),
             multiplier: Some(2.0),
             ma_type: Some("sma".to_string()),
+            band_source: Some(KeltnerBandSource::Atr),
         }
     }
 }
 
+/// What the Keltner band width is a multiple of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeltnerBandSource {
+    /// Wilder-smoothed Average True Range over `period` (the modern channel).
+    #[default]
+    Atr,
+    /// Simple average of `high - low` over `period` (Keltner's original channel).
+    TypicalRange,
+}
+
 #[derive(Debug, Clone)]
 pub struct KeltnerInput<'a> {
     pub data: KeltnerData<'a>,
@@ -86,6 +105,10 @@ impl<'a> KeltnerInput<'a> {
             .map(|s| s.to_lowercase())
             .unwrap_or_else(|| "sma".to_string())
     }
+
+    pub fn get_band_source(&self) -> KeltnerBandSource {
+        self.params.band_source.unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -140,32 +163,10 @@ pub fn keltner(input: &KeltnerInput) -> Result<KeltnerOutput, KeltnerError> {
         });
     }
 
-    let mut atr_values = vec![f64::NAN; len];
-    let alpha = 1.0 / (period as f64);
-    let mut sum_tr = 0.0;
-    let mut rma = f64::NAN;
-
-    for i in 0..len {
-        let tr = if i == 0 {
-            high[0] - low[0]
-        } else {
-            let hl = high[i] - low[i];
-            let hc = (high[i] - close[i - 1]).abs();
-            let lc = (low[i] - close[i - 1]).abs();
-            hl.max(hc).max(lc)
-        };
-
-        if i < period {
-            sum_tr += tr;
-            if i == period - 1 {
-                rma = sum_tr / (period as f64);
-                atr_values[i] = rma;
-            }
-        } else {
-            rma += alpha * (tr - rma);
-            atr_values[i] = rma;
-        }
-    }
+    let band_values = match input.get_band_source() {
+        KeltnerBandSource::Atr => wilder_atr(high, low, close, period),
+        KeltnerBandSource::TypicalRange => average_range(high, low, period),
+    };
 
     let ma_values = ma(&input.get_ma_type(), MaData::Slice(&source_slice), period)
         .map_err(KeltnerError::KeltnerMaError)?;
@@ -178,13 +179,14 @@ pub fn keltner(input: &KeltnerInput) -> Result<KeltnerOutput, KeltnerError> {
     }
 
     let first_valid_ma = ma_values.iter().position(|&v| !v.is_nan());
-    let first_valid_atr = atr_values.iter().position(|&v| !v.is_nan());
-    let first_valid_idx = match (first_valid_ma, first_valid_atr) {
+    let first_valid_band = band_values.iter().position(|&v| !v.is_nan());
+    let first_valid_idx = match (first_valid_ma, first_valid_band) {
         (Some(m), Some(a)) => m.max(a),
         _ => return Err(KeltnerError::KeltnerAllValuesNaN),
     };
 
     if (len - first_valid_idx) < period {
+        not_enough_valid_data("keltner", period, len - first_valid_idx);
         return Err(KeltnerError::KeltnerNotEnoughValidData {
             needed: period,
             valid: len - first_valid_idx,
@@ -198,13 +200,13 @@ pub fn keltner(input: &KeltnerInput) -> Result<KeltnerOutput, KeltnerError> {
 
     for i in first_valid_idx..len {
         let ma_v = ma_values[i];
-        let atr_v = atr_values[i];
-        if ma_v.is_nan() || atr_v.is_nan() {
+        let band_v = band_values[i];
+        if ma_v.is_nan() || band_v.is_nan() {
             continue;
         }
         middle_band[i] = ma_v;
-        upper_band[i] = ma_v + multiplier * atr_v;
-        lower_band[i] = ma_v - multiplier * atr_v;
+        upper_band[i] = ma_v + multiplier * band_v;
+        lower_band[i] = ma_v - multiplier * band_v;
     }
 
     Ok(KeltnerOutput {
@@ -214,6 +216,59 @@ pub fn keltner(input: &KeltnerInput) -> Result<KeltnerOutput, KeltnerError> {
     })
 }
 
+/// Wilder ATR seeded with the mean true range of the first `period` bars.
+#[inline]
+fn wilder_atr(high: &[f64], low: &[f64], close: &[f64], period: usize) -> Vec<f64> {
+    let len = close.len();
+    let mut atr_values = vec![f64::NAN; len];
+    let alpha = 1.0 / (period as f64);
+    let mut sum_tr = 0.0;
+    let mut rma = f64::NAN;
+
+    for i in 0..len {
+        let tr = if i == 0 {
+            high[0] - low[0]
+        } else {
+            let hl = high[i] - low[i];
+            let hc = (high[i] - close[i - 1]).abs();
+            let lc = (low[i] - close[i - 1]).abs();
+            hl.max(hc).max(lc)
+        };
+
+        if i < period {
+            sum_tr += tr;
+            if i == period - 1 {
+                rma = sum_tr / (period as f64);
+                atr_values[i] = rma;
+            }
+        } else {
+            rma += alpha * (tr - rma);
+            atr_values[i] = rma;
+        }
+    }
+    atr_values
+}
+
+/// Simple moving average of `high - low` over `period` bars.
+#[inline]
+fn average_range(high: &[f64], low: &[f64], period: usize) -> Vec<f64> {
+    let len = high.len();
+    let mut range_values = vec![f64::NAN; len];
+    let inv_period = 1.0 / (period as f64);
+    let mut sum_range = 0.0;
+
+    for i in 0..len {
+        sum_range += high[i] - low[i];
+        if i >= period {
+            sum_range -= high[i - period] - low[i - period];
+        }
+        if i + 1 >= period {
+            range_values[i] = sum_range * inv_period;
+        }
+    }
+    range_values
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,6 +283,7 @@ mod tests {
             period: Some(20),
             multiplier: Some(2.0),
             ma_type: Some("ema".to_string()),
+            ..KeltnerParams::default()
         };
         let input = KeltnerInput::from_candles(&candles, "close", params);
         let result = keltner(&input).expect("Failed to calculate Keltner channels");
@@ -315,6 +371,7 @@ mod tests {
             period: Some(0),
             multiplier: Some(2.0),
             ma_type: Some("ema".to_string()),
+            ..KeltnerParams::default()
         };
         let input = KeltnerInput::from_candles(&candles, "close", params);
         let result = keltner(&input);
@@ -337,6 +394,7 @@ mod tests {
             period: Some(999999),
             multiplier: Some(2.0),
             ma_type: Some("ema".to_string()),
+            ..KeltnerParams::default()
         };
         let input = KeltnerInput::from_candles(&candles, "close", params);
         let result = keltner(&input);
@@ -349,4 +407,36 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_keltner_typical_range_bands() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let atr_params = KeltnerParams {
+            ma_type: Some("sma".to_string()),
+            ..KeltnerParams::default()
+        };
+        let range_params = KeltnerParams {
+            band_source: Some(KeltnerBandSource::TypicalRange),
+            ..atr_params.clone()
+        };
+        let atr_bands = keltner(&KeltnerInput::from_candles(&candles, "hlc3", atr_params))
+            .expect("Failed to calculate ATR Keltner channels");
+        let range_bands = keltner(&KeltnerInput::from_candles(&candles, "hlc3", range_params))
+            .expect("Failed to calculate range Keltner channels");
+
+        crate::assert_slice_close!(range_bands.middle_band, atr_bands.middle_band, 0.0);
+
+        let last = candles.close.len() - 1;
+        assert!((range_bands.upper_band[last] - atr_bands.upper_band[last]).abs() > 1.0);
+        assert!((range_bands.lower_band[last] - atr_bands.lower_band[last]).abs() > 1.0);
+
+        let mean_range: f64 = (last - 19..=last)
+            .map(|i| candles.high[i] - candles.low[i])
+            .sum::<f64>()
+            / 20.0;
+        let half_width = (range_bands.upper_band[last] - range_bands.lower_band[last]) / 2.0;
+        assert!((half_width - 2.0 * mean_range).abs() < 1e-6);
+    }
 }
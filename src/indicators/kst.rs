@@ -17,6 +17,8 @@ use crate::indicators::moving_averages::sma::{
 /// - **roc_period3**: Period for the third ROC calculation. Defaults to 20.
 /// - **roc_period4**: Period for the fourth ROC calculation. Defaults to 30.
 /// - **signal_period**: Smoothing period for the signal line. Defaults to 9.
+/// - **weights**: Weights applied to the four smoothed ROCs when summing the KST line.
+///   Defaults to the classic `[1.0, 2.0, 3.0, 4.0]`.
 ///
 /// ## Errors
 /// - **EmptyData**: kst: Input data slice is empty.
@@ -24,6 +26,7 @@ use crate::indicators::moving_averages::sma::{
 /// - **NotEnoughValidData**: kst: Fewer than the necessary valid (non-`NaN`) data points remain
 ///   after the first valid index.
 /// - **InvalidPeriod**: kst: A requested period is zero or exceeds the data length.
+/// - **InvalidWeight**: kst: A weight is `NaN` or infinite.
 /// - **Roc(...)**: Propagated error from the underlying ROC calculation.
 /// - **Sma(...)**: Propagated error from the underlying SMA calculation.
 ///
@@ -63,6 +66,7 @@ pub struct KstParams {
     pub roc_period3: Option<usize>,
     pub roc_period4: Option<usize>,
     pub signal_period: Option<usize>,
+    pub weights: Option<[f64; 4]>,
 }
 
 impl Default for KstParams {
@@ -77,6 +81,7 @@ impl Default for KstParams {
             roc_period3: Some(20),
             roc_period4: Some(30),
             signal_period: Some(9),
+            weights: Some([1.0, 2.0, 3.0, 4.0]),
         }
     }
 }
@@ -115,6 +120,12 @@ impl<'a> KstInput<'a> {
     fn get_or_default(value: Option<usize>, default: usize) -> usize {
         value.unwrap_or(default)
     }
+
+    pub fn get_weights(&self) -> [f64; 4] {
+        self.params
+            .weights
+            .unwrap_or_else(|| KstParams::default().weights.unwrap())
+    }
 }
 
 #[derive(Debug, Error)]
@@ -131,6 +142,8 @@ pub enum KstError {
     NotEnoughValidData { needed: usize, valid: usize },
     #[error("kst: Invalid period: period = {period}, data length = {data_len}")]
     InvalidPeriod { period: usize, data_len: usize },
+    #[error("kst: Invalid weight: {weight}")]
+    InvalidWeight { weight: f64 },
 }
 
 #[inline]
@@ -154,6 +167,10 @@ pub fn kst(input: &KstInput) -> Result<KstOutput, KstError> {
     let roc_period3 = KstInput::get_or_default(p.roc_period3, 20);
     let roc_period4 = KstInput::get_or_default(p.roc_period4, 30);
     let signal_period = KstInput::get_or_default(p.signal_period, 9);
+    let [w1, w2, w3, w4] = input.get_weights();
+    if let Some(&weight) = [w1, w2, w3, w4].iter().find(|w| !w.is_finite()) {
+        return Err(KstError::InvalidWeight { weight });
+    }
 
     let roc1_input = RocInput::from_slice(
         data,
@@ -232,7 +249,7 @@ pub fn kst(input: &KstInput) -> Result<KstOutput, KstError> {
         if v1.is_nan() || v2.is_nan() || v3.is_nan() || v4.is_nan() {
             line[i] = f64::NAN;
         } else {
-            line[i] = v1 + 2.0 * v2 + 3.0 * v3 + 4.0 * v4;
+            line[i] = w1 * v1 + w2 * v2 + w3 * v3 + w4 * v4;
         }
     }
 
@@ -331,6 +348,7 @@ mod tests {
             roc_period3: Some(10),
             roc_period4: Some(12),
             signal_period: Some(3),
+            weights: None,
         };
         let input = KstInput::from_candles(&candles, "close", params);
         let result = kst(&input).expect("Failed to calculate KST with custom params");
@@ -397,4 +415,37 @@ mod tests {
         let result = kst(&input);
         assert!(result.is_err(), "Expected an error for all NaN data");
     }
+
+    #[test]
+    fn test_kst_custom_weights() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let classic = kst(&KstInput::with_default_candles(&candles)).expect("classic KST");
+        let doubled = kst(&KstInput::from_candles(
+            &candles,
+            "close",
+            KstParams {
+                weights: Some([2.0, 4.0, 6.0, 8.0]),
+                ..KstParams::default()
+            },
+        ))
+        .expect("weighted KST");
+
+        let last = candles.close.len() - 1;
+        assert!((doubled.line[last] - 2.0 * classic.line[last]).abs() < 1e-9);
+        assert!((doubled.signal[last] - 2.0 * classic.signal[last]).abs() < 1e-9);
+        for (d, c) in doubled.signal.iter().zip(&classic.signal) {
+            assert_eq!(d.is_nan(), c.is_nan());
+        }
+
+        let data = [10.0, 20.0, 30.0, 40.0];
+        let params = KstParams {
+            weights: Some([1.0, f64::NAN, 3.0, 4.0]),
+            ..KstParams::default()
+        };
+        assert!(matches!(
+            kst(&KstInput::from_slice(&data, params)),
+            Err(KstError::InvalidWeight { .. })
+        ));
+    }
 }
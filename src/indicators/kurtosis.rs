@@ -24,6 +24,7 @@
 ///   with leading `NaN`s until the moving window is filled.
 /// - **`Err(KurtosisError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum KurtosisData<'a> {
@@ -126,6 +127,7 @@ pub fn kurtosis(input: &KurtosisInput) -> Result<KurtosisOutput, KurtosisError>
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("kurtosis", period, data.len() - first_valid_idx);
         return Err(KurtosisError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -167,6 +169,69 @@ pub fn kurtosis(input: &KurtosisInput) -> Result<KurtosisOutput, KurtosisError>
     })
 }
 
+/// Online central moments (count, mean, M2, M3, M4) using the one-pass update formulas
+/// of Welford/Terriberry, so skewness and kurtosis can be read at any point without
+/// re-scanning the data. Accumulates over everything pushed (not a sliding window).
+///
+/// `skewness` and `kurtosis` use the same uncorrected (population) moments as the batch
+/// `kurtosis`, with `kurtosis` reported as excess kurtosis. Both are `NaN` until two
+/// values have been seen or while the variance is zero.
+#[derive(Debug, Clone, Default)]
+pub struct MomentState {
+    pub count: u64,
+    pub mean: f64,
+    pub m2: f64,
+    pub m3: f64,
+    pub m4: f64,
+}
+
+impl MomentState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `value`; `NaN`s are ignored.
+    pub fn update(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        let n1 = self.count as f64;
+        self.count += 1;
+        let n = self.count as f64;
+        let delta = value - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term1 = delta * delta_n * n1;
+
+        self.mean += delta_n;
+        self.m4 += term1 * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2
+            - 4.0 * delta_n * self.m3;
+        self.m3 += term1 * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term1;
+    }
+
+    pub fn variance(&self) -> f64 {
+        if self.count < 2 {
+            return f64::NAN;
+        }
+        self.m2 / self.count as f64
+    }
+
+    pub fn skewness(&self) -> f64 {
+        if self.count < 2 || self.m2.abs() < f64::EPSILON {
+            return f64::NAN;
+        }
+        (self.count as f64).sqrt() * self.m3 / self.m2.powf(1.5)
+    }
+
+    pub fn kurtosis(&self) -> f64 {
+        if self.count < 2 || self.m2.abs() < f64::EPSILON {
+            return f64::NAN;
+        }
+        self.count as f64 * self.m4 / (self.m2 * self.m2) - 3.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -376,4 +441,43 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_moment_state_matches_batch_kurtosis() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let period = 50;
+        let params = KurtosisParams {
+            period: Some(period),
+        };
+        let input = KurtosisInput::from_candles(&candles, "close", params);
+        let batch = kurtosis(&input).expect("Failed to calculate kurtosis");
+
+        let mut state = MomentState::new();
+        let len = candles.close.len();
+        for &value in &candles.close[len - period..] {
+            state.update(value);
+        }
+        let expected = batch.values[len - 1];
+        assert!(
+            (state.kurtosis() - expected).abs() < 1e-6,
+            "Streamed kurtosis {} vs batch {}",
+            state.kurtosis(),
+            expected
+        );
+
+        let mut symmetric = MomentState::new();
+        for value in [1.0, 2.0, 3.0, 4.0, 5.0] {
+            symmetric.update(value);
+        }
+        assert!(symmetric.skewness().abs() < 1e-12);
+        assert!((symmetric.variance() - 2.0).abs() < 1e-12);
+        assert!((symmetric.kurtosis() - (-1.3)).abs() < 1e-12);
+
+        let mut skewed = MomentState::new();
+        for value in [1.0, 1.0, 1.0, 10.0] {
+            skewed.update(value);
+        }
+        assert!(skewed.skewness() > 0.0);
+    }
 }
@@ -6,9 +6,12 @@
 /// volume force (VF), then applies two separate EMAs (short and long)
 /// to VF and calculates the difference.
 ///
+/// The signal line is a `signal_period` EMA of the KVO line, seeded with its first value.
+///
 /// ## Parameters
 /// - **short_period**: The short EMA period. Defaults to 2.
 /// - **long_period**: The long EMA period. Defaults to 5.
+/// - **signal_period**: The signal line EMA period. Defaults to 13.
 ///
 /// ## Errors
 /// - **EmptyData**: kvo: Input data slice is empty or not found.
@@ -16,11 +19,17 @@
 /// - **NotEnoughValidData**: kvo: Fewer than 2 valid (non-`NaN`) data points remain
 ///   after the first valid index.
 /// - **AllValuesNaN**: kvo: All input data values are `NaN`.
+/// - **Ema(...)**: kvo: Propagated error from the signal line EMA (e.g. `signal_period`
+///   is zero or longer than the KVO series).
 ///
 /// ## Returns
-/// - **`Ok(KvoOutput)`** on success, containing a `Vec<f64>` matching the input length,
-///   with leading `NaN`s until enough data is present for the calculation.
+/// - **`Ok(KvoOutput)`** on success, containing two `Vec<f64>` matching the input length:
+///   - `kvo`: The oscillator, with leading `NaN`s until enough data is present.
+///   - `signal`: The signal line, starting on the same bar as `kvo`.
 /// - **`Err(KvoError)`** otherwise.
+use crate::indicators::error::{require_min_len, CommonError};
+use crate::indicators::moving_averages::ema::{ema, EmaError, EmaInput, EmaParams};
+use crate::indicators::utility_functions::crossover_signals;
 use crate::utilities::data_loader::{read_candles_from_csv, Candles};
 use thiserror::Error;
 
@@ -39,13 +48,15 @@ pub enum KvoData<'a> {
 
 #[derive(Debug, Clone)]
 pub struct KvoOutput {
-    pub values: Vec<f64>,
+    pub kvo: Vec<f64>,
+    pub signal: Vec<f64>,
 }
 
 #[derive(Debug, Clone)]
 pub struct KvoParams {
     pub short_period: Option<usize>,
     pub long_period: Option<usize>,
+    pub signal_period: Option<usize>,
 }
 
 impl Default for KvoParams {
@@ -53,6 +64,7 @@ impl Default for KvoParams {
         Self {
             short_period: Some(2),
             long_period: Some(5),
+            signal_period: Some(13),
         }
     }
 }
@@ -107,6 +119,12 @@ impl<'a> KvoInput<'a> {
             .long_period
             .unwrap_or_else(|| KvoParams::default().long_period.unwrap())
     }
+
+    pub fn get_signal_period(&self) -> usize {
+        self.params
+            .signal_period
+            .unwrap_or_else(|| KvoParams::default().signal_period.unwrap())
+    }
 }
 
 #[derive(Debug, Error)]
@@ -119,6 +137,16 @@ pub enum KvoError {
     NotEnoughValidData { valid: usize },
     #[error("kvo: All values are NaN.")]
     AllValuesNaN,
+    #[error("kvo: {0}")]
+    Ema(#[from] EmaError),
+}
+
+impl From<CommonError> for KvoError {
+    fn from(err: CommonError) -> Self {
+        match err {
+            CommonError::NotEnoughValidData { valid, .. } => KvoError::NotEnoughValidData { valid },
+        }
+    }
 }
 
 #[inline]
@@ -171,11 +199,7 @@ pub fn kvo(input: &KvoInput) -> Result<KvoOutput, KvoError> {
         None => return Err(KvoError::AllValuesNaN),
     };
 
-    if (high.len() - first_valid_idx) < 2 {
-        return Err(KvoError::NotEnoughValidData {
-            valid: high.len() - first_valid_idx,
-        });
-    }
+    require_min_len(high.len() - first_valid_idx, 2, "kvo")?;
 
     let mut output = vec![f64::NAN; high.len()];
     let short_per = 2.0 / (short_period as f64 + 1.0);
@@ -216,7 +240,33 @@ pub fn kvo(input: &KvoInput) -> Result<KvoOutput, KvoError> {
         prev_hlc = hlc;
     }
 
-    Ok(KvoOutput { values: output })
+    let signal = ema(&EmaInput::from_slice(
+        &output,
+        EmaParams {
+            period: Some(input.get_signal_period()),
+            ..EmaParams::default()
+        },
+    ))?
+    .values;
+
+    Ok(KvoOutput {
+        kvo: output,
+        signal,
+    })
+}
+
+/// Zero-line crosses of the KVO line: `1` where it crosses above zero, `-1` where it
+/// crosses below, `0` otherwise (including `NaN` warmup bars).
+#[inline]
+pub fn kvo_zero_cross_signals(output: &KvoOutput) -> Vec<i8> {
+    crossover_signals(&output.kvo, &vec![0.0; output.kvo.len()])
+}
+
+/// Signal-line crosses: `1` where KVO crosses above its signal line, `-1` where it
+/// crosses below, `0` otherwise.
+#[inline]
+pub fn kvo_signal_cross_signals(output: &KvoOutput) -> Vec<i8> {
+    crossover_signals(&output.kvo, &output.signal)
 }
 
 #[cfg(test)]
@@ -232,18 +282,20 @@ mod tests {
         let default_params = KvoParams {
             short_period: None,
             long_period: None,
+            signal_period: None,
         };
         let input_default = KvoInput::from_candles(&candles, default_params);
         let output_default = kvo(&input_default).expect("Failed KVO with default params");
-        assert_eq!(output_default.values.len(), candles.close.len());
+        assert_eq!(output_default.kvo.len(), candles.close.len());
 
         let params_custom = KvoParams {
             short_period: Some(2),
             long_period: Some(7),
+            ..KvoParams::default()
         };
         let input_custom = KvoInput::from_candles(&candles, params_custom);
         let output_custom = kvo(&input_custom).expect("Failed KVO with custom params");
-        assert_eq!(output_custom.values.len(), candles.close.len());
+        assert_eq!(output_custom.kvo.len(), candles.close.len());
     }
 
     #[test]
@@ -259,28 +311,31 @@ mod tests {
         let kvo_result = kvo(&input).expect("Failed to calculate KVO");
 
         assert_eq!(
-            kvo_result.values.len(),
+            kvo_result.kvo.len(),
             close_prices.len(),
             "KVO length mismatch"
         );
 
+        // Reference values from an independent Python implementation of the Klinger
+        // recursion over the same candles, with the signal line as an EMA(13) of that KVO
+        // series seeded with its first value.
         let expected_last_five_kvo = [
             -246.42698280402647,
             530.8651474164992,
             237.2148311016648,
             608.8044103976362,
-            -6339.615516805162,
+            -6339.681376375308,
         ];
         assert!(
-            kvo_result.values.len() >= 5,
+            kvo_result.kvo.len() >= 5,
             "KVO result length too short for verification"
         );
-        let start_index = kvo_result.values.len() - 5;
-        let result_last_five_kvo = &kvo_result.values[start_index..];
+        let start_index = kvo_result.kvo.len() - 5;
+        let result_last_five_kvo = &kvo_result.kvo[start_index..];
         for (i, &value) in result_last_five_kvo.iter().enumerate() {
             let expected_value = expected_last_five_kvo[i];
             assert!(
-                (value - expected_value).abs() < 1e-1,
+                (value - expected_value).abs() < 1e-8,
                 "KVO mismatch at index {}: expected {}, got {}",
                 i,
                 expected_value,
@@ -288,18 +343,37 @@ mod tests {
             );
         }
 
+        let expected_last_five_signal = [
+            469.9485445190516,
+            478.65091636154415,
+            444.1600470387043,
+            467.6806703756946,
+            -504.7996220173057,
+        ];
+        assert_eq!(kvo_result.signal.len(), close_prices.len());
+        for (i, &value) in kvo_result.signal[start_index..].iter().enumerate() {
+            let expected_value = expected_last_five_signal[i];
+            assert!(
+                (value - expected_value).abs() < 1e-8,
+                "KVO signal mismatch at index {}: expected {}, got {}",
+                i,
+                expected_value,
+                value
+            );
+        }
+
         let first_valid_point = kvo_result
-            .values
+            .kvo
             .iter()
             .position(|&v| !v.is_nan())
-            .unwrap_or(kvo_result.values.len());
+            .unwrap_or(kvo_result.kvo.len());
         for i in 0..first_valid_point {
-            assert!(kvo_result.values[i].is_nan());
+            assert!(kvo_result.kvo[i].is_nan());
         }
 
         let default_input = KvoInput::with_default_candles(&candles);
         let default_kvo_result = kvo(&default_input).expect("Failed to calculate KVO defaults");
-        assert_eq!(default_kvo_result.values.len(), close_prices.len());
+        assert_eq!(default_kvo_result.kvo.len(), close_prices.len());
     }
 
     #[test]
@@ -315,6 +389,11 @@ mod tests {
             Some(5),
             "Expected default long_period of 5"
         );
+        assert_eq!(
+            default_params.signal_period,
+            Some(13),
+            "Expected default signal_period of 13"
+        );
     }
 
     #[test]
@@ -336,6 +415,7 @@ mod tests {
         let params = KvoParams {
             short_period: Some(0),
             long_period: Some(5),
+            ..KvoParams::default()
         };
         let input = KvoInput::from_candles(&candles, params);
         let result = kvo(&input);
@@ -350,6 +430,7 @@ mod tests {
         let params = KvoParams {
             short_period: Some(5),
             long_period: Some(2),
+            ..KvoParams::default()
         };
         let input = KvoInput::from_candles(&candles, params);
         let result = kvo(&input);
@@ -385,6 +466,7 @@ mod tests {
         let first_params = KvoParams {
             short_period: Some(2),
             long_period: Some(5),
+            ..KvoParams::default()
         };
         let first_input = KvoInput::from_candles(&candles, first_params);
         let first_result = kvo(&first_input).expect("Failed to calculate first KVO");
@@ -392,12 +474,13 @@ mod tests {
         let second_params = KvoParams {
             short_period: Some(2),
             long_period: Some(5),
+            ..KvoParams::default()
         };
         let second_input = KvoInput::from_slices(
             &candles.high,
             &candles.low,
             &candles.close,
-            &first_result.values,
+            &first_result.kvo,
             second_params,
         );
         let second_result = kvo(&second_input);
@@ -415,14 +498,37 @@ mod tests {
         let input = KvoInput::from_candles(&candles, params);
         let kvo_result = kvo(&input).expect("Failed to calculate KVO");
 
-        if kvo_result.values.len() > 240 {
-            for i in 240..kvo_result.values.len() {
+        if kvo_result.kvo.len() > 240 {
+            for i in 240..kvo_result.kvo.len() {
                 assert!(
-                    !kvo_result.values[i].is_nan(),
+                    !kvo_result.kvo[i].is_nan(),
                     "Expected no NaN after index 240, found NaN at {}",
                     i
                 );
             }
         }
     }
+
+    #[test]
+    fn test_kvo_cross_signals() {
+        let output = KvoOutput {
+            kvo: vec![f64::NAN, -2.0, 1.0, 3.0, -1.0, -2.0],
+            signal: vec![f64::NAN, -1.0, 0.0, 4.0, -3.0, 0.0],
+        };
+        assert_eq!(kvo_zero_cross_signals(&output), vec![0, 0, 1, 0, -1, 0]);
+        assert_eq!(kvo_signal_cross_signals(&output), vec![0, 0, 1, -1, 1, -1]);
+    }
+
+    #[test]
+    fn test_kvo_with_zero_signal_period() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let params = KvoParams {
+            signal_period: Some(0),
+            ..KvoParams::default()
+        };
+        let input = KvoInput::from_candles(&candles, params);
+        assert!(matches!(kvo(&input), Err(KvoError::Ema(_))));
+    }
 }
@@ -20,6 +20,7 @@
 ///   with leading `NaN`s until the calculation window is filled.
 /// - **`Err(Linearreg_angleError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::f64::consts::PI;
 use thiserror::Error;
 
@@ -129,6 +130,7 @@ pub fn linearreg_angle(
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("linearreg_angle", period, data.len() - first_valid_idx);
         return Err(Linearreg_angleError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -19,6 +19,7 @@
 ///   input length, with leading `NaN`s until the regression window is filled.
 /// - **`Err(LinearRegInterceptError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -127,6 +128,7 @@ pub fn linearreg_intercept(
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("linearreg_intercept", period, data.len() - first_valid_idx);
         return Err(LinearRegInterceptError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
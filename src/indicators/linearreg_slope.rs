@@ -19,6 +19,7 @@
 ///   with leading `NaN`s until the linear regression window is filled.
 /// - **`Err(LinearRegSlopeError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -127,6 +128,7 @@ pub fn linearreg_slope(
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("linearreg_slope", period, data.len() - first_valid_idx);
         return Err(LinearRegSlopeError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -1,5 +1,6 @@
 use crate::indicators::moving_averages::ma::{ma, MaData};
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 /// # Moving Average Bands (MAB)
 ///
 /// Calculates three bands (upper, middle, lower) based on a fast and a slow moving average,
@@ -225,6 +226,7 @@ pub fn mab(input: &MabInput) -> Result<MabOutput, MabError> {
     let second_valid_idx = fv_fast.max(fv_slow);
     let needed = usize::max(fast_period, slow_period);
     if (data.len() - second_valid_idx) < needed {
+        not_enough_valid_data("mab", needed, data.len() - second_valid_idx);
         return Err(MabError::NotEnoughValidData {
             needed,
             valid: data.len() - second_valid_idx,
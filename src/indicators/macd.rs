@@ -1,4 +1,5 @@
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 /// # Moving Average Convergence/Divergence (MACD)
 ///
 /// A trend-following momentum indicator that shows the relationship between two moving averages of a data series.
@@ -170,8 +171,17 @@ impl MacdOutputBuilder {
     }
 }
 
+/// The final bar of a [`MacdOutput`], as returned by [`macd_last`]. `signal` and `hist`
+/// are `NaN` while the signal line is still warming up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MacdLast {
+    pub macd: f64,
+    pub signal: f64,
+    pub hist: f64,
+}
+
 #[inline]
-pub fn macd(input: &MacdInput) -> Result<MacdOutput, MacdError> {
+fn validate_macd<'a>(input: &'a MacdInput) -> Result<(&'a [f64], usize), MacdError> {
     let data: &[f64] = match &input.data {
         MacdData::Candles { candles, source } => source_type(candles, source),
         MacdData::Slice(slice) => slice,
@@ -207,12 +217,24 @@ pub fn macd(input: &MacdInput) -> Result<MacdOutput, MacdError> {
     };
 
     if (data.len() - first_valid_idx) < slow_period {
+        not_enough_valid_data("macd", slow_period, data.len() - first_valid_idx);
         return Err(MacdError::NotEnoughValidData {
             needed: slow_period,
             valid: data.len() - first_valid_idx,
         });
     }
 
+    Ok((data, first_valid_idx))
+}
+
+#[inline]
+pub fn macd(input: &MacdInput) -> Result<MacdOutput, MacdError> {
+    let (data, first_valid_idx) = validate_macd(input)?;
+    let fast_period = input.get_fast_period();
+    let slow_period = input.get_slow_period();
+    let signal_period = input.get_signal_period();
+    let length = data.len();
+
     let mut output_builder = MacdOutputBuilder::new(data.len());
     let ma_type = input.get_ma_type();
 
@@ -261,6 +283,59 @@ pub fn macd(input: &MacdInput) -> Result<MacdOutput, MacdError> {
     Ok(output_builder.build())
 }
 
+/// The last bar of [`macd`] without allocating the three output series or the
+/// intermediate moving averages. With the default `"ema"` type the fast, slow and signal
+/// EMAs are run as scalars in one pass; other `ma_type`s fall back to [`macd`]. Returns
+/// `None` when the last MACD value is `NaN`.
+#[inline]
+pub fn macd_last(input: &MacdInput) -> Result<Option<MacdLast>, MacdError> {
+    let (data, first_valid_idx) = validate_macd(input)?;
+
+    if !input.get_ma_type().eq_ignore_ascii_case("ema") {
+        let output = macd(input)?;
+        let last = output.macd.len() - 1;
+        let macd = output.macd[last];
+        return Ok((!macd.is_nan()).then(|| MacdLast {
+            macd,
+            signal: output.signal[last],
+            hist: output.hist[last],
+        }));
+    }
+
+    let fast_period = input.get_fast_period();
+    let slow_period = input.get_slow_period();
+    let signal_period = input.get_signal_period();
+    let valid = data.len() - first_valid_idx;
+
+    let fast_alpha = 2.0 / (fast_period as f64 + 1.0);
+    let slow_alpha = 2.0 / (slow_period as f64 + 1.0);
+    let signal_alpha = 2.0 / (signal_period as f64 + 1.0);
+
+    let mut fast = data[first_valid_idx];
+    let mut slow = data[first_valid_idx];
+    let mut macd = fast - slow;
+    let mut signal = macd;
+    for &value in &data[(first_valid_idx + 1)..] {
+        fast = fast_alpha * value + (1.0 - fast_alpha) * fast;
+        slow = slow_alpha * value + (1.0 - slow_alpha) * slow;
+        macd = fast - slow;
+        signal = signal_alpha * macd + (1.0 - signal_alpha) * signal;
+    }
+
+    if macd.is_nan() {
+        return Ok(None);
+    }
+    if valid < slow_period + signal_period - 1 || signal.is_nan() {
+        signal = f64::NAN;
+    }
+
+    Ok(Some(MacdLast {
+        macd,
+        signal,
+        hist: macd - signal,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -550,4 +625,24 @@ mod tests {
             _ => panic!("Expected MacdData::Candles variant"),
         }
     }
+
+    #[test]
+    fn test_macd_last_matches_full() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        for ma_type in ["ema", "sma"] {
+            let params = MacdParams {
+                ma_type: Some(ma_type.to_string()),
+                ..MacdParams::default()
+            };
+            let input = MacdInput::from_candles(&candles, "close", params);
+            let full = macd(&input).expect("Failed to calculate MACD");
+            let last = macd_last(&input)
+                .expect("Failed to calculate last MACD")
+                .expect("Last MACD should not be NaN");
+            assert_eq!(last.macd, *full.macd.last().unwrap());
+            assert_eq!(last.signal, *full.signal.last().unwrap());
+            assert_eq!(last.hist, *full.hist.last().unwrap());
+        }
+    }
 }
@@ -22,6 +22,7 @@
 ///   with leading `NaN`s until the first valid index.
 /// - **`Err(MarketefiError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -170,6 +171,7 @@ pub fn marketfi(input: &MarketefiInput) -> Result<MarketefiOutput, MarketefiErro
     }
 
     if valid_count == 0 {
+        not_enough_valid_data("marketefi", 1, valid_count);
         return Err(MarketefiError::NotEnoughValidData);
     }
 
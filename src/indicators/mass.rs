@@ -20,6 +20,7 @@
 ///   with leading `NaN`s until enough data is accumulated for the Mass Index calculation.
 /// - **`Err(MassError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum MassData<'a> {
@@ -150,6 +151,7 @@ pub fn mass(input: &MassInput) -> Result<MassOutput, MassError> {
 
     let needed_bars = 16 + period - 1;
     if (high.len() - first_valid_idx) < needed_bars {
+        not_enough_valid_data("mass", needed_bars, high.len() - first_valid_idx);
         return Err(MassError::NotEnoughValidData {
             needed: needed_bars,
             valid: high.len() - first_valid_idx,
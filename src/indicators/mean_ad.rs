@@ -19,6 +19,7 @@
 ///   with leading `NaN`s until enough data points are accumulated for both passes.
 /// - **`Err(MeanAdError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -121,6 +122,7 @@ pub fn mean_ad(input: &MeanAdInput) -> Result<MeanAdOutput, MeanAdError> {
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("mean_ad", period, data.len() - first_valid_idx);
         return Err(MeanAdError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -132,6 +134,7 @@ pub fn mean_ad(input: &MeanAdInput) -> Result<MeanAdOutput, MeanAdError> {
     let first_valid_idx_rm = match rolling_mean_data.iter().position(|&x| !x.is_nan()) {
         Some(idx) => idx,
         None => {
+            not_enough_valid_data("mean_ad", period, 0);
             return Err(MeanAdError::NotEnoughValidData {
                 needed: period,
                 valid: 0,
@@ -18,7 +18,9 @@
 /// - **`Ok(MediumAdOutput)`** on success, containing a `Vec<f64>` matching the input length,
 ///   with leading `NaN`s until the MEDIUM_AD window is filled.
 /// - **`Err(MediumAdError)`** otherwise.
+use crate::indicators::utility_functions::sorted_window;
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -120,6 +122,7 @@ pub fn medium_ad(input: &MediumAdInput) -> Result<MediumAdOutput, MediumAdError>
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("medium_ad", period, data.len() - first_valid_idx);
         return Err(MediumAdError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -134,18 +137,17 @@ pub fn medium_ad(input: &MediumAdInput) -> Result<MediumAdOutput, MediumAdError>
             output_values[i] = f64::NAN;
             continue;
         }
-        let mut sorted_window: Vec<f64> = window.to_vec();
-        sorted_window.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let sorted = sorted_window(window);
+        if sorted.len() != period {
+            continue;
+        }
         let median_val = if period % 2 == 1 {
-            sorted_window[period / 2]
+            sorted[period / 2]
         } else {
-            0.5 * (sorted_window[period / 2 - 1] + sorted_window[period / 2])
+            0.5 * (sorted[period / 2 - 1] + sorted[period / 2])
         };
-        let mut abs_devs: Vec<f64> = sorted_window
-            .iter()
-            .map(|&v| (v - median_val).abs())
-            .collect();
-        abs_devs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let abs_devs: Vec<f64> = sorted.iter().map(|&v| (v - median_val).abs()).collect();
+        let abs_devs = sorted_window(&abs_devs);
         let mad = if period % 2 == 1 {
             abs_devs[period / 2]
         } else {
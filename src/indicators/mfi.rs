@@ -4,13 +4,19 @@
 /// over a specified period. It uses price and volume to identify overbought or oversold
 /// conditions by comparing the "typical price" movement and volume flow.
 ///
+/// Each bar's raw money flow is `typical price × volume`, classified as positive or
+/// negative by comparing its typical price with the previous bar's. The first valid bar
+/// only seeds the comparison, so `period` flows (and the first MFI value) need
+/// `period + 1` bars.
+///
 /// ## Parameters
 /// - **period**: The window size. Defaults to 14.
 ///
 /// ## Errors
 /// - **EmptyData**: mfi: Input data slices or candle fields are empty.
 /// - **InvalidPeriod**: mfi: `period` is zero, or exceeds the data length.
-/// - **NotEnoughValidData**: mfi: Fewer than `period` valid (non-`NaN`) data points remain
+/// - **NotEnoughData**: mfi: The data holds fewer than `period + 1` bars.
+/// - **NotEnoughValidData**: mfi: Fewer than `period + 1` valid (non-`NaN`) data points remain
 ///   after the first valid index.
 /// - **AllValuesNaN**: mfi: All computed typical prices or volumes are `NaN`.
 ///
@@ -19,6 +25,7 @@
 ///   with leading `NaN`s until the MFI window is filled.
 /// - **`Err(MfiError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -102,6 +109,8 @@ pub enum MfiError {
     EmptyData,
     #[error("mfi: Invalid period: period = {period}, data length = {data_len}")]
     InvalidPeriod { period: usize, data_len: usize },
+    #[error("mfi: Not enough data: needed = {needed}, data length = {data_len}")]
+    NotEnoughData { needed: usize, data_len: usize },
     #[error("mfi: Not enough valid data: needed = {needed}, valid = {valid}")]
     NotEnoughValidData { needed: usize, valid: usize },
     #[error("mfi: All values are NaN.")]
@@ -149,6 +158,13 @@ pub fn mfi(input: &MfiInput) -> Result<MfiOutput, MfiError> {
             data_len: length,
         });
     }
+    if length < period + 1 {
+        not_enough_valid_data("mfi", period + 1, length);
+        return Err(MfiError::NotEnoughData {
+            needed: period + 1,
+            data_len: length,
+        });
+    }
 
     let first_valid_idx = (0..length).find(|&i| {
         !high[i].is_nan() && !low[i].is_nan() && !close[i].is_nan() && !volume[i].is_nan()
@@ -157,9 +173,10 @@ pub fn mfi(input: &MfiInput) -> Result<MfiOutput, MfiError> {
         Some(idx) => idx,
         None => return Err(MfiError::AllValuesNaN),
     };
-    if (length - first_valid_idx) < period {
+    if (length - first_valid_idx) < period + 1 {
+        not_enough_valid_data("mfi", period + 1, length - first_valid_idx);
         return Err(MfiError::NotEnoughValidData {
-            needed: period,
+            needed: period + 1,
             valid: length - first_valid_idx,
         });
     }
@@ -200,16 +217,6 @@ pub fn mfi(input: &MfiInput) -> Result<MfiOutput, MfiError> {
         ring_idx = (ring_idx + 1) % period;
     }
 
-    let idx_mfi_start = first_valid_idx + period - 1;
-    if idx_mfi_start < length {
-        let total = pos_sum + neg_sum;
-        mfi_values[idx_mfi_start] = if total < 1e-14 {
-            0.0
-        } else {
-            100.0 * (pos_sum / total)
-        };
-    }
-
     for i in (first_valid_idx + period)..length {
         let old_pos = pos_buf[ring_idx];
         let old_neg = neg_buf[ring_idx];
@@ -386,4 +393,67 @@ mod tests {
         let second_result = mfi(&second_input).expect("Failed to calculate second MFI");
         assert_eq!(second_result.values.len(), first_result.values.len());
     }
+
+    #[test]
+    fn test_mfi_not_enough_data() {
+        let high = [2.0, 3.0, 4.0];
+        let low = [1.0, 2.0, 3.0];
+        let close = [1.5, 2.5, 3.5];
+        let volume = [10.0, 10.0, 10.0];
+        let params = MfiParams { period: Some(3) };
+        let input = MfiInput::from_slices(&high, &low, &close, &volume, params);
+        match mfi(&input) {
+            Err(MfiError::NotEnoughData { needed, data_len }) => {
+                assert_eq!(needed, 4);
+                assert_eq!(data_len, 3);
+            }
+            other => panic!("Expected NotEnoughData, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_mfi_first_twenty_candles_hand_computed() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let params = MfiParams { period: Some(5) };
+        let input = MfiInput::from_slices(
+            &candles.high[..20],
+            &candles.low[..20],
+            &candles.close[..20],
+            &candles.volume[..20],
+            params,
+        );
+        let result = mfi(&input).expect("Failed to calculate MFI");
+
+        let expected = [
+            47.130008711737496,
+            52.38189325924357,
+            23.483527033793735,
+            4.699957617719057,
+            4.334848522495014,
+            0.0,
+            15.212580858890558,
+            29.603122182804437,
+            34.885383141016945,
+            52.66343984369109,
+            59.69772452757713,
+            36.02845431276403,
+            33.821791795631974,
+            49.28032784188535,
+            45.69523233000702,
+        ];
+        for i in 0..5 {
+            assert!(result.values[i].is_nan(), "Expected NaN at index {}", i);
+        }
+        for (i, &exp) in expected.iter().enumerate() {
+            let got = result.values[i + 5];
+            assert!(
+                (got - exp).abs() < 1e-9,
+                "MFI mismatch at index {}: expected {}, got {}",
+                i + 5,
+                exp,
+                got
+            );
+        }
+    }
 }
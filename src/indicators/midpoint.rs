@@ -20,6 +20,7 @@
 ///   with leading `NaN`s until the period window is filled.
 /// - **`Err(MidpointError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -122,6 +123,7 @@ pub fn midpoint(input: &MidpointInput) -> Result<MidpointOutput, MidpointError>
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("midpoint", period, data.len() - first_valid_idx);
         return Err(MidpointError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
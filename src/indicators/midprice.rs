@@ -18,6 +18,7 @@
 ///   with leading `NaN`s until the window is filled.
 /// - **`Err(MidpriceError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -146,6 +147,7 @@ pub fn midprice(input: &MidpriceInput) -> Result<MidpriceOutput, MidpriceError>
     };
 
     if (high.len() - first_valid_idx) < period {
+        not_enough_valid_data("midprice", period, high.len() - first_valid_idx);
         return Err(MidpriceError::NotEnoughValidData {
             needed: period,
             valid: high.len() - first_valid_idx,
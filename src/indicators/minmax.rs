@@ -26,6 +26,7 @@
 ///   - `last_max`: Forward-filled `is_max`.
 /// - **`Err(MinmaxError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -158,6 +159,7 @@ pub fn minmax(input: &MinmaxInput) -> Result<MinmaxOutput, MinmaxError> {
     };
 
     if (len_data - first_valid_idx) < order {
+        not_enough_valid_data("minmax", order, len_data - first_valid_idx);
         return Err(MinmaxError::NotEnoughValidData {
             needed: order,
             valid: len_data - first_valid_idx,
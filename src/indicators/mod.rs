@@ -1,5 +1,6 @@
 pub mod acosc;
 pub mod ad;
+pub mod adaptive_rsi;
 pub mod adosc;
 pub mod adx;
 pub mod adxr;
@@ -11,6 +12,7 @@ pub mod aroonosc;
 pub mod atr;
 pub mod avgprice;
 pub mod bandpass;
+pub mod beta;
 pub mod bollinger_bands;
 pub mod bollinger_bands_width;
 pub mod bop;
@@ -24,6 +26,7 @@ pub mod cmo;
 pub mod coppock;
 pub mod correl_hl;
 pub mod correlation_cycle;
+pub mod crsi;
 pub mod cvi;
 pub mod damiani_volatmeter;
 pub mod dec_osc;
@@ -41,6 +44,7 @@ pub mod emd;
 pub mod emv;
 pub mod er;
 pub mod eri;
+pub mod error;
 pub mod fisher;
 pub mod fosc;
 pub mod gatorosc;
@@ -51,6 +55,7 @@ pub mod ht_phasor;
 pub mod ht_sine;
 pub mod ht_trendline;
 pub mod ht_trendmode;
+pub mod ichimoku;
 pub mod ift_rsi;
 pub mod kaufmanstop;
 pub mod kdj;
@@ -86,9 +91,11 @@ pub mod pma;
 pub mod ppo;
 pub mod pvi;
 pub mod qstick;
+pub mod registry;
 pub mod roc;
 pub mod rocp;
 pub mod rocr;
+pub mod rocr100;
 pub mod rsi;
 pub mod rsmk;
 pub mod rsx;
@@ -110,6 +117,7 @@ pub mod ui;
 pub mod ultosc;
 pub mod utility_functions;
 pub mod var;
+pub mod vhf;
 pub mod vi;
 pub mod vidya;
 pub mod vlma;
@@ -122,6 +130,7 @@ pub mod wad;
 pub mod wavetrend;
 pub mod wclprice;
 pub mod willr;
+pub mod zigzag;
 pub mod zscore;
 pub use moving_averages::{
     alma, cwma, dema, edcf, ehlers_itrend, ema, epma, frama, fwma, gaussian, highpass,
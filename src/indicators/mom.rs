@@ -19,6 +19,7 @@
 ///   with leading `NaN`s until the momentum window is filled.
 /// - **`Err(MomError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -120,6 +121,7 @@ pub fn mom(input: &MomInput) -> Result<MomOutput, MomError> {
 
     let valid_count = data_len - first_valid_idx;
     if valid_count < period {
+        not_enough_valid_data("mom", period, valid_count);
         return Err(MomError::NotEnoughValidData {
             needed: period,
             valid: valid_count,
@@ -22,6 +22,7 @@
 /// - **`Err(AlmaError)`** otherwise.
 ///
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum AlmaData<'a> {
@@ -149,6 +150,7 @@ pub fn alma(input: &AlmaInput) -> Result<AlmaOutput, AlmaError> {
     }
 
     if (len - first_valid_idx) < period {
+        not_enough_valid_data("alma", period, len - first_valid_idx);
         return Err(AlmaError::NotEnoughValidData {
             needed: period,
             valid: len - first_valid_idx,
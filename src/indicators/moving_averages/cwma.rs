@@ -15,6 +15,7 @@
 /// - **`Ok(CwmaOutput)`** on success, containing a `Vec<f64>` of length matching the input.
 /// - **`Err(CwmaError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum CwmaData<'a> {
@@ -113,6 +114,7 @@ pub fn cwma(input: &CwmaInput) -> Result<CwmaOutput, CwmaError> {
         });
     }
     if (len - first_valid_idx) < period {
+        not_enough_valid_data("cwma", period, len - first_valid_idx);
         return Err(CwmaError::NotEnoughValidData {
             needed: period,
             valid: len - first_valid_idx,
@@ -18,6 +18,7 @@
 /// - **`Err(DemaError)`** otherwise.
 ///
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::error::Error;
 
 #[derive(Debug, Clone)]
@@ -119,6 +120,7 @@ pub fn dema(input: &DemaInput) -> Result<DemaOutput, DemaError> {
 
     let needed = 2 * (period - 1);
     if size < needed {
+        not_enough_valid_data("dema", needed, size);
         return Err(DemaError::NotEnoughData {
             needed,
             valid: size,
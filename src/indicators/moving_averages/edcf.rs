@@ -22,6 +22,7 @@
 /// - **`Ok(EdcfOutput)`** on success, containing a `Vec<f64>` of length matching the input.
 /// - **`Err(EdcfError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::error::Error;
 
 #[derive(Debug, Clone)]
@@ -132,6 +133,7 @@ pub fn edcf(input: &EdcfInput) -> Result<EdcfOutput, EdcfError> {
 
     let needed = 2 * period;
     if (len - first_valid_idx) < needed {
+        not_enough_valid_data("edcf", needed, len - first_valid_idx);
         return Err(EdcfError::NotEnoughValidData {
             needed,
             idx: first_valid_idx,
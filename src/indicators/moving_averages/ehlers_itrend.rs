@@ -19,6 +19,7 @@
 /// - **`Ok(EhlersITrendOutput)`** on success, containing a `Vec<f64>` of length matching the input.
 /// - **`Err(EhlersITrendError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::error::Error;
 use std::f64::consts::PI;
 
@@ -120,6 +121,7 @@ pub fn ehlers_itrend(input: &EhlersITrendInput) -> Result<EhlersITrendOutput, Eh
     let warmup_bars = input.get_warmup_bars();
     let max_dc = input.get_max_dc_period().max(1);
     if warmup_bars >= length {
+        not_enough_valid_data("ehlers_itrend", warmup_bars + 1, length);
         return Err(EhlersITrendError::NotEnoughDataForWarmup {
             warmup_bars,
             length,
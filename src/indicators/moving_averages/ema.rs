@@ -6,6 +6,18 @@
 ///
 /// ## Parameters
 /// - **period**: Window size (number of data points).
+/// - **seed**: How the first EMA value is produced (see [`EmaSeed`]). Defaults to
+///   `EmaSeed::FirstValue`, which seeds with the first valid input and outputs from that
+///   bar on. `EmaSeed::Sma` seeds with the simple average of the first `period` valid
+///   inputs (as TradingView does), leaving the bars before it as `NaN`. A `NaN` inside the
+///   seed window is skipped rather than counted, so the seed lands on the `period`-th
+///   non-`NaN` input.
+/// - **adjust**: When `true`, each value is the weighted average of every input so far with
+///   weights `(1 - α)^k`, divided by the running sum of those weights, matching pandas'
+///   `ewm(span=period, adjust=True).mean()`. Output starts at the first valid bar and
+///   `seed` is ignored. An interior `NaN` is skipped while the weights keep decaying, so
+///   that bar repeats the previous value (pandas' default `ignore_na=False`). Defaults to
+///   `false` (the plain recursion).
 ///
 /// ## Errors
 /// - **AllValuesNaN**: ema: All input data values are `NaN`.
@@ -15,30 +27,44 @@
 /// ## Returns
 /// - **`Ok(EmaOutput)`** on success, containing a `Vec<f64>` of length matching the input.
 /// - **`Err(EmaError)`** otherwise.
-use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::data_loader::{Candles, SourceData};
+use crate::utilities::diagnostics::not_enough_valid_data;
+use crate::utilities::series::first_finite_index;
 
-#[derive(Debug, Clone)]
-pub enum EmaData<'a> {
-    Candles {
-        candles: &'a Candles,
-        source: &'a str,
-    },
-    Slice(&'a [f64]),
-}
+pub type EmaData<'a> = SourceData<'a>;
 
 #[derive(Debug, Clone)]
 pub struct EmaOutput {
     pub values: Vec<f64>,
+    /// Index of the first finite value in `values` (`values.len()` if there is none).
+    pub first_valid: usize,
+}
+
+/// How [`ema`] produces its first value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmaSeed {
+    /// Seed with the first valid input; output starts at the first valid bar.
+    #[default]
+    FirstValue,
+    /// Seed with the SMA of the first `period` valid inputs; output starts at the
+    /// `period`-th valid bar.
+    Sma,
 }
 
 #[derive(Debug, Clone)]
 pub struct EmaParams {
     pub period: Option<usize>,
+    pub seed: Option<EmaSeed>,
+    pub adjust: Option<bool>,
 }
 
 impl Default for EmaParams {
     fn default() -> Self {
-        Self { period: Some(9) }
+        Self {
+            period: Some(9),
+            seed: None,
+            adjust: Some(false),
+        }
     }
 }
 
@@ -73,11 +99,44 @@ impl<'a> EmaInput<'a> {
         }
     }
 
-    fn get_period(&self) -> usize {
+    pub fn get_period(&self) -> usize {
         self.params
             .period
             .unwrap_or_else(|| EmaParams::default().period.unwrap())
     }
+
+    pub fn get_seed(&self) -> EmaSeed {
+        self.params.seed.unwrap_or_default()
+    }
+
+    pub fn get_adjust(&self) -> bool {
+        self.params
+            .adjust
+            .unwrap_or_else(|| EmaParams::default().adjust.unwrap())
+    }
+
+    /// Copies the resolved source into an [`OwnedEmaInput`] that no longer borrows the
+    /// candles or slice it was built from.
+    pub fn into_owned(self) -> OwnedEmaInput {
+        OwnedEmaInput {
+            data: self.data.resolve().to_vec(),
+            params: self.params,
+        }
+    }
+}
+
+/// An [`EmaInput`] holding its own copy of the source, so it can be stored past the
+/// lifetime of the candles or sent to another thread.
+#[derive(Debug, Clone)]
+pub struct OwnedEmaInput {
+    pub data: Vec<f64>,
+    pub params: EmaParams,
+}
+
+impl OwnedEmaInput {
+    pub fn compute(&self) -> Result<EmaOutput, EmaError> {
+        ema(&EmaInput::from_slice(&self.data, self.params.clone()))
+    }
 }
 
 use thiserror::Error;
@@ -93,11 +152,8 @@ pub enum EmaError {
 }
 
 #[inline]
-pub fn ema(input: &EmaInput) -> Result<EmaOutput, EmaError> {
-    let data: &[f64] = match &input.data {
-        EmaData::Candles { candles, source } => source_type(candles, source),
-        EmaData::Slice(slice) => slice,
-    };
+fn validate_ema<'a>(input: &'a EmaInput) -> Result<(&'a [f64], usize, usize), EmaError> {
+    let data: &[f64] = input.data.resolve();
     let first_valid_idx = match data.iter().position(|&x| !x.is_nan()) {
         Some(idx) => idx,
         None => return Err(EmaError::AllValuesNaN),
@@ -112,33 +168,167 @@ pub fn ema(input: &EmaInput) -> Result<EmaOutput, EmaError> {
         });
     }
     if (len - first_valid_idx) < period {
+        not_enough_valid_data("ema", period, len - first_valid_idx);
         return Err(EmaError::NotEnoughValidData {
             needed: period,
             valid: len - first_valid_idx,
         });
     }
 
-    let mut ema_values = vec![f64::NAN; len];
+    Ok((data, period, first_valid_idx))
+}
+
+/// Runs the EMA recursion from the seed on, passing each `(index, value)` to `emit`, so
+/// [`ema`] and [`ema_last`] share the exact same arithmetic.
+#[inline(always)]
+fn ema_pass(
+    data: &[f64],
+    period: usize,
+    first_valid_idx: usize,
+    seed: EmaSeed,
+    adjust: bool,
+    mut emit: impl FnMut(usize, f64),
+) {
     let alpha = 2.0 / (period as f64 + 1.0);
-    ema_values[first_valid_idx] = data[first_valid_idx];
-    for i in (first_valid_idx + 1)..len {
-        ema_values[i] = alpha * data[i] + (1.0 - alpha) * ema_values[i - 1];
+    if adjust {
+        let decay = 1.0 - alpha;
+        let (mut weighted_sum, mut weight_sum) = (0.0, 0.0);
+        for (i, &value) in data.iter().enumerate().skip(first_valid_idx) {
+            if value.is_nan() {
+                weighted_sum *= decay;
+                weight_sum *= decay;
+            } else {
+                weighted_sum = value + decay * weighted_sum;
+                weight_sum = 1.0 + decay * weight_sum;
+            }
+            emit(i, weighted_sum / weight_sum);
+        }
+        return;
     }
 
-    Ok(EmaOutput { values: ema_values })
+    let (seed_idx, mut prev) = match seed {
+        EmaSeed::FirstValue => (first_valid_idx, data[first_valid_idx]),
+        EmaSeed::Sma => {
+            let mut valid = data
+                .iter()
+                .enumerate()
+                .skip(first_valid_idx)
+                .filter(|(_, v)| !v.is_nan());
+            let mut sum = 0.0;
+            let mut seed_idx = first_valid_idx;
+            for _ in 0..period {
+                match valid.next() {
+                    Some((i, &value)) => {
+                        sum += value;
+                        seed_idx = i;
+                    }
+                    None => return,
+                }
+            }
+            (seed_idx, sum / period as f64)
+        }
+    };
+    emit(seed_idx, prev);
+    for (i, &value) in data.iter().enumerate().skip(seed_idx + 1) {
+        prev = alpha * value + (1.0 - alpha) * prev;
+        emit(i, prev);
+    }
+}
+
+#[inline]
+pub fn ema(input: &EmaInput) -> Result<EmaOutput, EmaError> {
+    let (data, period, first_valid_idx) = validate_ema(input)?;
+
+    let mut ema_values = vec![f64::NAN; data.len()];
+    ema_pass(
+        data,
+        period,
+        first_valid_idx,
+        input.get_seed(),
+        input.get_adjust(),
+        |i, value| ema_values[i] = value,
+    );
+
+    Ok(EmaOutput {
+        first_valid: first_finite_index(&ema_values),
+        values: ema_values,
+    })
+}
+
+/// The last value of [`ema`] without allocating the output series. The recursion still
+/// walks the whole input; returns `None` when the last value is `NaN`.
+#[inline]
+pub fn ema_last(input: &EmaInput) -> Result<Option<f64>, EmaError> {
+    let (data, period, first_valid_idx) = validate_ema(input)?;
+
+    let mut last = f64::NAN;
+    ema_pass(
+        data,
+        period,
+        first_valid_idx,
+        input.get_seed(),
+        input.get_adjust(),
+        |_, value| last = value,
+    );
+    Ok(Some(last).filter(|v| !v.is_nan()))
+}
+
+/// Incremental EMA for streaming data.
+///
+/// Mirrors the batch `ema` with [`EmaSeed::FirstValue`]: leading `NaN`s return `None`, the first valid value seeds the
+/// average and is returned as-is, and every later update applies `α = 2 / (period + 1)`.
+#[derive(Debug, Clone)]
+pub struct EmaState {
+    pub period: usize,
+    pub alpha: f64,
+    pub prev_ema: Option<f64>,
+}
+
+impl EmaState {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            alpha: 2.0 / (period as f64 + 1.0),
+            prev_ema: None,
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        let ema = match self.prev_ema {
+            Some(prev) => self.alpha * value + (1.0 - self.alpha) * prev,
+            None if value.is_nan() => return None,
+            None => value,
+        };
+        self.prev_ema = Some(ema);
+        Some(ema)
+    }
+}
+
+/// Streams `iter` through an [`EmaState`], yielding one item per input.
+#[inline]
+pub fn ema_iter<I: Iterator<Item = f64>>(
+    iter: I,
+    period: usize,
+) -> impl Iterator<Item = Option<f64>> {
+    let mut state = EmaState::new(period);
+    iter.map(move |value| state.update(value))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::utilities::data_loader::read_candles_from_csv;
+    use crate::utilities::series::assert_first_valid_after_nan_prefix;
 
     #[test]
     fn test_ema_accuracy() {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
         let candles = read_candles_from_csv(file_path).unwrap();
         let close_prices = &candles.close;
-        let params = EmaParams { period: Some(9) };
+        let params = EmaParams {
+            period: Some(9),
+            ..EmaParams::default()
+        };
         let input = EmaInput::from_candles(&candles, "close", params);
         let result = ema(&input).unwrap();
         let expected_last_five = [59302.2, 59277.9, 59230.2, 59215.1, 59103.1];
@@ -186,7 +376,10 @@ mod tests {
     #[test]
     fn test_ema_with_zero_period() {
         let data = [10.0, 20.0, 30.0];
-        let params = EmaParams { period: Some(0) };
+        let params = EmaParams {
+            period: Some(0),
+            ..EmaParams::default()
+        };
         let input = EmaInput::from_slice(&data, params);
         let result = ema(&input);
         assert!(result.is_err(), "Result is not an error.");
@@ -195,7 +388,10 @@ mod tests {
     #[test]
     fn test_ema_period_exceeding_data_length() {
         let data = [10.0, 20.0, 30.0];
-        let params = EmaParams { period: Some(10) };
+        let params = EmaParams {
+            period: Some(10),
+            ..EmaParams::default()
+        };
         let input = EmaInput::from_slice(&data, params);
         let result = ema(&input);
         assert!(result.is_err(), "Result is not an error.");
@@ -204,7 +400,10 @@ mod tests {
     #[test]
     fn test_ema_very_small_data_set() {
         let data = [42.0];
-        let params = EmaParams { period: Some(9) };
+        let params = EmaParams {
+            period: Some(9),
+            ..EmaParams::default()
+        };
         let input = EmaInput::from_slice(&data, params);
         let result = ema(&input);
         assert!(result.is_err(), "Result is not an error.");
@@ -214,7 +413,10 @@ mod tests {
     fn test_ema_slice_data_reinput() {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
         let candles = read_candles_from_csv(file_path).unwrap();
-        let params_first = EmaParams { period: Some(9) };
+        let params_first = EmaParams {
+            period: Some(9),
+            ..EmaParams::default()
+        };
         let input_first = EmaInput::from_candles(&candles, "close", params_first);
         let result_first = ema(&input_first).unwrap();
         assert_eq!(
@@ -222,7 +424,10 @@ mod tests {
             candles.close.len(),
             "Result length mismatch."
         );
-        let params_second = EmaParams { period: Some(5) };
+        let params_second = EmaParams {
+            period: Some(5),
+            ..EmaParams::default()
+        };
         let input_second = EmaInput::from_slice(&result_first.values, params_second);
         let result_second = ema(&input_second).unwrap();
         assert_eq!(
@@ -245,7 +450,10 @@ mod tests {
     fn test_ema_nan_check() {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
         let candles = read_candles_from_csv(file_path).unwrap();
-        let params = EmaParams { period: Some(9) };
+        let params = EmaParams {
+            period: Some(9),
+            ..EmaParams::default()
+        };
         let input = EmaInput::from_candles(&candles, "close", params);
         let result = ema(&input).unwrap();
         assert_eq!(
@@ -259,4 +467,248 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_ema_source_data_variants_match() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let params = EmaParams {
+            period: Some(14),
+            ..EmaParams::default()
+        };
+
+        let candles_input = EmaInput::from_candles(&candles, "hl2", params.clone());
+        let slice_input = EmaInput::from_slice(&candles.hl2, params);
+        assert_eq!(candles_input.data.resolve(), candles.hl2.as_slice());
+        assert_eq!(slice_input.data.resolve(), candles.hl2.as_slice());
+
+        let from_candles = ema(&candles_input).expect("Failed ema from candles");
+        let from_slice = ema(&slice_input).expect("Failed ema from slice");
+        assert_eq!(from_candles.values.len(), candles.close.len());
+        for (i, (a, b)) in from_candles
+            .values
+            .iter()
+            .zip(&from_slice.values)
+            .enumerate()
+        {
+            assert!(
+                (a.is_nan() && b.is_nan()) || a == b,
+                "Ema variant mismatch at {}: {} vs {}",
+                i,
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn test_ema_iter_matches_batch() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = EmaInput::from_candles(
+            &candles,
+            "close",
+            EmaParams {
+                period: Some(9),
+                ..EmaParams::default()
+            },
+        );
+        let batch = ema(&input).expect("Failed to calculate EMA");
+
+        let streamed: Vec<f64> = ema_iter(candles.close.iter().copied(), 9)
+            .map(|v| v.expect("EMA stream should yield from the first valid value"))
+            .collect();
+        assert_eq!(streamed, batch.values);
+
+        let with_nans = [f64::NAN, f64::NAN, 1.0, 2.0];
+        let streamed: Vec<Option<f64>> = ema_iter(with_nans.iter().copied(), 3).collect();
+        assert_eq!(streamed, vec![None, None, Some(1.0), Some(1.5)]);
+    }
+
+    #[test]
+    fn test_ema_seed_modes_converge() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).unwrap();
+        let period = 9;
+        let first = ema(&EmaInput::from_candles(
+            &candles,
+            "close",
+            EmaParams {
+                period: Some(period),
+                seed: Some(EmaSeed::FirstValue),
+                ..EmaParams::default()
+            },
+        ))
+        .unwrap();
+        let sma_seeded = ema(&EmaInput::from_candles(
+            &candles,
+            "close",
+            EmaParams {
+                period: Some(period),
+                seed: Some(EmaSeed::Sma),
+                ..EmaParams::default()
+            },
+        ))
+        .unwrap();
+
+        let default_result = ema(&EmaInput::with_default_candles(&candles)).unwrap();
+        assert_eq!(default_result.values[..50], first.values[..50]);
+
+        assert!(sma_seeded.values[..period - 1].iter().all(|v| v.is_nan()));
+        assert!(first.values[..period - 1].iter().all(|v| !v.is_nan()));
+        let expected_seed: f64 = candles.close[..period].iter().sum::<f64>() / period as f64;
+        assert!((sma_seeded.values[period - 1] - expected_seed).abs() < 1e-9);
+        assert!((sma_seeded.values[period - 1] - first.values[period - 1]).abs() > 1e-6);
+
+        let last = candles.close.len() - 1;
+        assert!((sma_seeded.values[last] - first.values[last]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ema_first_valid_field() {
+        for (seed, warmup) in [(None, 0), (Some(EmaSeed::Sma), 5 - 1)] {
+            assert_first_valid_after_nan_prefix(warmup, |data| {
+                let output = ema(&EmaInput::from_slice(
+                    data,
+                    EmaParams {
+                        period: Some(5),
+                        seed,
+                        ..EmaParams::default()
+                    },
+                ))
+                .expect("Failed to calculate ema");
+                (output.values, output.first_valid)
+            });
+        }
+    }
+
+    #[test]
+    fn test_ema_sma_seed_skips_nan_in_window() {
+        let data = [f64::NAN, 1.0, 2.0, f64::NAN, 3.0, 4.0, 5.0];
+        let result = ema(&EmaInput::from_slice(
+            &data,
+            EmaParams {
+                period: Some(3),
+                seed: Some(EmaSeed::Sma),
+                ..EmaParams::default()
+            },
+        ))
+        .expect("Failed to calculate ema");
+        // Seed = (1 + 2 + 3) / 3 at index 4, then α = 0.5.
+        crate::assert_slice_close!(
+            result.values,
+            [f64::NAN, f64::NAN, f64::NAN, f64::NAN, 2.0, 3.0, 4.0],
+            1e-12
+        );
+        assert_eq!(result.first_valid, 4);
+
+        let sparse = [1.0, f64::NAN, f64::NAN, 2.0];
+        let result = ema(&EmaInput::from_slice(
+            &sparse,
+            EmaParams {
+                period: Some(3),
+                seed: Some(EmaSeed::Sma),
+                ..EmaParams::default()
+            },
+        ))
+        .expect("Failed to calculate ema");
+        assert!(result.values.iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_ema_last_matches_full() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        for seed in [EmaSeed::FirstValue, EmaSeed::Sma] {
+            let input = EmaInput::from_candles(
+                &candles,
+                "close",
+                EmaParams {
+                    seed: Some(seed),
+                    ..EmaParams::default()
+                },
+            );
+            let full = ema(&input).expect("Failed to calculate EMA");
+            let last = ema_last(&input).expect("Failed to calculate last EMA");
+            assert_eq!(last, full.values.last().copied());
+        }
+    }
+
+    #[test]
+    fn test_ema_adjust_matches_pandas() {
+        // pd.Series([1, 2, 3, 4, 5]).ewm(span=3, adjust=True).mean()
+        let data = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let expected = [
+            1.0,
+            1.6666666666666667,
+            2.4285714285714284,
+            3.2666666666666666,
+            4.161290322580645,
+        ];
+        let input = EmaInput::from_slice(
+            &data,
+            EmaParams {
+                period: Some(3),
+                adjust: Some(true),
+                ..EmaParams::default()
+            },
+        );
+        let result = ema(&input).expect("Failed to calculate adjusted EMA");
+        crate::assert_slice_close!(result.values, expected, 1e-12);
+        assert_eq!(ema_last(&input).unwrap(), Some(result.values[4]));
+
+        let leading_nan = [f64::NAN, 1.0, 2.0, 3.0];
+        let input = EmaInput::from_slice(
+            &leading_nan,
+            EmaParams {
+                period: Some(3),
+                adjust: Some(true),
+                ..EmaParams::default()
+            },
+        );
+        let result = ema(&input).expect("Failed to calculate adjusted EMA");
+        crate::assert_slice_close!(
+            result.values,
+            [f64::NAN, 1.0, 1.6666666666666667, 2.4285714285714284],
+            1e-12
+        );
+
+        // pd.Series([1, np.nan, 3, 4]).ewm(span=3, adjust=True).mean()
+        let interior_nan = [1.0, f64::NAN, 3.0, 4.0];
+        let input = EmaInput::from_slice(
+            &interior_nan,
+            EmaParams {
+                period: Some(3),
+                adjust: Some(true),
+                ..EmaParams::default()
+            },
+        );
+        assert!(input.get_adjust());
+        let result = ema(&input).expect("Failed to calculate adjusted EMA");
+        crate::assert_slice_close!(result.values, [1.0, 1.0, 2.6, 3.4615384615384617], 1e-12);
+    }
+
+    #[test]
+    fn test_ema_adjust_converges_to_recursive() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let recursive = ema(&EmaInput::with_default_candles(&candles))
+            .expect("Failed to calculate EMA")
+            .values;
+        let adjusted = ema(&EmaInput::from_candles(
+            &candles,
+            "close",
+            EmaParams {
+                adjust: Some(true),
+                ..EmaParams::default()
+            },
+        ))
+        .expect("Failed to calculate adjusted EMA")
+        .values;
+
+        assert_eq!(adjusted[0], candles.close[0]);
+        assert!((adjusted[1] - recursive[1]).abs() > 1e-6);
+        let last = candles.close.len() - 1;
+        assert!((adjusted[last] - recursive[last]).abs() < 1e-9);
+    }
 }
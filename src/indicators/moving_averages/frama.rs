@@ -22,6 +22,7 @@
 ///   with leading `NaN`s until the calculation window is filled.
 /// - **`Err(FramaError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -154,6 +155,7 @@ pub fn frama(input: &FramaInput) -> Result<FramaOutput, FramaError> {
     }
     let first_valid_idx = first_valid_idx.unwrap();
     if (data_len - first_valid_idx) < window {
+        not_enough_valid_data("frama", window, data_len - first_valid_idx);
         return Err(FramaError::NotEnoughValidData {
             needed: window,
             valid: data_len - first_valid_idx,
@@ -21,6 +21,7 @@
 /// - **`Ok(FwmaOutput)`** on success, containing a `Vec<f64>` matching the input length.
 /// - **`Err(FwmaError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::error::Error;
 
 #[derive(Debug, Clone)]
@@ -134,6 +135,7 @@ pub fn fwma(input: &FwmaInput) -> Result<FwmaOutput, FwmaError> {
     };
 
     if (len - first_valid_idx) < period {
+        not_enough_valid_data("fwma", period, len - first_valid_idx);
         return Err(FwmaError::NotEnoughValidData {
             needed: period,
             valid: len - first_valid_idx,
@@ -19,6 +19,8 @@
 /// - **`Ok(KamaOutput)`** on success, containing a `Vec<f64>` with length matching the input.
 /// - **`Err(KamaError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
+use std::collections::VecDeque;
 
 #[derive(Debug, Clone)]
 pub enum KamaData<'a> {
@@ -120,6 +122,7 @@ pub fn kama(input: &KamaInput) -> Result<KamaOutput, KamaError> {
 
     let lookback = period.saturating_sub(1);
     if lookback >= len {
+        not_enough_valid_data("kama", lookback + 1, len);
         return Err(KamaError::NotEnoughData {
             needed: lookback + 1,
             valid: len,
@@ -192,6 +195,73 @@ pub fn kama(input: &KamaInput) -> Result<KamaOutput, KamaError> {
     Ok(KamaOutput { values })
 }
 
+/// Incremental KAMA for streaming data.
+///
+/// Uses the same fixed smoothing constants as the batch `kama` (fast = 2, slow = 30).
+/// Warmup: the first `er_period` updates return `None`. Update number `er_period + 1`
+/// returns the input value itself as the seed (matching batch index `er_period`), and every
+/// later update returns the adapted average. `NaN` updates before the seed are skipped
+/// (they return `None` and do not count toward the warmup); after the seed a `NaN`
+/// propagates into every later value.
+#[derive(Debug, Clone)]
+pub struct KamaState {
+    pub er_period: usize,
+    pub fast_sc: f64,
+    pub slow_sc: f64,
+    pub prev_kama: Option<f64>,
+    pub window: VecDeque<f64>,
+    volatility: f64,
+}
+
+impl KamaState {
+    pub fn new(er_period: usize) -> Self {
+        Self {
+            er_period,
+            fast_sc: 2.0 / (2.0 + 1.0),
+            slow_sc: 2.0 / (30.0 + 1.0),
+            prev_kama: None,
+            window: VecDeque::with_capacity(er_period + 1),
+            volatility: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        if value.is_nan() && self.prev_kama.is_none() {
+            return None;
+        }
+        if let Some(&last) = self.window.back() {
+            self.volatility += (value - last).abs();
+        }
+        self.window.push_back(value);
+
+        if self.window.len() <= self.er_period {
+            return None;
+        }
+
+        let prev_kama = match self.prev_kama {
+            Some(prev) => prev,
+            None => {
+                self.prev_kama = Some(value);
+                return Some(value);
+            }
+        };
+
+        let oldest = self.window.pop_front().unwrap();
+        self.volatility -= (self.window[0] - oldest).abs();
+
+        let direction = (value - self.window[0]).abs();
+        let er = if self.volatility == 0.0 {
+            0.0
+        } else {
+            direction / self.volatility
+        };
+        let sc_root = er * (self.fast_sc - self.slow_sc) + self.slow_sc;
+        let kama = prev_kama + (value - prev_kama) * sc_root * sc_root;
+        self.prev_kama = Some(kama);
+        Some(kama)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,6 +308,69 @@ mod tests {
             );
         }
     }
+    #[test]
+    fn test_kama_state_matches_batch() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let period = 30;
+
+        let input = KamaInput::from_candles(
+            &candles,
+            "close",
+            KamaParams {
+                period: Some(period),
+            },
+        );
+        let batch = kama(&input).expect("Failed to calculate KAMA");
+
+        let mut state = KamaState::new(period);
+        for (i, &price) in candles.close.iter().enumerate() {
+            match state.update(price) {
+                None => {
+                    assert!(i < period, "Unexpected warmup at index {}", i);
+                    assert!(batch.values[i].is_nan());
+                }
+                Some(value) => {
+                    assert!(
+                        (value - batch.values[i]).abs() < 1e-9,
+                        "KAMA state mismatch at index {}: {} vs {}",
+                        i,
+                        value,
+                        batch.values[i]
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_kama_state_skips_nan_during_warmup() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let period = 10;
+        let clean = &candles.close[..100];
+
+        let mut gappy = vec![f64::NAN];
+        gappy.extend_from_slice(&clean[..3]);
+        gappy.push(f64::NAN);
+        gappy.extend_from_slice(&clean[3..]);
+
+        let mut expected_state = KamaState::new(period);
+        let expected: Vec<f64> = clean
+            .iter()
+            .filter_map(|&price| expected_state.update(price))
+            .collect();
+
+        let mut state = KamaState::new(period);
+        let actual: Vec<f64> = gappy
+            .iter()
+            .filter_map(|&price| state.update(price))
+            .collect();
+        assert_eq!(actual.len(), clean.len() - period);
+        crate::assert_slice_close!(actual, expected, 1e-12);
+        assert!(actual.iter().all(|v| v.is_finite()));
+    }
+
     #[test]
     fn test_kama_with_default_params() {
         let default_params = KamaParams::default();
@@ -192,12 +192,14 @@ pub fn ma<'a>(ma_type: &str, data: MaData<'a>, period: usize) -> Result<Vec<f64>
                     data: EmaData::Candles { candles, source },
                     params: EmaParams {
                         period: Some(period),
+                        ..EmaParams::default()
                     },
                 },
                 MaData::Slice(slice) => EmaInput {
                     data: EmaData::Slice(slice),
                     params: EmaParams {
                         period: Some(period),
+                        ..EmaParams::default()
                     },
                 },
             };
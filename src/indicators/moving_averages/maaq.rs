@@ -20,6 +20,7 @@
 /// - **`Ok(MaaqOutput)`** on success, containing a `Vec<f64>` of length matching the input.
 /// - **`Err(MaaqError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum MaaqData<'a> {
@@ -131,6 +132,7 @@ pub fn maaq(input: &MaaqInput) -> Result<MaaqOutput, MaaqError> {
     let len = data.len();
 
     if len < period {
+        not_enough_valid_data("maaq", period, len);
         return Err(MaaqError::NotEnoughData {
             needed: period,
             got: len,
@@ -16,12 +16,14 @@
 /// - **NotEnoughData**: mama: Fewer than 10 data points provided.
 /// - **InvalidFastLimit**: mama: The specified `fast_limit` is invalid (≤ 0.0, `NaN`, or infinite).
 /// - **InvalidSlowLimit**: mama: The specified `slow_limit` is invalid (≤ 0.0, `NaN`, or infinite).
+/// - **LimitsInverted**: mama: `slow_limit` is greater than `fast_limit`.
 ///
 /// ## Returns
 /// - **`Ok(MamaOutput)`** on success, containing two `Vec<f64>`:  
 ///   **`mama_values`** and **`fama_values`**, each of length matching the input.
 /// - **`Err(MamaError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use crate::utilities::math_functions::atan64;
 use std::f64::consts::PI;
 
@@ -122,6 +124,9 @@ pub enum MamaError {
 
     #[error("mama: Invalid slow limit: {slow_limit}")]
     InvalidSlowLimit { slow_limit: f64 },
+
+    #[error("mama: Slow limit {slow_limit} exceeds fast limit {fast_limit}")]
+    LimitsInverted { fast_limit: f64, slow_limit: f64 },
 }
 
 #[inline]
@@ -133,6 +138,7 @@ pub fn mama(input: &MamaInput) -> Result<MamaOutput, MamaError> {
 
     let len: usize = src.len();
     if len < 10 {
+        not_enough_valid_data("mama", 10, len);
         return Err(MamaError::NotEnoughData {
             needed: 10,
             found: len,
@@ -150,6 +156,13 @@ pub fn mama(input: &MamaInput) -> Result<MamaOutput, MamaError> {
         return Err(MamaError::InvalidSlowLimit { slow_limit });
     }
 
+    if slow_limit > fast_limit {
+        return Err(MamaError::LimitsInverted {
+            fast_limit,
+            slow_limit,
+        });
+    }
+
     let mut mama_values = vec![0.0; len];
     let mut fama_values = vec![0.0; len];
 
@@ -480,4 +493,31 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_mama_limits_inverted() {
+        let data: Vec<f64> = (0..20).map(|x| 100.0 + x as f64).collect();
+        let params = MamaParams {
+            fast_limit: Some(0.05),
+            slow_limit: Some(0.5),
+        };
+        let input = MamaInput::from_slice(&data, params);
+        match mama(&input) {
+            Err(MamaError::LimitsInverted {
+                fast_limit,
+                slow_limit,
+            }) => {
+                assert_eq!(fast_limit, 0.05);
+                assert_eq!(slow_limit, 0.5);
+            }
+            other => panic!("Expected LimitsInverted error, got {:?}", other.err()),
+        }
+
+        let equal_params = MamaParams {
+            fast_limit: Some(0.3),
+            slow_limit: Some(0.3),
+        };
+        let equal_input = MamaInput::from_slice(&data, equal_params);
+        assert!(mama(&equal_input).is_ok());
+    }
 }
@@ -17,6 +17,7 @@
 /// - **`Ok(NmaOutput)`** on success, containing a `Vec<f64>` with the same length as the input.
 /// - **`Err(NmaError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum NmaData<'a> {
@@ -108,6 +109,7 @@ pub fn nma(input: &NmaInput) -> Result<NmaOutput, NmaError> {
     }
 
     if len < period + 1 {
+        not_enough_valid_data("nma", period + 1, len);
         return Err(NmaError::NotEnoughData { len, period });
     }
 
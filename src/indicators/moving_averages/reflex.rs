@@ -19,6 +19,7 @@
 /// - **`Ok(ReflexOutput)`** on success, containing a `Vec<f64>` of length matching the input.
 /// - **`Err(ReflexError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::f64::consts::PI;
 
 #[derive(Debug, Clone)]
@@ -118,6 +119,7 @@ pub fn reflex(input: &ReflexInput) -> Result<ReflexOutput, ReflexError> {
         return Err(ReflexError::InvalidPeriod { period });
     }
     if period > len {
+        not_enough_valid_data("reflex", period, len);
         return Err(ReflexError::NotEnoughData {
             needed: period,
             found: len,
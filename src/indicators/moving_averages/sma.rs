@@ -18,20 +18,19 @@
 /// - **`Ok(SmaOutput)`** on success, containing a `Vec<f64>` matching the input length,
 ///   with leading `NaN`s until the moving average window is filled.
 /// - **`Err(SmaError)`** otherwise.
-use crate::utilities::data_loader::{source_type, Candles};
+use crate::indicators::utility_functions::sweep_periods;
+use crate::utilities::data_loader::{Candles, SourceData};
+use crate::utilities::diagnostics::not_enough_valid_data;
+use crate::utilities::series::first_finite_index;
+use std::collections::VecDeque;
 
-#[derive(Debug, Clone)]
-pub enum SmaData<'a> {
-    Candles {
-        candles: &'a Candles,
-        source: &'a str,
-    },
-    Slice(&'a [f64]),
-}
+pub type SmaData<'a> = SourceData<'a>;
 
 #[derive(Debug, Clone)]
 pub struct SmaOutput {
     pub values: Vec<f64>,
+    /// Index of the first finite value in `values` (`values.len()` if there is none).
+    pub first_valid: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -81,6 +80,29 @@ impl<'a> SmaInput<'a> {
             .period
             .unwrap_or_else(|| SmaParams::default().period.unwrap())
     }
+
+    /// Copies the resolved source into an [`OwnedSmaInput`] that no longer borrows the
+    /// candles or slice it was built from.
+    pub fn into_owned(self) -> OwnedSmaInput {
+        OwnedSmaInput {
+            data: self.data.resolve().to_vec(),
+            params: self.params,
+        }
+    }
+}
+
+/// A [`SmaInput`] holding its own copy of the source, so it can be stored past the
+/// lifetime of the candles or sent to another thread.
+#[derive(Debug, Clone)]
+pub struct OwnedSmaInput {
+    pub data: Vec<f64>,
+    pub params: SmaParams,
+}
+
+impl OwnedSmaInput {
+    pub fn compute(&self) -> Result<SmaOutput, SmaError> {
+        sma(&SmaInput::from_slice(&self.data, self.params.clone()))
+    }
 }
 use thiserror::Error;
 
@@ -97,11 +119,8 @@ pub enum SmaError {
 }
 
 #[inline]
-pub fn sma(input: &SmaInput) -> Result<SmaOutput, SmaError> {
-    let data: &[f64] = match &input.data {
-        SmaData::Candles { candles, source } => source_type(candles, source),
-        SmaData::Slice(slice) => slice,
-    };
+fn validate_sma<'a>(input: &'a SmaInput) -> Result<(&'a [f64], usize, usize), SmaError> {
+    let data: &[f64] = input.data.resolve();
 
     if data.is_empty() {
         return Err(SmaError::EmptyData);
@@ -121,33 +140,152 @@ pub fn sma(input: &SmaInput) -> Result<SmaOutput, SmaError> {
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("sma", period, data.len() - first_valid_idx);
         return Err(SmaError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
         });
     }
 
-    let mut sma_values = vec![f64::NAN; data.len()];
+    Ok((data, period, first_valid_idx))
+}
+
+/// Runs the SMA recurrence, passing each `(index, value)` from the first full window on
+/// to `emit`, so [`sma`] and [`sma_last`] share the exact same arithmetic.
+#[inline(always)]
+fn sma_pass(data: &[f64], period: usize, first_valid_idx: usize, mut emit: impl FnMut(usize, f64)) {
     let mut sum = 0.0;
     for &value in data[first_valid_idx..(first_valid_idx + period)].iter() {
         sum += value;
     }
 
     let inv_period = 1.0 / (period as f64);
-    sma_values[first_valid_idx + period - 1] = sum * inv_period;
+    emit(first_valid_idx + period - 1, sum * inv_period);
 
     for i in (first_valid_idx + period)..data.len() {
         sum += data[i] - data[i - period];
-        sma_values[i] = sum * inv_period;
+        emit(i, sum * inv_period);
     }
+}
+
+#[inline]
+pub fn sma(input: &SmaInput) -> Result<SmaOutput, SmaError> {
+    let (data, period, first_valid_idx) = validate_sma(input)?;
 
-    Ok(SmaOutput { values: sma_values })
+    let mut sma_values = vec![f64::NAN; data.len()];
+    sma_pass(data, period, first_valid_idx, |i, value| {
+        sma_values[i] = value
+    });
+
+    Ok(SmaOutput {
+        first_valid: first_finite_index(&sma_values),
+        values: sma_values,
+    })
+}
+
+/// The last value of [`sma`] without allocating the output series, e.g. for a live
+/// dashboard that only shows the latest reading. Validation and errors are the same as
+/// [`sma`]; returns `None` when the last value is `NaN`.
+#[inline]
+pub fn sma_last(input: &SmaInput) -> Result<Option<f64>, SmaError> {
+    let (data, period, first_valid_idx) = validate_sma(input)?;
+
+    let mut last = f64::NAN;
+    sma_pass(data, period, first_valid_idx, |_, value| last = value);
+    Ok(Some(last).filter(|v| !v.is_nan()))
+}
+
+/// Computes the SMA value at a single index from the `period` values ending at `idx`,
+/// without computing the whole series. Returns `None` when `period` is zero, `idx` is out
+/// of bounds, the window would start before the first bar, or the window contains `NaN`.
+#[inline]
+pub fn sma_at(data: &[f64], period: usize, idx: usize) -> Option<f64> {
+    if period == 0 || idx >= data.len() || idx + 1 < period {
+        return None;
+    }
+
+    let window = &data[idx + 1 - period..=idx];
+    if window.iter().any(|x| x.is_nan()) {
+        return None;
+    }
+
+    Some(window.iter().sum::<f64>() / period as f64)
+}
+
+/// Computes `sma` for every period in `periods` over the same candle source.
+/// Periods that fail validation are omitted from the result.
+#[inline]
+pub fn sweep_sma(
+    candles: &Candles,
+    source: &str,
+    periods: impl IntoIterator<Item = usize>,
+) -> Vec<(usize, Vec<f64>)> {
+    sweep_periods(periods, |period| {
+        let input = SmaInput::from_candles(
+            candles,
+            source,
+            SmaParams {
+                period: Some(period),
+            },
+        );
+        sma(&input).map(|output| output.values)
+    })
+}
+
+/// Incremental SMA for streaming data.
+///
+/// Leading `NaN`s are skipped like the batch `sma` skips them. After that the first
+/// `period - 1` updates return `None` and every later update returns the mean of the last
+/// `period` values.
+#[derive(Debug, Clone)]
+pub struct SmaState {
+    pub period: usize,
+    pub window: VecDeque<f64>,
+    sum: f64,
+}
+
+impl SmaState {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            sum: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        if self.period == 0 || (self.window.is_empty() && value.is_nan()) {
+            return None;
+        }
+
+        self.sum += value;
+        self.window.push_back(value);
+        if self.window.len() > self.period {
+            self.sum -= self.window.pop_front().unwrap();
+        }
+
+        if self.window.len() < self.period {
+            return None;
+        }
+        Some(self.sum / self.period as f64)
+    }
+}
+
+/// Streams `iter` through an [`SmaState`], yielding one item per input.
+#[inline]
+pub fn sma_iter<I: Iterator<Item = f64>>(
+    iter: I,
+    period: usize,
+) -> impl Iterator<Item = Option<f64>> {
+    let mut state = SmaState::new(period);
+    iter.map(move |value| state.update(value))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::utilities::data_loader::read_candles_from_csv;
+    use crate::utilities::series::assert_first_valid_after_nan_prefix;
 
     #[test]
     fn test_sma_partial_params() {
@@ -312,6 +450,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sma_at_matches_full_series() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let data = &candles.close;
+
+        let input = SmaInput::from_candles(&candles, "close", SmaParams { period: Some(14) });
+        let full = sma(&input).expect("Failed to calculate SMA");
+
+        for &i in &[13, 14, 100, 5000, data.len() - 1] {
+            let point = sma_at(data, 14, i).expect("Expected a value");
+            assert!(
+                (point - full.values[i]).abs() < 1e-6,
+                "sma_at mismatch at index {}: {} vs {}",
+                i,
+                point,
+                full.values[i]
+            );
+        }
+
+        assert!(sma_at(data, 14, 12).is_none());
+        assert!(sma_at(data, 14, data.len()).is_none());
+        assert!(sma_at(data, 0, 20).is_none());
+    }
+
+    #[test]
+    fn test_sweep_sma() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let results = sweep_sma(&candles, "close", 5..=10);
+        assert_eq!(results.len(), 6);
+
+        for (period, values) in &results {
+            let input = SmaInput::from_candles(
+                &candles,
+                "close",
+                SmaParams {
+                    period: Some(*period),
+                },
+            );
+            let expected = sma(&input).expect("Failed to calculate SMA");
+            assert_eq!(values.len(), candles.close.len());
+            assert_eq!(
+                values.last().unwrap().to_bits(),
+                expected.values.last().unwrap().to_bits()
+            );
+        }
+    }
+
     #[test]
     fn test_sma_accuracy_nan_check() {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
@@ -341,4 +529,112 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_sma_source_data_variants_match() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let params = SmaParams { period: Some(14) };
+
+        let candles_input = SmaInput::from_candles(&candles, "hl2", params.clone());
+        let slice_input = SmaInput::from_slice(&candles.hl2, params);
+        assert_eq!(candles_input.data.resolve(), candles.hl2.as_slice());
+        assert_eq!(slice_input.data.resolve(), candles.hl2.as_slice());
+
+        let from_candles = sma(&candles_input).expect("Failed sma from candles");
+        let from_slice = sma(&slice_input).expect("Failed sma from slice");
+        assert_eq!(from_candles.values.len(), candles.close.len());
+        for (i, (a, b)) in from_candles
+            .values
+            .iter()
+            .zip(&from_slice.values)
+            .enumerate()
+        {
+            assert!(
+                (a.is_nan() && b.is_nan()) || a == b,
+                "Sma variant mismatch at {}: {} vs {}",
+                i,
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn test_sma_iter_matches_batch() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = SmaInput::from_candles(&candles, "close", SmaParams { period: Some(14) });
+        let batch = sma(&input).expect("Failed to calculate SMA");
+
+        let streamed: Vec<Option<f64>> = sma_iter(candles.close.iter().copied(), 14).collect();
+        assert_eq!(streamed.len(), batch.values.len());
+        assert!(streamed[..13].iter().all(|v| v.is_none()));
+        for (i, value) in streamed.iter().enumerate().skip(13) {
+            let value = value.expect("Expected a value after warmup");
+            assert!(
+                (value - batch.values[i]).abs() < 1e-6,
+                "SMA stream mismatch at {}: {} vs {}",
+                i,
+                value,
+                batch.values[i]
+            );
+        }
+    }
+
+    #[test]
+    fn test_sma_first_valid_field() {
+        assert_first_valid_after_nan_prefix(5 - 1, |data| {
+            let output = sma(&SmaInput::from_slice(data, SmaParams { period: Some(5) }))
+                .expect("Failed to calculate sma");
+            (output.values, output.first_valid)
+        });
+    }
+
+    #[test]
+    fn test_sma_owned_input_in_thread() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let params = SmaParams { period: Some(20) };
+        let expected = sma(&SmaInput::from_candles(&candles, "hl2", params.clone()))
+            .expect("Failed to calculate SMA");
+
+        let owned = SmaInput::from_candles(&candles, "hl2", params).into_owned();
+        drop(candles);
+        let result = std::thread::spawn(move || owned.compute())
+            .join()
+            .expect("SMA thread panicked")
+            .expect("Failed to calculate owned SMA");
+
+        crate::assert_slice_close!(result.values, expected.values, 0.0);
+        assert_eq!(result.first_valid, expected.first_valid);
+    }
+
+    #[test]
+    fn test_sma_last_matches_full() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        for period in [1, 9, 50] {
+            let input = SmaInput::from_candles(
+                &candles,
+                "close",
+                SmaParams {
+                    period: Some(period),
+                },
+            );
+            let full = sma(&input).expect("Failed to calculate SMA");
+            let last = sma_last(&input).expect("Failed to calculate last SMA");
+            assert_eq!(last, full.values.last().copied());
+        }
+
+        let data = [1.0, 2.0, 3.0, f64::NAN];
+        let input = SmaInput::from_slice(&data, SmaParams { period: Some(2) });
+        assert_eq!(sma_last(&input).unwrap(), None);
+        let input = SmaInput::from_slice(&data, SmaParams { period: Some(5) });
+        assert!(matches!(
+            sma_last(&input),
+            Err(SmaError::InvalidPeriod { .. })
+        ));
+    }
 }
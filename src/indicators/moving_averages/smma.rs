@@ -19,6 +19,7 @@
 ///   the input. Values prior to `first_valid_idx + period - 1` remain `NaN`.
 /// - **`Err(SmmaError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum SmmaData<'a> {
@@ -122,6 +123,7 @@ pub fn smma(input: &SmmaInput) -> Result<SmmaOutput, SmmaError> {
     }
 
     if (len - first_valid_idx) < period {
+        not_enough_valid_data("smma", period, len - first_valid_idx);
         return Err(SmmaError::NotEnoughValidData {
             needed: period,
             valid: len - first_valid_idx,
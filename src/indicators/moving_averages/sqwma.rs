@@ -18,6 +18,7 @@
 /// - **`Ok(SqwmaOutput)`** on success, containing a `Vec<f64>` of length matching the input.
 /// - **`Err(SqwmaError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum SqwmaData<'a> {
@@ -117,6 +118,7 @@ pub fn sqwma(input: &SqwmaInput) -> Result<SqwmaOutput, SqwmaError> {
         return Err(SqwmaError::InvalidPeriod { period });
     }
     if period + 1 > n {
+        not_enough_valid_data("sqwma", period + 1, n);
         return Err(SqwmaError::NotEnoughData {
             required: period + 1,
             found: n,
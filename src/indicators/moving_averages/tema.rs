@@ -17,6 +17,7 @@
 /// - **`Ok(TemaOutput)`** on success, containing a `Vec<f64>` of length matching the input.
 /// - **`Err(TemaError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum TemaData<'a> {
@@ -115,6 +116,7 @@ pub fn tema(input: &TemaInput) -> Result<TemaOutput, TemaError> {
     }
 
     if period > n {
+        not_enough_valid_data("tema", period, n);
         return Err(TemaError::NotEnoughDataPoints {
             period,
             data_len: n,
@@ -23,6 +23,7 @@
 ///   with `NaN` values where insufficient data exists for the calculation.
 /// - **`Err(TilsonError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::series::first_finite_index;
 
 #[derive(Debug, Clone)]
 pub enum TilsonData<'a> {
@@ -36,6 +37,8 @@ pub enum TilsonData<'a> {
 #[derive(Debug, Clone)]
 pub struct TilsonOutput {
     pub values: Vec<f64>,
+    /// Index of the first finite value in `values` (`values.len()` if there is none).
+    pub first_valid: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -261,13 +264,17 @@ pub fn tilson(input: &TilsonInput) -> Result<TilsonOutput, TilsonError> {
         out_idx += 1;
     }
 
-    Ok(TilsonOutput { values: out_values })
+    Ok(TilsonOutput {
+        first_valid: first_finite_index(&out_values),
+        values: out_values,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::utilities::data_loader::read_candles_from_csv;
+    use crate::utilities::series::assert_first_valid_after_nan_prefix;
 
     #[test]
     fn test_tilson_partial_params() {
@@ -438,4 +445,19 @@ mod tests {
             assert!(val.is_finite(), "NaN found at index {}", idx);
         }
     }
+
+    #[test]
+    fn test_tilson_first_valid_field() {
+        assert_first_valid_after_nan_prefix(6 * (5 - 1), |data| {
+            let output = tilson(&TilsonInput::from_slice(
+                data,
+                TilsonParams {
+                    period: Some(5),
+                    volume_factor: None,
+                },
+            ))
+            .expect("Failed to calculate tilson");
+            (output.values, output.first_valid)
+        });
+    }
 }
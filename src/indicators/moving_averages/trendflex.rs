@@ -21,6 +21,7 @@
 /// - **`Ok(TrendFlexOutput)`** on success, containing a `Vec<f64>` with the same length as the input.
 /// - **`Err(TrendFlexError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::series::first_finite_index;
 
 #[derive(Debug, Clone)]
 pub enum TrendFlexData<'a> {
@@ -34,6 +35,8 @@ pub enum TrendFlexData<'a> {
 #[derive(Debug, Clone)]
 pub struct TrendFlexOutput {
     pub values: Vec<f64>,
+    /// Index of the first finite value in `values` (`values.len()` if there is none).
+    pub first_valid: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -189,12 +192,16 @@ pub fn trendflex(input: &TrendFlexInput) -> Result<TrendFlexOutput, TrendFlexErr
             rolling_sum += ssf[i] - ssf[i - trendflex_period];
         }
 
-        return Ok(TrendFlexOutput { values: tf_values });
+        return Ok(TrendFlexOutput {
+            first_valid: first_finite_index(&tf_values),
+            values: tf_values,
+        });
     } else {
         let m = len - first_valid_idx;
         if m < trendflex_period {
             return Ok(TrendFlexOutput {
                 values: vec![f64::NAN; len],
+                first_valid: len,
             });
         }
         if m < ss_period {
@@ -259,6 +266,7 @@ pub fn trendflex(input: &TrendFlexInput) -> Result<TrendFlexOutput, TrendFlexErr
         }
 
         return Ok(TrendFlexOutput {
+            first_valid: first_finite_index(&final_values),
             values: final_values,
         });
     }
@@ -268,6 +276,7 @@ pub fn trendflex(input: &TrendFlexInput) -> Result<TrendFlexOutput, TrendFlexErr
 mod tests {
     use super::*;
     use crate::utilities::data_loader::read_candles_from_csv;
+    use crate::utilities::series::assert_first_valid_after_nan_prefix;
 
     #[test]
     fn test_trendflex_partial_params() {
@@ -410,4 +419,16 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_trendflex_first_valid_field() {
+        assert_first_valid_after_nan_prefix(20, |data| {
+            let output = trendflex(&TrendFlexInput::from_slice(
+                data,
+                TrendFlexParams { period: Some(20) },
+            ))
+            .expect("Failed to calculate trendflex");
+            (output.values, output.first_valid)
+        });
+    }
 }
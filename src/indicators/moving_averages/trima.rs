@@ -18,6 +18,8 @@
 /// - **`Ok(TrimaOutput)`** on success, containing a `Vec<f64>` of length matching the input.
 /// - **`Err(TrimaError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
+use crate::utilities::series::first_finite_index;
 
 #[derive(Debug, Clone)]
 pub enum TrimaData<'a> {
@@ -31,6 +33,8 @@ pub enum TrimaData<'a> {
 #[derive(Debug, Clone)]
 pub struct TrimaOutput {
     pub values: Vec<f64>,
+    /// Index of the first finite value in `values` (`values.len()` if there is none).
+    pub first_valid: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -117,6 +121,7 @@ pub fn trima(input: &TrimaInput) -> Result<TrimaOutput, TrimaError> {
     let period: usize = input.get_period();
 
     if period > n {
+        not_enough_valid_data("trima", period, n);
         return Err(TrimaError::NotEnoughData {
             needed: period,
             found: n,
@@ -135,6 +140,7 @@ pub fn trima(input: &TrimaInput) -> Result<TrimaOutput, TrimaError> {
     };
 
     if (n - first_valid_idx) < period {
+        not_enough_valid_data("trima", period, n - first_valid_idx);
         return Err(TrimaError::NotEnoughData {
             needed: period,
             found: n - first_valid_idx,
@@ -217,7 +223,10 @@ pub fn trima(input: &TrimaInput) -> Result<TrimaOutput, TrimaError> {
         tsi2 += 1;
     }
 
-    Ok(TrimaOutput { values: out })
+    Ok(TrimaOutput {
+        first_valid: first_finite_index(&out),
+        values: out,
+    })
 }
 
 use crate::indicators::sma::{sma, SmaData, SmaInput, SmaParams};
@@ -236,6 +245,7 @@ pub fn trima_from_slice(input: &TrimaInput) -> Result<TrimaOutput, TrimaError> {
         return Err(TrimaError::NoData);
     }
     if period > n {
+        not_enough_valid_data("trima", period, n);
         return Err(TrimaError::NotEnoughData {
             needed: period,
             found: n,
@@ -258,9 +268,9 @@ pub fn trima_from_slice(input: &TrimaInput) -> Result<TrimaOutput, TrimaError> {
     };
     let pass2 = sma(&input2)?;
 
-    let pass2_values = pass2.values;
     Ok(TrimaOutput {
-        values: pass2_values,
+        first_valid: pass2.first_valid,
+        values: pass2.values,
     })
 }
 
@@ -268,6 +278,7 @@ pub fn trima_from_slice(input: &TrimaInput) -> Result<TrimaOutput, TrimaError> {
 mod tests {
     use super::*;
     use crate::utilities::data_loader::read_candles_from_csv;
+    use crate::utilities::series::assert_first_valid_after_nan_prefix;
 
     #[test]
     fn test_trima_partial_params() {
@@ -413,4 +424,16 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_trima_first_valid_field() {
+        assert_first_valid_after_nan_prefix(10 - 1, |data| {
+            let output = trima(&TrimaInput::from_slice(
+                data,
+                TrimaParams { period: Some(10) },
+            ))
+            .expect("Failed to calculate trima");
+            (output.values, output.first_valid)
+        });
+    }
 }
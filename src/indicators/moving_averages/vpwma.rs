@@ -32,6 +32,7 @@
 /// println!("VPWMA output: {:?}", result.values);
 /// ```
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum VpwmaData<'a> {
@@ -139,6 +140,7 @@ pub fn vpwma(input: &VpwmaInput) -> Result<VpwmaOutput, VpwmaError> {
     let len = data.len();
 
     if len < period + 1 {
+        not_enough_valid_data("vpwma", period + 1, len);
         return Err(VpwmaError::NotEnoughData {
             data_len: len,
             period_plus_1: period + 1,
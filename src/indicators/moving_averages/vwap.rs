@@ -6,7 +6,9 @@
 /// aggregation periods in minutes, hours, days, or months. Internally, VWAP
 /// groups data points by the chosen bucket size, tracks cumulative volume
 /// (`volume_sum`) and cumulative volume*price (`vol_price_sum`), and produces
-/// a cumulative average for each group.
+/// a cumulative average for each group. Alongside VWAP it also tracks the
+/// volume-weighted variance of price around the running VWAP within the same
+/// bucket, which yields ±1 and ±2 standard-deviation bands.
 ///
 /// ## Parameters
 /// - **anchor**: Defines the grouping period (e.g., `1m`, `4h`, `1d`, `1M`).
@@ -21,9 +23,9 @@
 /// - **MonthConversionError**: vwap: Error converting timestamp to month-based anchor.
 ///
 /// ## Returns
-/// - **`Ok(VwapOutput)`** on success, containing a `Vec<f64>` of length matching
-///   the input, where each value represents the cumulative VWAP within that
-///   anchor bucket.
+/// - **`Ok(VwapOutput)`** on success, containing `values` (the cumulative VWAP
+///   within each anchor bucket) and the `upper1`/`lower1`/`upper2`/`lower2` bands,
+///   all matching the input length.
 /// - **`Err(VwapError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
 use chrono::{Datelike, NaiveDateTime, Utc};
@@ -97,6 +99,10 @@ impl<'a> VwapInput<'a> {
 #[derive(Debug, Clone)]
 pub struct VwapOutput {
     pub values: Vec<f64>,
+    pub upper1: Vec<f64>,
+    pub lower1: Vec<f64>,
+    pub upper2: Vec<f64>,
+    pub lower2: Vec<f64>,
 }
 
 use thiserror::Error;
@@ -169,9 +175,14 @@ pub fn vwap(input: &VwapInput) -> Result<VwapOutput, VwapError> {
         .map_err(|e| VwapError::ParseAnchorError { msg: e.to_string() })?;
 
     let mut vwap_values = vec![f64::NAN; n];
+    let mut upper1 = vec![f64::NAN; n];
+    let mut lower1 = vec![f64::NAN; n];
+    let mut upper2 = vec![f64::NAN; n];
+    let mut lower2 = vec![f64::NAN; n];
     let mut current_group_id = -1_i64;
     let mut volume_sum = 0.0;
     let mut vol_price_sum = 0.0;
+    let mut vol_price_sq_sum = 0.0;
 
     for i in 0..n {
         let ts_ms = timestamps[i];
@@ -200,20 +211,31 @@ pub fn vwap(input: &VwapInput) -> Result<VwapOutput, VwapError> {
             current_group_id = group_id;
             volume_sum = 0.0;
             vol_price_sum = 0.0;
+            vol_price_sq_sum = 0.0;
         }
 
         volume_sum += volume;
         vol_price_sum += volume * price;
-
-        vwap_values[i] = if volume_sum > 0.0 {
-            vol_price_sum / volume_sum
-        } else {
-            f64::NAN
-        };
+        vol_price_sq_sum += volume * price * price;
+
+        if volume_sum > 0.0 {
+            let mean = vol_price_sum / volume_sum;
+            let variance = (vol_price_sq_sum / volume_sum - mean * mean).max(0.0);
+            let sd = variance.sqrt();
+            vwap_values[i] = mean;
+            upper1[i] = mean + sd;
+            lower1[i] = mean - sd;
+            upper2[i] = mean + 2.0 * sd;
+            lower2[i] = mean - 2.0 * sd;
+        }
     }
 
     Ok(VwapOutput {
         values: vwap_values,
+        upper1,
+        lower1,
+        upper2,
+        lower2,
     })
 }
 
@@ -388,6 +410,77 @@ mod tests {
         assert_eq!(second_result.values.len(), first_result.values.len());
     }
 
+    #[test]
+    fn test_vwap_bands() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let params = VwapParams {
+            anchor: Some("1M".to_string()),
+        };
+        let input = VwapInput::from_candles(&candles, "hlc3", params);
+        let result = vwap(&input).expect("Failed to calculate VWAP");
+
+        let mut widths = Vec::new();
+        for i in 0..result.values.len() {
+            let v = result.values[i];
+            if v.is_nan() {
+                continue;
+            }
+            assert!(result.lower2[i] <= result.lower1[i], "lower bands at {}", i);
+            assert!(
+                result.lower1[i] <= v && v <= result.upper1[i],
+                "1sd at {}",
+                i
+            );
+            assert!(result.upper1[i] <= result.upper2[i], "upper bands at {}", i);
+            let width1 = result.upper1[i] - v;
+            let width2 = result.upper2[i] - v;
+            assert!((width2 - 2.0 * width1).abs() < 1e-6, "2sd width at {}", i);
+            assert!(
+                (v - result.lower1[i] - width1).abs() < 1e-6,
+                "symmetry at {}",
+                i
+            );
+            widths.push(width1);
+        }
+
+        assert!(widths.iter().any(|&w| w > 0.0));
+    }
+
+    #[test]
+    fn test_vwap_bands_widen_with_volatility() {
+        let day_ms = 86_400_000;
+        let bar_ms = 4 * 3_600_000;
+        let calm = [100.0, 101.0, 99.0, 101.0, 99.0, 100.0];
+        let wild = [100.0, 110.0, 90.0, 115.0, 85.0, 100.0];
+        let closes: Vec<f64> = calm.iter().chain(wild.iter()).cloned().collect();
+        let n = closes.len();
+        let timestamp = (0..n as i64).map(|i| day_ms + i * bar_ms).collect();
+        let candles = Candles::new(
+            timestamp,
+            closes.clone(),
+            closes.clone(),
+            closes.clone(),
+            closes,
+            vec![1.0; n],
+        );
+
+        let input = VwapInput::from_candles(&candles, "close", VwapParams::default());
+        let result = vwap(&input).expect("Failed to calculate VWAP");
+
+        let calm_width = result.upper1[5] - result.lower1[5];
+        let wild_width = result.upper1[11] - result.lower1[11];
+        assert!((result.values[5] - 100.0).abs() < 1e-9);
+        assert!((result.values[11] - 100.0).abs() < 1e-9);
+        assert!(
+            wild_width > 5.0 * calm_width,
+            "expected wider bands on the volatile day: calm={}, wild={}",
+            calm_width,
+            wild_width
+        );
+        assert_eq!(result.upper1[6], result.values[6]);
+    }
+
     #[test]
     fn test_vwap_accuracy_nan_check() {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
@@ -21,6 +21,7 @@
 /// - **`Ok(VwmaOutput)`** on success, containing a `Vec<f64>` of length matching the input.
 /// - **`Err(VwmaError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum VwmaData<'a> {
@@ -159,6 +160,7 @@ pub fn vwma(input: &VwmaInput) -> Result<VwmaOutput, VwmaError> {
     };
 
     if (len - first_valid_idx) < period {
+        not_enough_valid_data("vwma", period, len - first_valid_idx);
         return Err(VwmaError::NotEnoughData {
             needed: period,
             found: len - first_valid_idx,
@@ -17,6 +17,7 @@
 /// - **`Ok(WildersOutput)`** on success, containing a `Vec<f64>` of length matching the input.
 /// - **`Err(WildersError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum WildersData<'a> {
@@ -124,6 +125,7 @@ pub fn wilders(input: &WildersInput) -> Result<WildersOutput, WildersError> {
     };
 
     if (len - first_valid_idx) < period {
+        not_enough_valid_data("wilders", period, len - first_valid_idx);
         return Err(WildersError::NotEnoughData {
             needed: period,
             found: len - first_valid_idx,
@@ -17,16 +17,10 @@
 /// ## Returns
 /// - **`Ok(WmaOutput)`** on success, containing a `Vec<f64>` of length matching the input.
 /// - **`Err(WmaError)`** otherwise.
-use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::data_loader::{Candles, SourceData};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
-#[derive(Debug, Clone)]
-pub enum WmaData<'a> {
-    Candles {
-        candles: &'a Candles,
-        source: &'a str,
-    },
-    Slice(&'a [f64]),
-}
+pub type WmaData<'a> = SourceData<'a>;
 
 #[derive(Debug, Clone)]
 pub struct WmaOutput {
@@ -80,6 +74,29 @@ impl<'a> WmaInput<'a> {
             .period
             .unwrap_or_else(|| WmaParams::default().period.unwrap())
     }
+
+    /// Copies the resolved source into an [`OwnedWmaInput`] that no longer borrows the
+    /// candles or slice it was built from.
+    pub fn into_owned(self) -> OwnedWmaInput {
+        OwnedWmaInput {
+            data: self.data.resolve().to_vec(),
+            params: self.params,
+        }
+    }
+}
+
+/// A [`WmaInput`] holding its own copy of the source, so it can be stored past the
+/// lifetime of the candles or sent to another thread.
+#[derive(Debug, Clone)]
+pub struct OwnedWmaInput {
+    pub data: Vec<f64>,
+    pub params: WmaParams,
+}
+
+impl OwnedWmaInput {
+    pub fn compute(&self) -> Result<WmaOutput, WmaError> {
+        wma(&WmaInput::from_slice(&self.data, self.params.clone()))
+    }
 }
 
 use thiserror::Error;
@@ -96,14 +113,13 @@ pub enum WmaError {
     AllValuesNaN,
     #[error("Insufficient data for WMA calculation: needed {needed} found {found}.")]
     NotEnoughData { needed: usize, found: usize },
+    #[error("Sum of weights is zero, cannot normalize WMA.")]
+    ZeroWeightSum,
 }
 
 #[inline]
 pub fn wma(input: &WmaInput) -> Result<WmaOutput, WmaError> {
-    let data: &[f64] = match &input.data {
-        WmaData::Candles { candles, source } => source_type(candles, source),
-        WmaData::Slice(slice) => slice,
-    };
+    let data: &[f64] = input.data.resolve();
 
     if data.is_empty() {
         return Err(WmaError::EmptyData);
@@ -128,6 +144,7 @@ pub fn wma(input: &WmaInput) -> Result<WmaOutput, WmaError> {
     }
 
     if (len - first_valid_idx) < period {
+        not_enough_valid_data("wma", period, len - first_valid_idx);
         return Err(WmaError::NotEnoughData {
             needed: period,
             found: len - first_valid_idx,
@@ -173,6 +190,63 @@ pub fn wma(input: &WmaInput) -> Result<WmaOutput, WmaError> {
 
     Ok(WmaOutput { values })
 }
+
+/// Moving average with caller-supplied weights.
+///
+/// `weights` are ordered oldest to newest, so `weights[weights.len() - 1]` is applied to the
+/// most recent value in each window. Each window is normalized by the sum of the weights.
+/// Leading values are `NaN` until the first full window.
+#[inline]
+pub fn weighted_ma(data: &[f64], weights: &[f64]) -> Result<Vec<f64>, WmaError> {
+    if data.is_empty() {
+        return Err(WmaError::EmptyData);
+    }
+
+    let period = weights.len();
+    if period == 0 {
+        return Err(WmaError::InvalidPeriod { period });
+    }
+
+    let len = data.len();
+    if period > len {
+        return Err(WmaError::PeriodExceedsDataLen {
+            period,
+            data_len: len,
+        });
+    }
+
+    let first_valid_idx = match data.iter().position(|&x| !x.is_nan()) {
+        Some(idx) => idx,
+        None => return Err(WmaError::AllValuesNaN),
+    };
+
+    if (len - first_valid_idx) < period {
+        not_enough_valid_data("weighted_ma", weights.len(), len - first_valid_idx);
+        return Err(WmaError::NotEnoughData {
+            needed: period,
+            found: len - first_valid_idx,
+        });
+    }
+
+    let weight_sum: f64 = weights.iter().sum();
+    if weight_sum == 0.0 {
+        return Err(WmaError::ZeroWeightSum);
+    }
+    let inv_weight_sum = 1.0 / weight_sum;
+
+    let mut values = vec![f64::NAN; len];
+    for i in (first_valid_idx + period - 1)..len {
+        let window = &data[i + 1 - period..=i];
+        let mut acc = 0.0;
+        for (&w, &v) in weights.iter().zip(window.iter()) {
+            acc += w * v;
+        }
+        values[i] = acc * inv_weight_sum;
+    }
+
+    Ok(values)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -308,6 +382,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_weighted_ma_matches_sma_and_wma() {
+        use crate::indicators::sma::{sma, SmaInput, SmaParams};
+
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let period = 14;
+
+        let equal_weights = vec![1.0; period];
+        let equal_result =
+            weighted_ma(&candles.close, &equal_weights).expect("Failed weighted_ma equal");
+        let sma_input = SmaInput::from_candles(
+            &candles,
+            "close",
+            SmaParams {
+                period: Some(period),
+            },
+        );
+        let sma_result = sma(&sma_input).expect("Failed to calculate SMA");
+        assert_eq!(equal_result.len(), sma_result.values.len());
+        for (i, (&a, &b)) in equal_result
+            .iter()
+            .zip(sma_result.values.iter())
+            .enumerate()
+        {
+            if b.is_nan() {
+                assert!(a.is_nan(), "Expected NaN at index {}", i);
+            } else {
+                assert!((a - b).abs() < 1e-6, "SMA mismatch at index {}", i);
+            }
+        }
+
+        let linear_weights: Vec<f64> = (1..=period).map(|w| w as f64).collect();
+        let linear_result =
+            weighted_ma(&candles.close, &linear_weights).expect("Failed weighted_ma linear");
+        let wma_input = WmaInput::from_candles(
+            &candles,
+            "close",
+            WmaParams {
+                period: Some(period),
+            },
+        );
+        let wma_result = wma(&wma_input).expect("Failed to calculate WMA");
+        for (i, (&a, &b)) in linear_result
+            .iter()
+            .zip(wma_result.values.iter())
+            .enumerate()
+        {
+            if b.is_nan() {
+                assert!(a.is_nan(), "Expected NaN at index {}", i);
+            } else {
+                assert!((a - b).abs() < 1e-6, "WMA mismatch at index {}", i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_weighted_ma_invalid_weights() {
+        let data = [10.0, 20.0, 30.0];
+        assert!(weighted_ma(&data, &[]).is_err());
+        assert!(weighted_ma(&data, &[1.0, -1.0]).is_err());
+        assert!(weighted_ma(&data, &[1.0; 5]).is_err());
+    }
+
     #[test]
     fn test_wma_accuracy_nan_check() {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
@@ -322,4 +460,34 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_wma_source_data_variants_match() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let params = WmaParams { period: Some(14) };
+
+        let candles_input = WmaInput::from_candles(&candles, "hl2", params.clone());
+        let slice_input = WmaInput::from_slice(&candles.hl2, params);
+        assert_eq!(candles_input.data.resolve(), candles.hl2.as_slice());
+        assert_eq!(slice_input.data.resolve(), candles.hl2.as_slice());
+
+        let from_candles = wma(&candles_input).expect("Failed wma from candles");
+        let from_slice = wma(&slice_input).expect("Failed wma from slice");
+        assert_eq!(from_candles.values.len(), candles.close.len());
+        for (i, (a, b)) in from_candles
+            .values
+            .iter()
+            .zip(&from_slice.values)
+            .enumerate()
+        {
+            assert!(
+                (a.is_nan() && b.is_nan()) || a == b,
+                "Wma variant mismatch at {}: {} vs {}",
+                i,
+                a,
+                b
+            );
+        }
+    }
 }
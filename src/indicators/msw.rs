@@ -20,6 +20,7 @@
 ///   the Mesa Sine Wave window is filled.
 /// - **`Err(MswError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use crate::utilities::math_functions::{atan64, fast_cos_f64, fast_sin_f64};
 use std::f64::consts::PI;
 use thiserror::Error;
@@ -128,6 +129,7 @@ pub fn msw(input: &MswInput) -> Result<MswOutput, MswError> {
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("msw", period, data.len() - first_valid_idx);
         return Err(MswError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -19,6 +19,7 @@
 ///   with leading `NaN`s until the ATR window is filled.
 /// - **`Err(NatrError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -143,6 +144,7 @@ pub fn natr(input: &NatrInput) -> Result<NatrOutput, NatrError> {
     };
 
     if (len - first_valid_idx) < period {
+        not_enough_valid_data("natr", period, len - first_valid_idx);
         return Err(NatrError::NotEnoughValidData {
             needed: period,
             valid: len - first_valid_idx,
@@ -19,6 +19,7 @@
 ///   with leading `NaN`s until the first valid index.
 /// - **`Err(NviError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -117,6 +118,7 @@ pub fn nvi(input: &NviInput) -> Result<NviOutput, NviError> {
     };
 
     if (close.len() - first_valid_idx) < 2 {
+        not_enough_valid_data("nvi", 2, close.len() - first_valid_idx);
         return Err(NviError::NotEnoughValidData {
             needed: 2,
             valid: close.len() - first_valid_idx,
@@ -1,4 +1,7 @@
+use crate::indicators::sma::{sma, SmaInput, SmaParams};
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
+use std::borrow::Cow;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -15,7 +18,58 @@ impl Default for PatternType {
 #[derive(Debug, Clone, Default)]
 pub struct PatternParams {
     pub pattern_type: PatternType,
+    /// Fraction of the first candle's body that the last candle must close into, for
+    /// patterns that take one. Dark cloud cover, piercing and the star patterns treat
+    /// `0.0` (the `Default`) as "use the TA-Lib default" (0.5 for dark cloud cover and
+    /// piercing, 0.3 for the stars), so an explicit zero penetration cannot be requested
+    /// from them. Morning/evening star and piercing reject negative or non-finite values
+    /// with [`PatternError::InvalidPenetration`].
     pub penetration: f64,
+    pub trend_filter: Option<TrendFilter>,
+    /// Averaging period of the "equal" tolerance used by counterattack (default 10) and
+    /// the on-neck/in-neck/thrusting family (default 5). `None` keeps each pattern's
+    /// default; a shorter period tracks recent candles more closely, and `Some(0)`
+    /// requires exactly equal prices.
+    pub equal_period: Option<usize>,
+    /// Candle field (e.g. `"hlc3"`) that replaces `close` when measuring the bodies fed
+    /// into a pattern's average-body thresholds, so `|open - hlc3|` sets what counts as
+    /// a long or short body. The pattern's own geometry (colors, gaps, where a candle
+    /// opens or closes) is still checked on raw OHLC. `None` measures bodies off
+    /// `close`.
+    ///
+    /// Honored by every pattern that averages candle bodies. Three black crows, three
+    /// outside, engulfing, concealing baby swallow and tasuki gap average only shadows or
+    /// ranges (or nothing), so their output does not depend on it.
+    pub measure_source: Option<String>,
+    /// Averaging period of the long-body, short-body and doji thresholds used by harami
+    /// and harami cross. `None` uses 10; `Some(0)` makes every non-empty body long.
+    pub body_period: Option<usize>,
+    /// Averaging period of the long-body threshold used by marubozu and upside gap two
+    /// crows. `None` uses 10.
+    pub body_long_period: Option<usize>,
+    /// Averaging period of the short-body threshold used by the hammer family (hammer,
+    /// hanging man, inverted hammer, shooting star), spinning top and upside gap two
+    /// crows. `None` uses 10.
+    pub body_short_period: Option<usize>,
+    /// Averaging period of the high-low range behind the "very short shadow" threshold
+    /// used by the hammer family and marubozu. `None` uses 10.
+    pub shadow_very_short_period: Option<usize>,
+    /// Averaging period of the high-low range behind the "near" tolerance used by the
+    /// hammer family and tasuki gap. `None` uses 5.
+    pub near_period: Option<usize>,
+}
+
+/// Prior-trend precondition for reversal patterns. A bullish signal is kept only when the
+/// close just before the pattern's first candle is below its `ma_period` SMA (a
+/// downtrend to reverse); a bearish signal needs that close above the SMA.
+///
+/// Honored by the reversal patterns: abandoned baby, dark cloud cover, engulfing,
+/// evening doji star, hammer, hanging man, harami, harami cross, inverted hammer,
+/// morning/evening star, piercing, shooting star and three outside. Continuation
+/// and indecision patterns ignore it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrendFilter {
+    pub ma_period: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -83,6 +137,172 @@ pub enum PatternType {
     CdlXSideGap3Methods,
 }
 
+/// What a pattern signals, for grouping patterns in a UI. Reversal patterns such as
+/// engulfing or harami fire in either direction, so the reversal direction of a given
+/// hit comes from [`PatternType::category_for`] with the fired score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternCategory {
+    BullishReversal,
+    BearishReversal,
+    Continuation,
+    Indecision,
+}
+
+impl PatternType {
+    /// The pattern's proper name, e.g. `"Two Crows"` for [`PatternType::Cdl2Crows`].
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            PatternType::Cdl2Crows => "Two Crows",
+            PatternType::Cdl3BlackCrows => "Three Black Crows",
+            PatternType::Cdl3Inside => "Three Inside Up/Down",
+            PatternType::Cdl3LineStrike => "Three-Line Strike",
+            PatternType::Cdl3Outside => "Three Outside Up/Down",
+            PatternType::Cdl3StarsInSouth => "Three Stars in the South",
+            PatternType::Cdl3WhiteSoldiers => "Three White Soldiers",
+            PatternType::CdlAbandonedBaby => "Abandoned Baby",
+            PatternType::CdlAdvanceBlock => "Advance Block",
+            PatternType::CdlBeltHold => "Belt Hold",
+            PatternType::CdlBreakaway => "Breakaway",
+            PatternType::CdlClosingMarubozu => "Closing Marubozu",
+            PatternType::CdlConcealBabySwall => "Concealing Baby Swallow",
+            PatternType::CdlCounterAttack => "Counterattack",
+            PatternType::CdlDarkCloudCover => "Dark Cloud Cover",
+            PatternType::CdlDoji => "Doji",
+            PatternType::CdlDojiStar => "Doji Star",
+            PatternType::CdlDragonflyDoji => "Dragonfly Doji",
+            PatternType::CdlEngulfing => "Engulfing",
+            PatternType::CdlEveningDojiStar => "Evening Doji Star",
+            PatternType::CdlEveningStar => "Evening Star",
+            PatternType::CdlGapSideSideWhite => "Up/Down-Gap Side-by-Side White Lines",
+            PatternType::CdlGravestoneDoji => "Gravestone Doji",
+            PatternType::CdlHammer => "Hammer",
+            PatternType::CdlHangingMan => "Hanging Man",
+            PatternType::CdlHarami => "Harami",
+            PatternType::CdlHaramiCross => "Harami Cross",
+            PatternType::CdlHighWave => "High Wave",
+            PatternType::CdlHikkake => "Hikkake",
+            PatternType::CdlHikkakeMod => "Modified Hikkake",
+            PatternType::CdlHomingPigeon => "Homing Pigeon",
+            PatternType::CdlIdentical3Crows => "Identical Three Crows",
+            PatternType::CdlInNeck => "In-Neck",
+            PatternType::CdlInvertedHammer => "Inverted Hammer",
+            PatternType::CdlKicking => "Kicking",
+            PatternType::CdlKickingByLength => "Kicking by Length",
+            PatternType::CdlLadderBottom => "Ladder Bottom",
+            PatternType::CdlLongLeggedDoji => "Long-Legged Doji",
+            PatternType::CdlLongLine => "Long Line",
+            PatternType::CdlMarubozu => "Marubozu",
+            PatternType::CdlMatchingLow => "Matching Low",
+            PatternType::CdlMatHold => "Mat Hold",
+            PatternType::CdlMorningDojiStar => "Morning Doji Star",
+            PatternType::CdlMorningStar => "Morning Star",
+            PatternType::CdlOnNeck => "On-Neck",
+            PatternType::CdlPiercing => "Piercing Line",
+            PatternType::CdlRickshawMan => "Rickshaw Man",
+            PatternType::CdlRiseFall3Methods => "Rising/Falling Three Methods",
+            PatternType::CdlSeparatingLines => "Separating Lines",
+            PatternType::CdlShootingStar => "Shooting Star",
+            PatternType::CdlShortLine => "Short Line",
+            PatternType::CdlSpinningTop => "Spinning Top",
+            PatternType::CdlStalledPattern => "Stalled Pattern",
+            PatternType::CdlStickSandwich => "Stick Sandwich",
+            PatternType::CdlTakuri => "Takuri",
+            PatternType::CdlTasukiGap => "Tasuki Gap",
+            PatternType::CdlThrusting => "Thrusting",
+            PatternType::CdlTristar => "Tristar",
+            PatternType::CdlUnique3River => "Unique Three River",
+            PatternType::CdlUpsideGap2Crows => "Upside Gap Two Crows",
+            PatternType::CdlXSideGap3Methods => "Upside/Downside Gap Three Methods",
+        }
+    }
+
+    /// The pattern's [`PatternCategory`] as listed for its bullish (positive) form. For
+    /// patterns that fire in both directions this says nothing about a particular hit; use
+    /// [`PatternType::category_for`] to label a fired score.
+    pub fn category(&self) -> PatternCategory {
+        match self {
+            PatternType::Cdl3Inside
+            | PatternType::Cdl3Outside
+            | PatternType::Cdl3StarsInSouth
+            | PatternType::Cdl3WhiteSoldiers
+            | PatternType::CdlAbandonedBaby
+            | PatternType::CdlBeltHold
+            | PatternType::CdlBreakaway
+            | PatternType::CdlConcealBabySwall
+            | PatternType::CdlCounterAttack
+            | PatternType::CdlDojiStar
+            | PatternType::CdlEngulfing
+            | PatternType::CdlHammer
+            | PatternType::CdlHarami
+            | PatternType::CdlHaramiCross
+            | PatternType::CdlHikkake
+            | PatternType::CdlHikkakeMod
+            | PatternType::CdlHomingPigeon
+            | PatternType::CdlInvertedHammer
+            | PatternType::CdlKicking
+            | PatternType::CdlKickingByLength
+            | PatternType::CdlLadderBottom
+            | PatternType::CdlMatchingLow
+            | PatternType::CdlMorningDojiStar
+            | PatternType::CdlMorningStar
+            | PatternType::CdlPiercing
+            | PatternType::CdlStickSandwich
+            | PatternType::CdlTakuri
+            | PatternType::CdlTristar
+            | PatternType::CdlUnique3River => PatternCategory::BullishReversal,
+            PatternType::Cdl2Crows
+            | PatternType::Cdl3BlackCrows
+            | PatternType::CdlAdvanceBlock
+            | PatternType::CdlDarkCloudCover
+            | PatternType::CdlEveningDojiStar
+            | PatternType::CdlEveningStar
+            | PatternType::CdlHangingMan
+            | PatternType::CdlIdentical3Crows
+            | PatternType::CdlShootingStar
+            | PatternType::CdlStalledPattern
+            | PatternType::CdlUpsideGap2Crows => PatternCategory::BearishReversal,
+            PatternType::Cdl3LineStrike
+            | PatternType::CdlClosingMarubozu
+            | PatternType::CdlGapSideSideWhite
+            | PatternType::CdlInNeck
+            | PatternType::CdlLongLine
+            | PatternType::CdlMarubozu
+            | PatternType::CdlMatHold
+            | PatternType::CdlOnNeck
+            | PatternType::CdlRiseFall3Methods
+            | PatternType::CdlSeparatingLines
+            | PatternType::CdlTasukiGap
+            | PatternType::CdlThrusting
+            | PatternType::CdlXSideGap3Methods => PatternCategory::Continuation,
+            PatternType::CdlDoji
+            | PatternType::CdlDragonflyDoji
+            | PatternType::CdlGravestoneDoji
+            | PatternType::CdlHighWave
+            | PatternType::CdlLongLeggedDoji
+            | PatternType::CdlRickshawMan
+            | PatternType::CdlShortLine
+            | PatternType::CdlSpinningTop => PatternCategory::Indecision,
+        }
+    }
+
+    /// The [`PatternCategory`] of a hit with this pattern's output `score`: a reversal
+    /// pattern is a [`PatternCategory::BullishReversal`] when `score > 0` and a
+    /// [`PatternCategory::BearishReversal`] when `score < 0`, so a bearish engulfing is
+    /// labeled bearish. Continuation and indecision patterns keep their category, and a
+    /// `score` of zero falls back to [`PatternType::category`].
+    pub fn category_for(&self, score: i8) -> PatternCategory {
+        match self.category() {
+            PatternCategory::BullishReversal | PatternCategory::BearishReversal if score > 0 => {
+                PatternCategory::BullishReversal
+            }
+            PatternCategory::BullishReversal | PatternCategory::BearishReversal if score < 0 => {
+                PatternCategory::BearishReversal
+            }
+            category => category,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PatternInput<'a> {
     pub data: PatternData<'a>,
@@ -118,6 +338,17 @@ pub enum PatternError {
     #[error("pattern_recognition: Not enough data points. Length={len}, pattern={pattern:?}")]
     NotEnoughData { len: usize, pattern: PatternType },
 
+    #[error("pattern_recognition: Invalid penetration: {penetration}")]
+    InvalidPenetration { penetration: f64 },
+
+    #[error(
+        "pattern_recognition: Invalid trend filter: ma_period = {ma_period}, data length = {len}"
+    )]
+    InvalidTrendFilter { ma_period: usize, len: usize },
+
+    #[error("pattern_recognition: Output length mismatch: expected {expected}, got {actual}")]
+    LengthMismatch { expected: usize, actual: usize },
+
     #[error("pattern_recognition: Candle field error: {0}")]
     CandleFieldError(String),
 
@@ -144,11 +375,83 @@ fn candle_range(open: f64, close: f64) -> f64 {
     real_body(open, close)
 }
 
+/// Whether the body of candle `inner` sits strictly inside the body of candle `outer` at
+/// both ends, as the harami family requires.
+#[inline(always)]
+fn body_inside(inner_open: f64, inner_close: f64, outer_open: f64, outer_close: f64) -> bool {
+    inner_open.max(inner_close) < outer_open.max(outer_close)
+        && inner_open.min(inner_close) > outer_open.min(outer_close)
+}
+
+/// The close series used for average-body thresholds: `params.measure_source` when set,
+/// otherwise the raw close.
+fn measure_close<'a>(
+    candles: &'a Candles,
+    params: &PatternParams,
+) -> Result<Cow<'a, [f64]>, PatternError> {
+    match &params.measure_source {
+        Some(source) => candles
+            .column(source)
+            .map_err(|e| PatternError::CandleFieldError(e.to_string())),
+        None => Ok(Cow::Borrowed(&candles.close)),
+    }
+}
+
+/// Applies `params.trend_filter` (if any) to `out`. `pattern_len` is the number of candles
+/// in the pattern, so the trend is read at `i - pattern_len` for a signal at `i`. Signals
+/// whose reference bar has no SMA yet are zeroed.
+fn apply_trend_filter(
+    out: &mut [i8],
+    close: &[f64],
+    params: &PatternParams,
+    pattern_len: usize,
+) -> Result<(), PatternError> {
+    let filter = match params.trend_filter {
+        Some(filter) => filter,
+        None => return Ok(()),
+    };
+    if filter.ma_period == 0 || filter.ma_period > close.len() {
+        return Err(PatternError::InvalidTrendFilter {
+            ma_period: filter.ma_period,
+            len: close.len(),
+        });
+    }
+
+    let sma_input = SmaInput::from_slice(
+        close,
+        SmaParams {
+            period: Some(filter.ma_period),
+        },
+    );
+    let trend = match sma(&sma_input) {
+        Ok(result) => result.values,
+        Err(_) => vec![f64::NAN; close.len()],
+    };
+
+    for (i, signal) in out.iter_mut().enumerate() {
+        if *signal == 0 {
+            continue;
+        }
+        let keep = i
+            .checked_sub(pattern_len)
+            .map(|r| {
+                let avg = trend[r];
+                !avg.is_nan()
+                    && ((*signal > 0 && close[r] < avg) || (*signal < 0 && close[r] > avg))
+            })
+            .unwrap_or(false);
+        if !keep {
+            *signal = 0;
+        }
+    }
+    Ok(())
+}
+
 #[inline]
 pub fn cdl2crows(input: &PatternInput) -> Result<PatternOutput, PatternError> {
     const BODY_LONG_PERIOD: usize = 10;
 
-    let (open, high, low, close) = match &input.data {
+    let (open, high, low, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -163,7 +466,9 @@ pub fn cdl2crows(input: &PatternInput) -> Result<PatternOutput, PatternError> {
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
 
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+
+            (open, high, low, close, measure)
         }
     };
 
@@ -171,6 +476,11 @@ pub fn cdl2crows(input: &PatternInput) -> Result<PatternOutput, PatternError> {
     let lookback_total = 2 + BODY_LONG_PERIOD;
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -183,7 +493,7 @@ pub fn cdl2crows(input: &PatternInput) -> Result<PatternOutput, PatternError> {
     let body_long_trailing_start = 0;
     let body_long_trailing_end = BODY_LONG_PERIOD;
     for i in body_long_trailing_start..body_long_trailing_end {
-        body_long_period_total += candle_range(open[i], close[i]);
+        body_long_period_total += candle_range(open[i], measure[i]);
     }
 
     for i in lookback_total..size {
@@ -217,8 +527,8 @@ pub fn cdl2crows(input: &PatternInput) -> Result<PatternOutput, PatternError> {
 
         let old_idx = i - lookback_total;
         let new_idx = i - 2;
-        body_long_period_total += candle_range(open[new_idx], close[new_idx])
-            - candle_range(open[old_idx], close[old_idx]);
+        body_long_period_total += candle_range(open[new_idx], measure[new_idx])
+            - candle_range(open[old_idx], measure[old_idx]);
     }
 
     Ok(PatternOutput { values: out })
@@ -250,6 +560,11 @@ pub fn cdl3blackcrows(input: &PatternInput) -> Result<PatternOutput, PatternErro
     let size = open.len();
     let lookback_total = 3 + SHADOW_VERY_SHORT_PERIOD;
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -332,7 +647,7 @@ pub fn cdl3inside(input: &PatternInput) -> Result<PatternOutput, PatternError> {
     const BODY_LONG_PERIOD: usize = 10;
     const BODY_SHORT_PERIOD: usize = 10;
 
-    let (open, high, low, close) = match &input.data {
+    let (open, high, low, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -347,13 +662,19 @@ pub fn cdl3inside(input: &PatternInput) -> Result<PatternOutput, PatternError> {
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
 
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
     let size = open.len();
     let lookback_total = 2 + BODY_LONG_PERIOD.max(BODY_SHORT_PERIOD);
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -376,32 +697,16 @@ pub fn cdl3inside(input: &PatternInput) -> Result<PatternOutput, PatternError> {
         (c - o).abs()
     }
 
-    fn max2(a: f64, b: f64) -> f64 {
-        if a > b {
-            a
-        } else {
-            b
-        }
-    }
-
-    fn min2(a: f64, b: f64) -> f64 {
-        if a < b {
-            a
-        } else {
-            b
-        }
-    }
-
     let mut out = vec![0i8; size];
 
     let mut body_long_period_total = 0.0;
     let mut body_short_period_total = 0.0;
 
     for i in 0..BODY_LONG_PERIOD {
-        body_long_period_total += candle_range(open[i], close[i]);
+        body_long_period_total += candle_range(open[i], measure[i]);
     }
     for i in 0..BODY_SHORT_PERIOD {
-        body_short_period_total += candle_range(open[i], close[i]);
+        body_short_period_total += candle_range(open[i], measure[i]);
     }
 
     for i in lookback_total..size {
@@ -410,8 +715,7 @@ pub fn cdl3inside(input: &PatternInput) -> Result<PatternOutput, PatternError> {
 
         if real_body(open[i - 2], close[i - 2]) > avg_body_long
             && real_body(open[i - 1], close[i - 1]) <= avg_body_short
-            && max2(close[i - 1], open[i - 1]) < max2(close[i - 2], open[i - 2])
-            && min2(close[i - 1], open[i - 1]) > min2(close[i - 2], open[i - 2])
+            && body_inside(open[i - 1], close[i - 1], open[i - 2], close[i - 2])
             && ((candle_color(open[i - 2], close[i - 2]) == 1
                 && candle_color(open[i], close[i]) == -1
                 && close[i] < open[i - 2])
@@ -425,12 +729,12 @@ pub fn cdl3inside(input: &PatternInput) -> Result<PatternOutput, PatternError> {
         }
 
         let old_idx_long = i - lookback_total;
-        body_long_period_total += candle_range(open[i - 2], close[i - 2])
-            - candle_range(open[old_idx_long], close[old_idx_long]);
+        body_long_period_total += candle_range(open[i - 2], measure[i - 2])
+            - candle_range(open[old_idx_long], measure[old_idx_long]);
 
         let old_idx_short = i - lookback_total + 1;
-        body_short_period_total += candle_range(open[i - 1], close[i - 1])
-            - candle_range(open[old_idx_short], close[old_idx_short]);
+        body_short_period_total += candle_range(open[i - 1], measure[i - 1])
+            - candle_range(open[old_idx_short], measure[old_idx_short]);
     }
 
     Ok(PatternOutput { values: out })
@@ -440,7 +744,7 @@ pub fn cdl3inside(input: &PatternInput) -> Result<PatternOutput, PatternError> {
 pub fn cdl3linestrike(input: &PatternInput) -> Result<PatternOutput, PatternError> {
     const NEAR_PERIOD: usize = 10;
 
-    let (open, high, low, close) = match &input.data {
+    let (open, high, low, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -455,13 +759,19 @@ pub fn cdl3linestrike(input: &PatternInput) -> Result<PatternOutput, PatternErro
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
 
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
     let size = open.len();
     let lookback_total = 3 + NEAR_PERIOD;
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -501,8 +811,8 @@ pub fn cdl3linestrike(input: &PatternInput) -> Result<PatternOutput, PatternErro
     let mut sum2 = 0.0;
 
     for i in 0..NEAR_PERIOD {
-        sum3 += candle_range(open[i], close[i]);
-        sum2 += candle_range(open[i + 1], close[i + 1]);
+        sum3 += candle_range(open[i], measure[i]);
+        sum2 += candle_range(open[i + 1], measure[i + 1]);
     }
 
     for i in lookback_total..size {
@@ -534,18 +844,21 @@ pub fn cdl3linestrike(input: &PatternInput) -> Result<PatternOutput, PatternErro
 
         let old_idx3 = i - lookback_total;
         let new_idx3 = i - 3;
-        sum3 += candle_range(open[new_idx3], close[new_idx3])
-            - candle_range(open[old_idx3], close[old_idx3]);
+        sum3 += candle_range(open[new_idx3], measure[new_idx3])
+            - candle_range(open[old_idx3], measure[old_idx3]);
 
         let old_idx2 = i - lookback_total + 1;
         let new_idx2 = i - 2;
-        sum2 += candle_range(open[new_idx2], close[new_idx2])
-            - candle_range(open[old_idx2], close[old_idx2]);
+        sum2 += candle_range(open[new_idx2], measure[new_idx2])
+            - candle_range(open[old_idx2], measure[old_idx2]);
     }
 
     Ok(PatternOutput { values: out })
 }
 
+/// Three Outside Up/Down: an engulfing pair confirmed by a third candle closing beyond
+/// the second. The pattern compares candles directly and needs no averaging period, so
+/// the only requirement is three bars. Honors `params.trend_filter`.
 #[inline]
 pub fn cdl3outside(input: &PatternInput) -> Result<PatternOutput, PatternError> {
     let (open, high, low, close) = match &input.data {
@@ -579,6 +892,11 @@ pub fn cdl3outside(input: &PatternInput) -> Result<PatternOutput, PatternError>
     let lookback_total = 2;
 
     if size < lookback_total + 1 {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total + 1,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -610,17 +928,19 @@ pub fn cdl3outside(input: &PatternInput) -> Result<PatternOutput, PatternError>
         }
     }
 
+    apply_trend_filter(&mut out, close, &input.params, 3)?;
+
     Ok(PatternOutput { values: out })
 }
 
 #[inline]
 pub fn cdl3starsinsouth(input: &PatternInput) -> Result<PatternOutput, PatternError> {
     const BODY_LONG_PERIOD: usize = 10;
-    const SHADOW_LONG_PERIOD: usize = 10;
     const SHADOW_VERY_SHORT_PERIOD: usize = 10;
+    const SHADOW_VERY_SHORT_FACTOR: f64 = 0.1;
     const BODY_SHORT_PERIOD: usize = 10;
 
-    let (open, high, low, close) = match &input.data {
+    let (open, high, low, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -634,7 +954,8 @@ pub fn cdl3starsinsouth(input: &PatternInput) -> Result<PatternOutput, PatternEr
             let close = candles
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
@@ -662,17 +983,17 @@ pub fn cdl3starsinsouth(input: &PatternInput) -> Result<PatternOutput, PatternEr
             h - c
         }
     }
-    fn candle_range(o: f64, c: f64) -> f64 {
-        (c - o).abs()
-    }
-
     let size = open.len();
     let lookback_total = 2 + BODY_LONG_PERIOD
-        .max(SHADOW_LONG_PERIOD)
         .max(SHADOW_VERY_SHORT_PERIOD)
         .max(BODY_SHORT_PERIOD);
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -682,48 +1003,39 @@ pub fn cdl3starsinsouth(input: &PatternInput) -> Result<PatternOutput, PatternEr
     let mut out = vec![0i8; size];
 
     let mut body_long_sum = 0.0;
-    let mut shadow_long_sum = 0.0;
     let mut shadow_very_short_sum_1 = 0.0;
     let mut shadow_very_short_sum_0 = 0.0;
     let mut body_short_sum = 0.0;
 
-    let body_long_trail_start = lookback_total - BODY_LONG_PERIOD;
-    for idx in body_long_trail_start..lookback_total {
-        let ref_index = if idx >= 2 { idx - 2 } else { 0 };
-        body_long_sum += candle_range(open[ref_index], close[ref_index]);
-    }
+    let mut body_long_trailing_idx = lookback_total - BODY_LONG_PERIOD;
+    let mut shadow_very_short_trailing_idx = lookback_total - SHADOW_VERY_SHORT_PERIOD;
+    let mut body_short_trailing_idx = lookback_total - BODY_SHORT_PERIOD;
 
-    let shadow_long_trail_start = lookback_total - SHADOW_LONG_PERIOD;
-    for idx in shadow_long_trail_start..lookback_total {
-        let ref_index = if idx >= 2 { idx - 2 } else { 0 };
-        shadow_long_sum += candle_range(open[ref_index], close[ref_index]);
+    for idx in body_long_trailing_idx..lookback_total {
+        body_long_sum += real_body(open[idx - 2], measure[idx - 2]);
     }
-
-    let shadow_very_short_trail_start = lookback_total - SHADOW_VERY_SHORT_PERIOD;
-    for idx in shadow_very_short_trail_start..lookback_total {
-        let ref_index_1 = if idx >= 1 { idx - 1 } else { 0 };
-        shadow_very_short_sum_1 +=
-            lower_shadow(open[ref_index_1], close[ref_index_1], low[ref_index_1]);
-
-        shadow_very_short_sum_0 += lower_shadow(open[idx], close[idx], low[idx]);
+    for idx in shadow_very_short_trailing_idx..lookback_total {
+        shadow_very_short_sum_1 += high[idx - 1] - low[idx - 1];
+        shadow_very_short_sum_0 += high[idx] - low[idx];
     }
-
-    let body_short_trail_start = lookback_total - BODY_SHORT_PERIOD;
-    for idx in body_short_trail_start..lookback_total {
-        body_short_sum += candle_range(open[idx], close[idx]);
+    for idx in body_short_trailing_idx..lookback_total {
+        body_short_sum += real_body(open[idx], measure[idx]);
     }
+
     for i in lookback_total..size {
         let avg_body_long = body_long_sum / (BODY_LONG_PERIOD as f64);
-        let avg_shadow_long = shadow_long_sum / (SHADOW_LONG_PERIOD as f64);
-        let avg_shadow_very_short_1 = shadow_very_short_sum_1 / (SHADOW_VERY_SHORT_PERIOD as f64);
-        let avg_shadow_very_short_0 = shadow_very_short_sum_0 / (SHADOW_VERY_SHORT_PERIOD as f64);
+        let avg_shadow_very_short_1 =
+            SHADOW_VERY_SHORT_FACTOR * shadow_very_short_sum_1 / (SHADOW_VERY_SHORT_PERIOD as f64);
+        let avg_shadow_very_short_0 =
+            SHADOW_VERY_SHORT_FACTOR * shadow_very_short_sum_0 / (SHADOW_VERY_SHORT_PERIOD as f64);
         let avg_body_short = body_short_sum / (BODY_SHORT_PERIOD as f64);
 
         if candle_color(open[i - 2], close[i - 2]) == -1
             && candle_color(open[i - 1], close[i - 1]) == -1
             && candle_color(open[i], close[i]) == -1
             && real_body(open[i - 2], close[i - 2]) > avg_body_long
-            && lower_shadow(open[i - 2], close[i - 2], low[i - 2]) > avg_shadow_long
+            && lower_shadow(open[i - 2], close[i - 2], low[i - 2])
+                > real_body(open[i - 2], close[i - 2])
             && real_body(open[i - 1], close[i - 1]) < real_body(open[i - 2], close[i - 2])
             && open[i - 1] > close[i - 2]
             && open[i - 1] <= high[i - 2]
@@ -737,42 +1049,26 @@ pub fn cdl3starsinsouth(input: &PatternInput) -> Result<PatternOutput, PatternEr
             && high[i] < high[i - 1]
         {
             out[i] = 100;
-        } else {
-            out[i] = 0;
-        }
-
-        let old_idx = i - lookback_total;
-
-        {
-            let old_ref = if old_idx >= 2 { old_idx - 2 } else { 0 };
-            let new_ref = i - 2;
-            body_long_sum += candle_range(open[new_ref], close[new_ref])
-                - candle_range(open[old_ref], close[old_ref]);
         }
 
-        {
-            let old_ref = if old_idx >= 2 { old_idx - 2 } else { 0 };
-            let new_ref = i - 2;
-            shadow_long_sum += candle_range(open[new_ref], close[new_ref])
-                - candle_range(open[old_ref], close[old_ref]);
-        }
-
-        {
-            let old_ref_1 = if old_idx >= 1 { old_idx - 1 } else { 0 };
-            let new_ref_1 = i - 1;
-            shadow_very_short_sum_1 +=
-                lower_shadow(open[new_ref_1], close[new_ref_1], low[new_ref_1])
-                    - lower_shadow(open[old_ref_1], close[old_ref_1], low[old_ref_1]);
-        }
-        {
-            shadow_very_short_sum_0 += lower_shadow(open[i], close[i], low[i])
-                - lower_shadow(open[old_idx], close[old_idx], low[old_idx]);
-        }
+        body_long_sum += real_body(open[i - 2], measure[i - 2])
+            - real_body(
+                open[body_long_trailing_idx - 2],
+                measure[body_long_trailing_idx - 2],
+            );
+        shadow_very_short_sum_1 += (high[i - 1] - low[i - 1])
+            - (high[shadow_very_short_trailing_idx - 1] - low[shadow_very_short_trailing_idx - 1]);
+        shadow_very_short_sum_0 += (high[i] - low[i])
+            - (high[shadow_very_short_trailing_idx] - low[shadow_very_short_trailing_idx]);
+        body_short_sum += real_body(open[i], measure[i])
+            - real_body(
+                open[body_short_trailing_idx],
+                measure[body_short_trailing_idx],
+            );
 
-        {
-            body_short_sum +=
-                candle_range(open[i], close[i]) - candle_range(open[old_idx], close[old_idx]);
-        }
+        body_long_trailing_idx += 1;
+        shadow_very_short_trailing_idx += 1;
+        body_short_trailing_idx += 1;
     }
 
     Ok(PatternOutput { values: out })
@@ -785,7 +1081,7 @@ pub fn cdl3whitesoldiers(input: &PatternInput) -> Result<PatternOutput, PatternE
     const FAR_PERIOD: usize = 10;
     const BODY_SHORT_PERIOD: usize = 10;
 
-    let (open, high, low, close) = match &input.data {
+    let (open, high, low, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -800,7 +1096,8 @@ pub fn cdl3whitesoldiers(input: &PatternInput) -> Result<PatternOutput, PatternE
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
 
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
@@ -843,6 +1140,11 @@ pub fn cdl3whitesoldiers(input: &PatternInput) -> Result<PatternOutput, PatternE
         .max(BODY_SHORT_PERIOD);
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -866,22 +1168,22 @@ pub fn cdl3whitesoldiers(input: &PatternInput) -> Result<PatternOutput, PatternE
     }
     for i in 0..NEAR_PERIOD {
         if i + 2 < size {
-            near_sum[2] += candle_range(open[i + 2], close[i + 2]);
+            near_sum[2] += candle_range(open[i + 2], measure[i + 2]);
         }
         if i + 1 < size {
-            near_sum[1] += candle_range(open[i + 1], close[i + 1]);
+            near_sum[1] += candle_range(open[i + 1], measure[i + 1]);
         }
     }
     for i in 0..FAR_PERIOD {
         if i + 2 < size {
-            far_sum[2] += candle_range(open[i + 2], close[i + 2]);
+            far_sum[2] += candle_range(open[i + 2], measure[i + 2]);
         }
         if i + 1 < size {
-            far_sum[1] += candle_range(open[i + 1], close[i + 1]);
+            far_sum[1] += candle_range(open[i + 1], measure[i + 1]);
         }
     }
     for i in 0..BODY_SHORT_PERIOD {
-        body_short_sum += candle_range(open[i], close[i]);
+        body_short_sum += candle_range(open[i], measure[i]);
     }
 
     for i in lookback_total..size {
@@ -932,30 +1234,30 @@ pub fn cdl3whitesoldiers(input: &PatternInput) -> Result<PatternOutput, PatternE
         shadow_very_short_sum[0] += upper_shadow(open[i], close[i], high[i])
             - upper_shadow(open[old_idx], close[old_idx], high[old_idx]);
 
-        far_sum[2] += candle_range(open[i - 2], close[i - 2])
+        far_sum[2] += candle_range(open[i - 2], measure[i - 2])
             - candle_range(
                 open[old_idx.saturating_sub(2)],
-                close[old_idx.saturating_sub(2)],
+                measure[old_idx.saturating_sub(2)],
             );
-        far_sum[1] += candle_range(open[i - 1], close[i - 1])
+        far_sum[1] += candle_range(open[i - 1], measure[i - 1])
             - candle_range(
                 open[old_idx.saturating_sub(1)],
-                close[old_idx.saturating_sub(1)],
+                measure[old_idx.saturating_sub(1)],
             );
 
-        near_sum[2] += candle_range(open[i - 2], close[i - 2])
+        near_sum[2] += candle_range(open[i - 2], measure[i - 2])
             - candle_range(
                 open[old_idx.saturating_sub(2)],
-                close[old_idx.saturating_sub(2)],
+                measure[old_idx.saturating_sub(2)],
             );
-        near_sum[1] += candle_range(open[i - 1], close[i - 1])
+        near_sum[1] += candle_range(open[i - 1], measure[i - 1])
             - candle_range(
                 open[old_idx.saturating_sub(1)],
-                close[old_idx.saturating_sub(1)],
+                measure[old_idx.saturating_sub(1)],
             );
 
         body_short_sum +=
-            candle_range(open[i], close[i]) - candle_range(open[old_idx], close[old_idx]);
+            candle_range(open[i], measure[i]) - candle_range(open[old_idx], measure[old_idx]);
     }
 
     Ok(PatternOutput { values: out })
@@ -967,7 +1269,7 @@ pub fn cdlabandonedbaby(input: &PatternInput) -> Result<PatternOutput, PatternEr
     const BODY_DOJI_PERIOD: usize = 10;
     const BODY_SHORT_PERIOD: usize = 10;
 
-    let (open, high, low, close) = match &input.data {
+    let (open, high, low, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -982,7 +1284,8 @@ pub fn cdlabandonedbaby(input: &PatternInput) -> Result<PatternOutput, PatternEr
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
 
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
@@ -1018,6 +1321,11 @@ pub fn cdlabandonedbaby(input: &PatternInput) -> Result<PatternOutput, PatternEr
         .max(BODY_SHORT_PERIOD);
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -1030,13 +1338,13 @@ pub fn cdlabandonedbaby(input: &PatternInput) -> Result<PatternOutput, PatternEr
     let mut body_short_sum = 0.0;
 
     for i in 0..BODY_LONG_PERIOD {
-        body_long_sum += candle_range(open[i], close[i]);
+        body_long_sum += candle_range(open[i], measure[i]);
     }
     for i in 0..BODY_DOJI_PERIOD {
-        body_doji_sum += candle_range(open[i], close[i]);
+        body_doji_sum += candle_range(open[i], measure[i]);
     }
     for i in 0..BODY_SHORT_PERIOD {
-        body_short_sum += candle_range(open[i], close[i]);
+        body_short_sum += candle_range(open[i], measure[i]);
     }
 
     for i in lookback_total..size {
@@ -1065,26 +1373,28 @@ pub fn cdlabandonedbaby(input: &PatternInput) -> Result<PatternOutput, PatternEr
         }
 
         let old_idx = i - lookback_total;
-        body_long_sum += candle_range(open[i - 2], close[i - 2])
+        body_long_sum += candle_range(open[i - 2], measure[i - 2])
             - candle_range(
                 open[old_idx.saturating_sub(2)],
-                close[old_idx.saturating_sub(2)],
+                measure[old_idx.saturating_sub(2)],
             );
-        body_doji_sum += candle_range(open[i - 1], close[i - 1])
+        body_doji_sum += candle_range(open[i - 1], measure[i - 1])
             - candle_range(
                 open[old_idx.saturating_sub(1)],
-                close[old_idx.saturating_sub(1)],
+                measure[old_idx.saturating_sub(1)],
             );
         body_short_sum +=
-            candle_range(open[i], close[i]) - candle_range(open[old_idx], close[old_idx]);
+            candle_range(open[i], measure[i]) - candle_range(open[old_idx], measure[old_idx]);
     }
 
+    apply_trend_filter(&mut out, close, &input.params, 3)?;
+
     Ok(PatternOutput { values: out })
 }
 
 #[inline]
 pub fn cdladvanceblock(input: &PatternInput) -> Result<PatternOutput, PatternError> {
-    let (open, high, low, close) = match &input.data {
+    let (open, high, low, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -1099,7 +1409,8 @@ pub fn cdladvanceblock(input: &PatternInput) -> Result<PatternOutput, PatternErr
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
 
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
@@ -1116,6 +1427,11 @@ pub fn cdladvanceblock(input: &PatternInput) -> Result<PatternOutput, PatternErr
         .max(body_long_period);
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -1182,19 +1498,20 @@ pub fn cdladvanceblock(input: &PatternInput) -> Result<PatternOutput, PatternErr
     }
     i = near_trailing_idx;
     while i < start_idx {
-        near_period_total[2] += real_body(open[i.saturating_sub(2)], close[i.saturating_sub(2)]);
-        near_period_total[1] += real_body(open[i.saturating_sub(1)], close[i.saturating_sub(1)]);
+        near_period_total[2] += real_body(open[i.saturating_sub(2)], measure[i.saturating_sub(2)]);
+        near_period_total[1] += real_body(open[i.saturating_sub(1)], measure[i.saturating_sub(1)]);
         i += 1;
     }
     i = far_trailing_idx;
     while i < start_idx {
-        far_period_total[2] += real_body(open[i.saturating_sub(2)], close[i.saturating_sub(2)]);
-        far_period_total[1] += real_body(open[i.saturating_sub(1)], close[i.saturating_sub(1)]);
+        far_period_total[2] += real_body(open[i.saturating_sub(2)], measure[i.saturating_sub(2)]);
+        far_period_total[1] += real_body(open[i.saturating_sub(1)], measure[i.saturating_sub(1)]);
         i += 1;
     }
     i = body_long_trailing_idx;
     while i < start_idx {
-        body_long_period_total += real_body(open[i.saturating_sub(2)], close[i.saturating_sub(2)]);
+        body_long_period_total +=
+            real_body(open[i.saturating_sub(2)], measure[i.saturating_sub(2)]);
         i += 1;
     }
 
@@ -1269,24 +1586,24 @@ pub fn cdladvanceblock(input: &PatternInput) -> Result<PatternOutput, PatternErr
         for tot_idx in (1..=2).rev() {
             far_period_total[tot_idx] += real_body(
                 open[idx.saturating_sub(tot_idx)],
-                close[idx.saturating_sub(tot_idx)],
+                measure[idx.saturating_sub(tot_idx)],
             ) - real_body(
                 open[far_trailing_idx.saturating_sub(tot_idx)],
-                close[far_trailing_idx.saturating_sub(tot_idx)],
+                measure[far_trailing_idx.saturating_sub(tot_idx)],
             );
             near_period_total[tot_idx] += real_body(
                 open[idx.saturating_sub(tot_idx)],
-                close[idx.saturating_sub(tot_idx)],
+                measure[idx.saturating_sub(tot_idx)],
             ) - real_body(
                 open[near_trailing_idx.saturating_sub(tot_idx)],
-                close[near_trailing_idx.saturating_sub(tot_idx)],
+                measure[near_trailing_idx.saturating_sub(tot_idx)],
             );
         }
 
-        body_long_period_total += real_body(open[idx - 2], close[idx - 2])
+        body_long_period_total += real_body(open[idx - 2], measure[idx - 2])
             - real_body(
                 open[body_long_trailing_idx.saturating_sub(2)],
-                close[body_long_trailing_idx.saturating_sub(2)],
+                measure[body_long_trailing_idx.saturating_sub(2)],
             );
 
         idx += 1;
@@ -1302,7 +1619,7 @@ pub fn cdladvanceblock(input: &PatternInput) -> Result<PatternOutput, PatternErr
 
 #[inline]
 pub fn cdlbelthold(input: &PatternInput) -> Result<PatternOutput, PatternError> {
-    let (open, high, low, close) = match &input.data {
+    let (open, high, low, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -1317,7 +1634,8 @@ pub fn cdlbelthold(input: &PatternInput) -> Result<PatternOutput, PatternError>
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
 
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
@@ -1327,6 +1645,11 @@ pub fn cdlbelthold(input: &PatternInput) -> Result<PatternOutput, PatternError>
     let lookback_total = body_long_period.max(shadow_very_short_period);
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -1370,7 +1693,7 @@ pub fn cdlbelthold(input: &PatternInput) -> Result<PatternOutput, PatternError>
 
     let mut i = body_long_trailing_idx;
     while i < start_idx {
-        body_long_period_total += real_body(open[i], close[i]);
+        body_long_period_total += real_body(open[i], measure[i]);
         i += 1;
     }
 
@@ -1399,8 +1722,11 @@ pub fn cdlbelthold(input: &PatternInput) -> Result<PatternOutput, PatternError>
             out[start_idx] = (color as i8) * 100;
         }
 
-        body_long_period_total += real_body(open[start_idx], close[start_idx])
-            - real_body(open[body_long_trailing_idx], close[body_long_trailing_idx]);
+        body_long_period_total += real_body(open[start_idx], measure[start_idx])
+            - real_body(
+                open[body_long_trailing_idx],
+                measure[body_long_trailing_idx],
+            );
 
         let trailing_color = candle_color(open[start_idx], close[start_idx]);
         let new_range = if trailing_color == 1 {
@@ -1438,7 +1764,7 @@ pub fn cdlbelthold(input: &PatternInput) -> Result<PatternOutput, PatternError>
 
 #[inline]
 pub fn cdlbreakaway(input: &PatternInput) -> Result<PatternOutput, PatternError> {
-    let (open, high, low, close) = match &input.data {
+    let (open, high, low, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -1453,7 +1779,8 @@ pub fn cdlbreakaway(input: &PatternInput) -> Result<PatternOutput, PatternError>
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
 
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
@@ -1462,6 +1789,11 @@ pub fn cdlbreakaway(input: &PatternInput) -> Result<PatternOutput, PatternError>
     let lookback_total = 4 + body_long_period;
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -1491,7 +1823,7 @@ pub fn cdlbreakaway(input: &PatternInput) -> Result<PatternOutput, PatternError>
 
     let mut i = body_long_trailing_idx;
     while i < start_idx {
-        body_long_period_total += candle_range(open[i - 4], close[i - 4]);
+        body_long_period_total += candle_range(open[i - 4], measure[i - 4]);
         i += 1;
     }
 
@@ -1538,10 +1870,10 @@ pub fn cdlbreakaway(input: &PatternInput) -> Result<PatternOutput, PatternError>
             out[start_idx] = (c5 as i8) * 100;
         }
 
-        body_long_period_total += candle_range(open[start_idx - 4], close[start_idx - 4])
+        body_long_period_total += candle_range(open[start_idx - 4], measure[start_idx - 4])
             - candle_range(
                 open[body_long_trailing_idx - 4],
-                close[body_long_trailing_idx - 4],
+                measure[body_long_trailing_idx - 4],
             );
 
         start_idx += 1;
@@ -1553,7 +1885,7 @@ pub fn cdlbreakaway(input: &PatternInput) -> Result<PatternOutput, PatternError>
 
 #[inline]
 pub fn cdlclosingmarubozu(input: &PatternInput) -> Result<PatternOutput, PatternError> {
-    let (open, high, low, close) = match &input.data {
+    let (open, high, low, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -1568,7 +1900,8 @@ pub fn cdlclosingmarubozu(input: &PatternInput) -> Result<PatternOutput, Pattern
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
 
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
@@ -1578,6 +1911,11 @@ pub fn cdlclosingmarubozu(input: &PatternInput) -> Result<PatternOutput, Pattern
     let lookback_total = body_long_period.max(shadow_very_short_period);
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -1621,7 +1959,7 @@ pub fn cdlclosingmarubozu(input: &PatternInput) -> Result<PatternOutput, Pattern
 
     let mut i = body_long_trailing_idx;
     while i < start_idx {
-        body_long_period_total += real_body(open[i], close[i]);
+        body_long_period_total += real_body(open[i], measure[i]);
         i += 1;
     }
 
@@ -1650,8 +1988,11 @@ pub fn cdlclosingmarubozu(input: &PatternInput) -> Result<PatternOutput, Pattern
             out[start_idx] = (color as i8) * 100;
         }
 
-        body_long_period_total += real_body(open[start_idx], close[start_idx])
-            - real_body(open[body_long_trailing_idx], close[body_long_trailing_idx]);
+        body_long_period_total += real_body(open[start_idx], measure[start_idx])
+            - real_body(
+                open[body_long_trailing_idx],
+                measure[body_long_trailing_idx],
+            );
 
         let trailing_color = candle_color(open[start_idx], close[start_idx]);
         let new_shadow = if trailing_color == 1 {
@@ -1714,6 +2055,11 @@ pub fn cdlconcealbabyswall(input: &PatternInput) -> Result<PatternOutput, Patter
     let lookback_total = 3 + shadow_very_short_period;
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -1869,7 +2215,7 @@ pub fn cdlconcealbabyswall(input: &PatternInput) -> Result<PatternOutput, Patter
 
 #[inline]
 pub fn cdlcounterattack(input: &PatternInput) -> Result<PatternOutput, PatternError> {
-    let (open, high, low, close) = match &input.data {
+    let (open, high, low, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -1884,16 +2230,22 @@ pub fn cdlcounterattack(input: &PatternInput) -> Result<PatternOutput, PatternEr
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
 
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
     let size = open.len();
     let body_long_period = 10;
-    let equal_period = 10;
+    let equal_period = input.params.equal_period.unwrap_or(10);
     let lookback_total = 1 + body_long_period.max(equal_period);
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -1933,14 +2285,14 @@ pub fn cdlcounterattack(input: &PatternInput) -> Result<PatternOutput, PatternEr
 
     let mut i = equal_trailing_idx;
     while i < start_idx {
-        equal_period_total += real_body(open[i - 1], close[i - 1]);
+        equal_period_total += real_body(open[i - 1], measure[i - 1]);
         i += 1;
     }
 
     i = body_long_trailing_idx;
     while i < start_idx {
-        body_long_period_total[1] += real_body(open[i - 1], close[i - 1]);
-        body_long_period_total[0] += real_body(open[i], close[i]);
+        body_long_period_total[1] += real_body(open[i - 1], measure[i - 1]);
+        body_long_period_total[0] += real_body(open[i], measure[i]);
         i += 1;
     }
 
@@ -1962,15 +2314,18 @@ pub fn cdlcounterattack(input: &PatternInput) -> Result<PatternOutput, PatternEr
             out[start_idx] = (c2 as i8) * 100;
         }
 
-        equal_period_total += real_body(open[start_idx - 1], close[start_idx - 1])
-            - real_body(open[equal_trailing_idx - 1], close[equal_trailing_idx - 1]);
+        equal_period_total += real_body(open[start_idx - 1], measure[start_idx - 1])
+            - real_body(
+                open[equal_trailing_idx - 1],
+                measure[equal_trailing_idx - 1],
+            );
 
         for tot_idx in (0..=1).rev() {
             body_long_period_total[tot_idx] +=
-                real_body(open[start_idx - tot_idx], close[start_idx - tot_idx])
+                real_body(open[start_idx - tot_idx], measure[start_idx - tot_idx])
                     - real_body(
                         open[body_long_trailing_idx - tot_idx],
-                        close[body_long_trailing_idx - tot_idx],
+                        measure[body_long_trailing_idx - tot_idx],
                     );
         }
 
@@ -1984,7 +2339,7 @@ pub fn cdlcounterattack(input: &PatternInput) -> Result<PatternOutput, PatternEr
 
 #[inline]
 pub fn cdldarkcloudcover(input: &PatternInput) -> Result<PatternOutput, PatternError> {
-    let (open, high, _, close) = match &input.data {
+    let (open, high, _, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -1998,7 +2353,8 @@ pub fn cdldarkcloudcover(input: &PatternInput) -> Result<PatternOutput, PatternE
             let close = candles
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
@@ -2012,6 +2368,11 @@ pub fn cdldarkcloudcover(input: &PatternInput) -> Result<PatternOutput, PatternE
     let lookback_total = 1 + body_long_period;
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -2035,7 +2396,7 @@ pub fn cdldarkcloudcover(input: &PatternInput) -> Result<PatternOutput, PatternE
 
     let mut i = body_long_trailing_idx;
     while i < start_idx {
-        body_long_period_total += real_body(open[i - 1], close[i - 1]);
+        body_long_period_total += real_body(open[i - 1], measure[i - 1]);
         i += 1;
     }
 
@@ -2053,22 +2414,24 @@ pub fn cdldarkcloudcover(input: &PatternInput) -> Result<PatternOutput, PatternE
             out[start_idx] = -100;
         }
 
-        body_long_period_total += real_body(open[start_idx - 1], close[start_idx - 1])
+        body_long_period_total += real_body(open[start_idx - 1], measure[start_idx - 1])
             - real_body(
                 open[body_long_trailing_idx - 1],
-                close[body_long_trailing_idx - 1],
+                measure[body_long_trailing_idx - 1],
             );
 
         start_idx += 1;
         body_long_trailing_idx += 1;
     }
 
+    apply_trend_filter(&mut out, close, &input.params, 2)?;
+
     Ok(PatternOutput { values: out })
 }
 
 #[inline]
 pub fn cdldoji(input: &PatternInput) -> Result<PatternOutput, PatternError> {
-    let (open, _, _, close) = match &input.data {
+    let (open, _, _, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -2082,7 +2445,8 @@ pub fn cdldoji(input: &PatternInput) -> Result<PatternOutput, PatternError> {
             let close = candles
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
@@ -2091,6 +2455,11 @@ pub fn cdldoji(input: &PatternInput) -> Result<PatternOutput, PatternError> {
     let lookback_total = body_doji_period;
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -2114,7 +2483,7 @@ pub fn cdldoji(input: &PatternInput) -> Result<PatternOutput, PatternError> {
 
     let mut i = body_doji_trailing_idx;
     while i < start_idx {
-        body_doji_period_total += candle_range(open[i], close[i]);
+        body_doji_period_total += candle_range(open[i], measure[i]);
         i += 1;
     }
 
@@ -2124,8 +2493,11 @@ pub fn cdldoji(input: &PatternInput) -> Result<PatternOutput, PatternError> {
             out[start_idx] = 100;
         }
 
-        body_doji_period_total += candle_range(open[start_idx], close[start_idx])
-            - candle_range(open[body_doji_trailing_idx], close[body_doji_trailing_idx]);
+        body_doji_period_total += candle_range(open[start_idx], measure[start_idx])
+            - candle_range(
+                open[body_doji_trailing_idx],
+                measure[body_doji_trailing_idx],
+            );
 
         start_idx += 1;
         body_doji_trailing_idx += 1;
@@ -2136,7 +2508,7 @@ pub fn cdldoji(input: &PatternInput) -> Result<PatternOutput, PatternError> {
 
 #[inline]
 pub fn cdldojistar(input: &PatternInput) -> Result<PatternOutput, PatternError> {
-    let (open, high, low, close) = match &input.data {
+    let (open, high, low, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -2151,7 +2523,8 @@ pub fn cdldojistar(input: &PatternInput) -> Result<PatternOutput, PatternError>
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
 
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
@@ -2161,6 +2534,11 @@ pub fn cdldojistar(input: &PatternInput) -> Result<PatternOutput, PatternError>
     let lookback_total = 1 + body_long_period.max(body_doji_period);
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -2196,13 +2574,13 @@ pub fn cdldojistar(input: &PatternInput) -> Result<PatternOutput, PatternError>
 
     let mut i = body_long_trailing_idx;
     while i < start_idx - 1 {
-        body_long_period_total += real_body(open[i], close[i]);
+        body_long_period_total += real_body(open[i], measure[i]);
         i += 1;
     }
 
     i = body_doji_trailing_idx;
     while i < start_idx {
-        body_doji_period_total += real_body(open[i], close[i]);
+        body_doji_period_total += real_body(open[i], measure[i]);
         i += 1;
     }
 
@@ -2229,11 +2607,17 @@ pub fn cdldojistar(input: &PatternInput) -> Result<PatternOutput, PatternError>
             out[start_idx] = -candle_color(open[start_idx - 1], close[start_idx - 1]) as i8 * 100;
         }
 
-        body_long_period_total += real_body(open[start_idx - 1], close[start_idx - 1])
-            - real_body(open[body_long_trailing_idx], close[body_long_trailing_idx]);
+        body_long_period_total += real_body(open[start_idx - 1], measure[start_idx - 1])
+            - real_body(
+                open[body_long_trailing_idx],
+                measure[body_long_trailing_idx],
+            );
 
-        body_doji_period_total += real_body(open[start_idx], close[start_idx])
-            - real_body(open[body_doji_trailing_idx], close[body_doji_trailing_idx]);
+        body_doji_period_total += real_body(open[start_idx], measure[start_idx])
+            - real_body(
+                open[body_doji_trailing_idx],
+                measure[body_doji_trailing_idx],
+            );
 
         start_idx += 1;
         body_long_trailing_idx += 1;
@@ -2245,7 +2629,7 @@ pub fn cdldojistar(input: &PatternInput) -> Result<PatternOutput, PatternError>
 
 #[inline]
 pub fn cdldragonflydoji(input: &PatternInput) -> Result<PatternOutput, PatternError> {
-    let (open, high, low, close) = match &input.data {
+    let (open, high, low, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -2260,7 +2644,8 @@ pub fn cdldragonflydoji(input: &PatternInput) -> Result<PatternOutput, PatternEr
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
 
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
@@ -2270,6 +2655,11 @@ pub fn cdldragonflydoji(input: &PatternInput) -> Result<PatternOutput, PatternEr
     let lookback_total = body_doji_period.max(shadow_very_short_period);
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -2313,7 +2703,7 @@ pub fn cdldragonflydoji(input: &PatternInput) -> Result<PatternOutput, PatternEr
 
     let mut i = body_doji_trailing_idx;
     while i < start_idx {
-        body_doji_period_total += candle_range(open[i], close[i]);
+        body_doji_period_total += candle_range(open[i], measure[i]);
         i += 1;
     }
 
@@ -2336,8 +2726,11 @@ pub fn cdldragonflydoji(input: &PatternInput) -> Result<PatternOutput, PatternEr
             out[start_idx] = 100;
         }
 
-        body_doji_period_total += candle_range(open[start_idx], close[start_idx])
-            - candle_range(open[body_doji_trailing_idx], close[body_doji_trailing_idx]);
+        body_doji_period_total += candle_range(open[start_idx], measure[start_idx])
+            - candle_range(
+                open[body_doji_trailing_idx],
+                measure[body_doji_trailing_idx],
+            );
 
         let current_shadow_sum = (upper_shadow(open[start_idx], high[start_idx], close[start_idx]))
             .max(lower_shadow(
@@ -2366,6 +2759,9 @@ pub fn cdldragonflydoji(input: &PatternInput) -> Result<PatternOutput, PatternEr
     Ok(PatternOutput { values: out })
 }
 
+/// Engulfing: a candle whose body engulfs the previous opposite-colored body. Like
+/// `cdl3outside` it needs no averaging period, only two bars. Honors
+/// `params.trend_filter`.
 #[inline]
 pub fn cdlengulfing(input: &PatternInput) -> Result<PatternOutput, PatternError> {
     let (open, _, _, close) = match &input.data {
@@ -2387,7 +2783,13 @@ pub fn cdlengulfing(input: &PatternInput) -> Result<PatternOutput, PatternError>
     };
 
     let size = open.len();
-    if size < 2 {
+    let lookback_total = 1;
+    if size < lookback_total + 1 {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total + 1,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -2395,7 +2797,7 @@ pub fn cdlengulfing(input: &PatternInput) -> Result<PatternOutput, PatternError>
     }
 
     let mut out = vec![0i8; size];
-    for i in 1..size {
+    for i in lookback_total..size {
         let c1 = candle_color(open[i - 1], close[i - 1]);
         let c2 = candle_color(open[i], close[i]);
         if (c2 == 1
@@ -2417,12 +2819,14 @@ pub fn cdlengulfing(input: &PatternInput) -> Result<PatternOutput, PatternError>
         }
     }
 
+    apply_trend_filter(&mut out, close, &input.params, 2)?;
+
     Ok(PatternOutput { values: out })
 }
 
 #[inline]
 pub fn cdleveningdojistar(input: &PatternInput) -> Result<PatternOutput, PatternError> {
-    let (open, _, _, close) = match &input.data {
+    let (open, _, _, close, measure) = match &input.data {
         PatternData::Candles { candles } => {
             let open = candles
                 .select_candle_field("open")
@@ -2436,7 +2840,8 @@ pub fn cdleveningdojistar(input: &PatternInput) -> Result<PatternOutput, Pattern
             let close = candles
                 .select_candle_field("close")
                 .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
-            (open, high, low, close)
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
         }
     };
 
@@ -2454,6 +2859,11 @@ pub fn cdleveningdojistar(input: &PatternInput) -> Result<PatternOutput, Pattern
         .max(body_short_period);
 
     if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
         return Err(PatternError::NotEnoughData {
             len: size,
             pattern: input.params.pattern_type.clone(),
@@ -2486,19 +2896,19 @@ pub fn cdleveningdojistar(input: &PatternInput) -> Result<PatternOutput, Pattern
 
     let mut i = body_long_trailing_idx;
     while i < start_idx - 2 {
-        body_long_period_total += real_body(open[i], close[i]);
+        body_long_period_total += real_body(open[i], measure[i]);
         i += 1;
     }
 
     i = body_doji_trailing_idx;
     while i < start_idx - 1 {
-        body_doji_period_total += real_body(open[i], close[i]);
+        body_doji_period_total += real_body(open[i], measure[i]);
         i += 1;
     }
 
     i = body_short_trailing_idx;
     while i < start_idx {
-        body_short_period_total += real_body(open[i], close[i]);
+        body_short_period_total += real_body(open[i], measure[i]);
         i += 1;
     }
 
@@ -2524,16 +2934,22 @@ pub fn cdleveningdojistar(input: &PatternInput) -> Result<PatternOutput, Pattern
             out[start_idx] = -100;
         }
 
-        body_long_period_total += real_body(open[start_idx - 2], close[start_idx - 2])
-            - real_body(open[body_long_trailing_idx], close[body_long_trailing_idx]);
+        body_long_period_total += real_body(open[start_idx - 2], measure[start_idx - 2])
+            - real_body(
+                open[body_long_trailing_idx],
+                measure[body_long_trailing_idx],
+            );
 
-        body_doji_period_total += real_body(open[start_idx - 1], close[start_idx - 1])
-            - real_body(open[body_doji_trailing_idx], close[body_doji_trailing_idx]);
+        body_doji_period_total += real_body(open[start_idx - 1], measure[start_idx - 1])
+            - real_body(
+                open[body_doji_trailing_idx],
+                measure[body_doji_trailing_idx],
+            );
 
-        body_short_period_total += real_body(open[start_idx], close[start_idx])
+        body_short_period_total += real_body(open[start_idx], measure[start_idx])
             - real_body(
                 open[body_short_trailing_idx],
-                close[body_short_trailing_idx],
+                measure[body_short_trailing_idx],
             );
 
         start_idx += 1;
@@ -2542,5 +2958,2262 @@ pub fn cdleveningdojistar(input: &PatternInput) -> Result<PatternOutput, Pattern
         body_short_trailing_idx += 1;
     }
 
+    apply_trend_filter(&mut out, close, &input.params, 3)?;
+
     Ok(PatternOutput { values: out })
 }
+
+#[inline]
+pub fn cdleveningstar(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    star_pattern(input, -1)
+}
+
+#[inline]
+pub fn cdlmorningstar(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    star_pattern(input, 1)
+}
+
+#[inline]
+fn star_pattern(input: &PatternInput, direction: i32) -> Result<PatternOutput, PatternError> {
+    let (open, _, _, close, measure) = match &input.data {
+        PatternData::Candles { candles } => {
+            let open = candles
+                .select_candle_field("open")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let high = candles
+                .select_candle_field("high")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let low = candles
+                .select_candle_field("low")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let close = candles
+                .select_candle_field("close")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
+        }
+    };
+
+    let size = open.len();
+    let body_long_period = 10;
+    let body_short_period = 10;
+    let penetration = if input.params.penetration == 0.0 {
+        0.3
+    } else {
+        input.params.penetration
+    };
+    if !penetration.is_finite() || penetration < 0.0 {
+        return Err(PatternError::InvalidPenetration { penetration });
+    }
+    let lookback_total = 2 + body_long_period.max(body_short_period);
+
+    if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
+        return Err(PatternError::NotEnoughData {
+            len: size,
+            pattern: input.params.pattern_type.clone(),
+        });
+    }
+
+    let mut out = vec![0i8; size];
+    let mut body_long_period_total = 0.0;
+    let mut body_short_period_total = 0.0;
+    let mut body_short_period_total_2 = 0.0;
+
+    #[inline(always)]
+    fn candle_average(sum: f64, period: usize) -> f64 {
+        if period == 0 {
+            0.0
+        } else {
+            sum / period as f64
+        }
+    }
+
+    #[inline(always)]
+    fn gap_up(current_open: f64, current_close: f64, prev_open: f64, prev_close: f64) -> bool {
+        current_open.min(current_close) > prev_open.max(prev_close)
+    }
+
+    #[inline(always)]
+    fn gap_down(current_open: f64, current_close: f64, prev_open: f64, prev_close: f64) -> bool {
+        current_open.max(current_close) < prev_open.min(prev_close)
+    }
+
+    let mut start_idx = lookback_total;
+    let mut body_long_trailing_idx = start_idx - 2 - body_long_period;
+    let mut body_short_trailing_idx = start_idx - 1 - body_short_period;
+
+    let mut i = body_long_trailing_idx;
+    while i < start_idx - 2 {
+        body_long_period_total += real_body(open[i], measure[i]);
+        i += 1;
+    }
+
+    i = body_short_trailing_idx;
+    while i < start_idx - 1 {
+        body_short_period_total += real_body(open[i], measure[i]);
+        body_short_period_total_2 += real_body(open[i + 1], measure[i + 1]);
+        i += 1;
+    }
+
+    while start_idx < size {
+        let first_body = real_body(open[start_idx - 2], close[start_idx - 2]);
+        let star_gaps = if direction == 1 {
+            gap_down(
+                open[start_idx - 1],
+                close[start_idx - 1],
+                open[start_idx - 2],
+                close[start_idx - 2],
+            )
+        } else {
+            gap_up(
+                open[start_idx - 1],
+                close[start_idx - 1],
+                open[start_idx - 2],
+                close[start_idx - 2],
+            )
+        };
+        let third_penetrates = if direction == 1 {
+            close[start_idx] > close[start_idx - 2] + first_body * penetration
+        } else {
+            close[start_idx] < close[start_idx - 2] - first_body * penetration
+        };
+
+        if first_body > candle_average(body_long_period_total, body_long_period)
+            && candle_color(open[start_idx - 2], close[start_idx - 2]) == -direction
+            && real_body(open[start_idx - 1], close[start_idx - 1])
+                <= candle_average(body_short_period_total, body_short_period)
+            && star_gaps
+            && real_body(open[start_idx], close[start_idx])
+                > candle_average(body_short_period_total_2, body_short_period)
+            && candle_color(open[start_idx], close[start_idx]) == direction
+            && third_penetrates
+        {
+            out[start_idx] = (direction * 100) as i8;
+        }
+
+        body_long_period_total += real_body(open[start_idx - 2], measure[start_idx - 2])
+            - real_body(
+                open[body_long_trailing_idx],
+                measure[body_long_trailing_idx],
+            );
+
+        body_short_period_total += real_body(open[start_idx - 1], measure[start_idx - 1])
+            - real_body(
+                open[body_short_trailing_idx],
+                measure[body_short_trailing_idx],
+            );
+
+        body_short_period_total_2 += real_body(open[start_idx], measure[start_idx])
+            - real_body(
+                open[body_short_trailing_idx + 1],
+                measure[body_short_trailing_idx + 1],
+            );
+
+        start_idx += 1;
+        body_long_trailing_idx += 1;
+        body_short_trailing_idx += 1;
+    }
+
+    apply_trend_filter(&mut out, close, &input.params, 3)?;
+
+    Ok(PatternOutput { values: out })
+}
+
+#[inline]
+pub fn cdlpiercing(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    let (open, _, low, close, measure) = match &input.data {
+        PatternData::Candles { candles } => {
+            let open = candles
+                .select_candle_field("open")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let high = candles
+                .select_candle_field("high")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let low = candles
+                .select_candle_field("low")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let close = candles
+                .select_candle_field("close")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
+        }
+    };
+
+    let size = open.len();
+    let body_long_period = 10;
+    let penetration = if input.params.penetration == 0.0 {
+        0.5
+    } else {
+        input.params.penetration
+    };
+    if !penetration.is_finite() || penetration < 0.0 {
+        return Err(PatternError::InvalidPenetration { penetration });
+    }
+    let lookback_total = 1 + body_long_period;
+
+    if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
+        return Err(PatternError::NotEnoughData {
+            len: size,
+            pattern: input.params.pattern_type.clone(),
+        });
+    }
+
+    let mut out = vec![0i8; size];
+    let mut body_long_period_total = [0.0; 2];
+
+    #[inline(always)]
+    fn candle_average(sum: f64, period: usize) -> f64 {
+        if period == 0 {
+            0.0
+        } else {
+            sum / period as f64
+        }
+    }
+
+    let mut start_idx = lookback_total;
+    let mut body_long_trailing_idx = start_idx - body_long_period;
+
+    let mut i = body_long_trailing_idx;
+    while i < start_idx {
+        body_long_period_total[1] += real_body(open[i - 1], measure[i - 1]);
+        body_long_period_total[0] += real_body(open[i], measure[i]);
+        i += 1;
+    }
+
+    while start_idx < size {
+        if candle_color(open[start_idx - 1], close[start_idx - 1]) == -1
+            && real_body(open[start_idx - 1], close[start_idx - 1])
+                > candle_average(body_long_period_total[1], body_long_period)
+            && candle_color(open[start_idx], close[start_idx]) == 1
+            && real_body(open[start_idx], close[start_idx])
+                > candle_average(body_long_period_total[0], body_long_period)
+            && open[start_idx] < low[start_idx - 1]
+            && close[start_idx] < open[start_idx - 1]
+            && close[start_idx]
+                > close[start_idx - 1]
+                    + real_body(open[start_idx - 1], close[start_idx - 1]) * penetration
+        {
+            out[start_idx] = 100;
+        }
+
+        for (tot_idx, total) in body_long_period_total.iter_mut().enumerate() {
+            *total += real_body(open[start_idx - tot_idx], measure[start_idx - tot_idx])
+                - real_body(
+                    open[body_long_trailing_idx - tot_idx],
+                    measure[body_long_trailing_idx - tot_idx],
+                );
+        }
+
+        start_idx += 1;
+        body_long_trailing_idx += 1;
+    }
+
+    apply_trend_filter(&mut out, close, &input.params, 2)?;
+
+    Ok(PatternOutput { values: out })
+}
+
+#[inline]
+pub fn cdlonneck(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    neck_pattern(input, NeckVariant::OnNeck)
+}
+
+#[inline]
+pub fn cdlinneck(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    neck_pattern(input, NeckVariant::InNeck)
+}
+
+#[inline]
+pub fn cdlthrusting(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    neck_pattern(input, NeckVariant::Thrusting)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum NeckVariant {
+    OnNeck,
+    InNeck,
+    Thrusting,
+}
+
+/// Shared scan for the bearish continuation patterns where a long black candle is
+/// followed by a white candle opening below its low. The variants differ only in how far
+/// the white candle closes back into the black body:
+/// - on-neck: close near the prior low,
+/// - in-neck: close at or just above the prior close,
+/// - thrusting: close clearly above the prior close but not beyond the body midpoint.
+///
+/// "Near" uses TA-Lib's `Equal` setting: 5% of the average high-low range over
+/// `equal_period` bars (5 by default).
+#[inline]
+fn neck_pattern(input: &PatternInput, variant: NeckVariant) -> Result<PatternOutput, PatternError> {
+    let (open, high, low, close, measure) = match &input.data {
+        PatternData::Candles { candles } => {
+            let open = candles
+                .select_candle_field("open")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let high = candles
+                .select_candle_field("high")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let low = candles
+                .select_candle_field("low")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let close = candles
+                .select_candle_field("close")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
+        }
+    };
+
+    let size = open.len();
+    let body_long_period = 10;
+    let equal_period = input.params.equal_period.unwrap_or(5);
+    let equal_factor = 0.05;
+    let lookback_total = 1 + body_long_period.max(equal_period);
+
+    if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
+        return Err(PatternError::NotEnoughData {
+            len: size,
+            pattern: input.params.pattern_type.clone(),
+        });
+    }
+
+    let mut out = vec![0i8; size];
+    let mut body_long_period_total = 0.0;
+    let mut equal_period_total = 0.0;
+
+    #[inline(always)]
+    fn candle_average(sum: f64, period: usize) -> f64 {
+        if period == 0 {
+            0.0
+        } else {
+            sum / period as f64
+        }
+    }
+
+    let mut start_idx = lookback_total;
+    let mut body_long_trailing_idx = start_idx - body_long_period;
+    let mut equal_trailing_idx = start_idx - equal_period;
+
+    let mut i = body_long_trailing_idx;
+    while i < start_idx {
+        body_long_period_total += real_body(open[i - 1], measure[i - 1]);
+        i += 1;
+    }
+
+    i = equal_trailing_idx;
+    while i < start_idx {
+        equal_period_total += high[i - 1] - low[i - 1];
+        i += 1;
+    }
+
+    while start_idx < size {
+        let prev = start_idx - 1;
+        let equal = equal_factor * candle_average(equal_period_total, equal_period);
+        let closes_back = match variant {
+            NeckVariant::OnNeck => {
+                close[start_idx] <= low[prev] + equal && close[start_idx] >= low[prev] - equal
+            }
+            NeckVariant::InNeck => {
+                close[start_idx] <= close[prev] + equal && close[start_idx] >= close[prev]
+            }
+            NeckVariant::Thrusting => {
+                close[start_idx] > close[prev] + equal
+                    && close[start_idx] <= close[prev] + real_body(open[prev], close[prev]) * 0.5
+            }
+        };
+
+        if candle_color(open[prev], close[prev]) == -1
+            && real_body(open[prev], close[prev])
+                > candle_average(body_long_period_total, body_long_period)
+            && candle_color(open[start_idx], close[start_idx]) == 1
+            && open[start_idx] < low[prev]
+            && closes_back
+        {
+            out[start_idx] = -100;
+        }
+
+        body_long_period_total += real_body(open[prev], measure[prev])
+            - real_body(
+                open[body_long_trailing_idx - 1],
+                measure[body_long_trailing_idx - 1],
+            );
+        equal_period_total +=
+            (high[prev] - low[prev]) - (high[equal_trailing_idx - 1] - low[equal_trailing_idx - 1]);
+
+        start_idx += 1;
+        body_long_trailing_idx += 1;
+        equal_trailing_idx += 1;
+    }
+
+    Ok(PatternOutput { values: out })
+}
+
+#[inline]
+pub fn cdlhammer(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    hammer_pattern(input, HammerVariant::Hammer)
+}
+
+#[inline]
+pub fn cdlhangingman(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    hammer_pattern(input, HammerVariant::HangingMan)
+}
+
+#[inline]
+pub fn cdlinvertedhammer(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    hammer_pattern(input, HammerVariant::InvertedHammer)
+}
+
+#[inline]
+pub fn cdlshootingstar(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    hammer_pattern(input, HammerVariant::ShootingStar)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HammerVariant {
+    Hammer,
+    HangingMan,
+    InvertedHammer,
+    ShootingStar,
+}
+
+/// Shared scan for the single-candle patterns with a short body, one long shadow (longer
+/// than the body) and one very short shadow (under 10% of the average high-low range over
+/// `params.shadow_very_short_period` bars, default 10). The body is short when it is
+/// below the average body over the last `params.body_short_period` bars (default 10).
+/// - hammer (+100): long lower shadow, body at or near the prior low,
+/// - hanging man (-100): long lower shadow, body at or near the prior high,
+/// - inverted hammer (+100): long upper shadow, body gapping down from the prior body,
+/// - shooting star (-100): long upper shadow, body gapping up from the prior body.
+///
+/// "Near" uses TA-Lib's `Near` setting: 20% of the average high-low range over
+/// `params.near_period` bars (default 5), measured on the prior candle.
+#[inline]
+fn hammer_pattern(
+    input: &PatternInput,
+    variant: HammerVariant,
+) -> Result<PatternOutput, PatternError> {
+    let (open, high, low, close, measure) = match &input.data {
+        PatternData::Candles { candles } => {
+            let open = candles
+                .select_candle_field("open")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let high = candles
+                .select_candle_field("high")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let low = candles
+                .select_candle_field("low")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let close = candles
+                .select_candle_field("close")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
+        }
+    };
+
+    let size = open.len();
+    let body_short_period = input.params.body_short_period.unwrap_or(10);
+    let shadow_very_short_period = input.params.shadow_very_short_period.unwrap_or(10);
+    let shadow_very_short_factor = 0.1;
+    let near_period = input.params.near_period.unwrap_or(5);
+    let near_factor = 0.2;
+    let lookback_total = 1 + body_short_period
+        .max(shadow_very_short_period)
+        .max(near_period);
+
+    if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
+        return Err(PatternError::NotEnoughData {
+            len: size,
+            pattern: input.params.pattern_type.clone(),
+        });
+    }
+
+    let mut out = vec![0i8; size];
+    let mut body_short_period_total = 0.0;
+    let mut shadow_very_short_period_total = 0.0;
+    let mut near_period_total = 0.0;
+
+    #[inline(always)]
+    fn candle_average(sum: f64, period: usize) -> f64 {
+        if period == 0 {
+            0.0
+        } else {
+            sum / period as f64
+        }
+    }
+
+    let mut start_idx = lookback_total;
+    let mut body_short_trailing_idx = start_idx - body_short_period;
+    let mut shadow_very_short_trailing_idx = start_idx - shadow_very_short_period;
+    let mut near_trailing_idx = start_idx - 1 - near_period;
+
+    let mut i = body_short_trailing_idx;
+    while i < start_idx {
+        body_short_period_total += real_body(open[i], measure[i]);
+        i += 1;
+    }
+
+    i = shadow_very_short_trailing_idx;
+    while i < start_idx {
+        shadow_very_short_period_total += high[i] - low[i];
+        i += 1;
+    }
+
+    i = near_trailing_idx;
+    while i < start_idx - 1 {
+        near_period_total += high[i] - low[i];
+        i += 1;
+    }
+
+    while start_idx < size {
+        let prev = start_idx - 1;
+        let body = real_body(open[start_idx], close[start_idx]);
+        let body_top = open[start_idx].max(close[start_idx]);
+        let body_bottom = open[start_idx].min(close[start_idx]);
+        let upper_shadow = high[start_idx] - body_top;
+        let lower_shadow = body_bottom - low[start_idx];
+        let very_short = shadow_very_short_factor
+            * candle_average(shadow_very_short_period_total, shadow_very_short_period);
+        let near = near_factor * candle_average(near_period_total, near_period);
+
+        let (long_shadow, short_shadow, placed, signal) = match variant {
+            HammerVariant::Hammer => (
+                lower_shadow,
+                upper_shadow,
+                body_bottom <= low[prev] + near,
+                100,
+            ),
+            HammerVariant::HangingMan => (
+                lower_shadow,
+                upper_shadow,
+                body_bottom >= high[prev] - near,
+                -100,
+            ),
+            HammerVariant::InvertedHammer => (
+                upper_shadow,
+                lower_shadow,
+                body_top < open[prev].min(close[prev]),
+                100,
+            ),
+            HammerVariant::ShootingStar => (
+                upper_shadow,
+                lower_shadow,
+                body_bottom > open[prev].max(close[prev]),
+                -100,
+            ),
+        };
+
+        if body < candle_average(body_short_period_total, body_short_period)
+            && long_shadow > body
+            && short_shadow < very_short
+            && placed
+        {
+            out[start_idx] = signal;
+        }
+
+        body_short_period_total += real_body(open[start_idx], measure[start_idx])
+            - real_body(
+                open[body_short_trailing_idx],
+                measure[body_short_trailing_idx],
+            );
+        shadow_very_short_period_total += (high[start_idx] - low[start_idx])
+            - (high[shadow_very_short_trailing_idx] - low[shadow_very_short_trailing_idx]);
+        near_period_total +=
+            (high[prev] - low[prev]) - (high[near_trailing_idx] - low[near_trailing_idx]);
+
+        start_idx += 1;
+        body_short_trailing_idx += 1;
+        shadow_very_short_trailing_idx += 1;
+        near_trailing_idx += 1;
+    }
+
+    apply_trend_filter(&mut out, close, &input.params, 1)?;
+
+    Ok(PatternOutput { values: out })
+}
+
+/// Marubozu: a long body (above the `params.body_long_period` average body, default 10)
+/// with both shadows very short (under 10% of the `params.shadow_very_short_period`
+/// average high-low range, default 10). Unlike [`cdlclosingmarubozu`], the
+/// shadow on the opening side must be very short too. Returns +100 for a white candle
+/// and -100 for a black one.
+#[inline]
+pub fn cdlmarubozu(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    let (open, high, low, close, measure) = match &input.data {
+        PatternData::Candles { candles } => {
+            let open = candles
+                .select_candle_field("open")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let high = candles
+                .select_candle_field("high")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let low = candles
+                .select_candle_field("low")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let close = candles
+                .select_candle_field("close")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
+        }
+    };
+
+    let size = open.len();
+    let body_long_period = input.params.body_long_period.unwrap_or(10);
+    let shadow_very_short_period = input.params.shadow_very_short_period.unwrap_or(10);
+    let shadow_very_short_factor = 0.1;
+    let lookback_total = body_long_period.max(shadow_very_short_period);
+
+    if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
+        return Err(PatternError::NotEnoughData {
+            len: size,
+            pattern: input.params.pattern_type.clone(),
+        });
+    }
+
+    let mut out = vec![0i8; size];
+    let mut body_long_period_total = 0.0;
+    let mut shadow_very_short_period_total = 0.0;
+
+    #[inline(always)]
+    fn candle_average(sum: f64, period: usize) -> f64 {
+        if period == 0 {
+            0.0
+        } else {
+            sum / period as f64
+        }
+    }
+
+    let mut start_idx = lookback_total;
+    let mut body_long_trailing_idx = start_idx - body_long_period;
+    let mut shadow_very_short_trailing_idx = start_idx - shadow_very_short_period;
+
+    let mut i = body_long_trailing_idx;
+    while i < start_idx {
+        body_long_period_total += real_body(open[i], measure[i]);
+        i += 1;
+    }
+
+    i = shadow_very_short_trailing_idx;
+    while i < start_idx {
+        shadow_very_short_period_total += high[i] - low[i];
+        i += 1;
+    }
+
+    while start_idx < size {
+        let upper_shadow = high[start_idx] - open[start_idx].max(close[start_idx]);
+        let lower_shadow = open[start_idx].min(close[start_idx]) - low[start_idx];
+        let very_short = shadow_very_short_factor
+            * candle_average(shadow_very_short_period_total, shadow_very_short_period);
+
+        if real_body(open[start_idx], close[start_idx])
+            > candle_average(body_long_period_total, body_long_period)
+            && upper_shadow < very_short
+            && lower_shadow < very_short
+        {
+            out[start_idx] = (candle_color(open[start_idx], close[start_idx]) * 100) as i8;
+        }
+
+        body_long_period_total += real_body(open[start_idx], measure[start_idx])
+            - real_body(
+                open[body_long_trailing_idx],
+                measure[body_long_trailing_idx],
+            );
+        shadow_very_short_period_total += (high[start_idx] - low[start_idx])
+            - (high[shadow_very_short_trailing_idx] - low[shadow_very_short_trailing_idx]);
+
+        start_idx += 1;
+        body_long_trailing_idx += 1;
+        shadow_very_short_trailing_idx += 1;
+    }
+
+    Ok(PatternOutput { values: out })
+}
+
+/// Spinning top: a short body (below the `params.body_short_period` average body,
+/// default 10) with both shadows longer than the body. Returns +100 for a white candle and -100 for a black one.
+#[inline]
+pub fn cdlspinningtop(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    let (open, high, low, close, measure) = match &input.data {
+        PatternData::Candles { candles } => {
+            let open = candles
+                .select_candle_field("open")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let high = candles
+                .select_candle_field("high")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let low = candles
+                .select_candle_field("low")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let close = candles
+                .select_candle_field("close")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
+        }
+    };
+
+    let size = open.len();
+    let body_short_period = input.params.body_short_period.unwrap_or(10);
+    let lookback_total = body_short_period;
+
+    if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
+        return Err(PatternError::NotEnoughData {
+            len: size,
+            pattern: input.params.pattern_type.clone(),
+        });
+    }
+
+    let mut out = vec![0i8; size];
+    let mut body_short_period_total = 0.0;
+
+    #[inline(always)]
+    fn candle_average(sum: f64, period: usize) -> f64 {
+        if period == 0 {
+            0.0
+        } else {
+            sum / period as f64
+        }
+    }
+
+    let mut start_idx = lookback_total;
+    let mut body_short_trailing_idx = start_idx - body_short_period;
+
+    let mut i = body_short_trailing_idx;
+    while i < start_idx {
+        body_short_period_total += real_body(open[i], measure[i]);
+        i += 1;
+    }
+
+    while start_idx < size {
+        let body = real_body(open[start_idx], close[start_idx]);
+        let upper_shadow = high[start_idx] - open[start_idx].max(close[start_idx]);
+        let lower_shadow = open[start_idx].min(close[start_idx]) - low[start_idx];
+
+        if body < candle_average(body_short_period_total, body_short_period)
+            && upper_shadow > body
+            && lower_shadow > body
+        {
+            out[start_idx] = (candle_color(open[start_idx], close[start_idx]) * 100) as i8;
+        }
+
+        body_short_period_total += real_body(open[start_idx], measure[start_idx])
+            - real_body(
+                open[body_short_trailing_idx],
+                measure[body_short_trailing_idx],
+            );
+
+        start_idx += 1;
+        body_short_trailing_idx += 1;
+    }
+
+    Ok(PatternOutput { values: out })
+}
+
+/// Tasuki gap: a candle that gaps away from the prior body in the trend direction,
+/// followed by an opposite-colored candle of similar body size that opens inside the
+/// second body and closes inside the gap without filling it. Body sizes are "similar"
+/// when they differ by less than 0.2 times the `params.near_period` (default 5) average
+/// high-low range. Returns +100
+/// for an upside gap (white then black) and -100 for a downside gap (black then white).
+#[inline]
+pub fn cdltasukigap(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    let (open, high, low, close) = match &input.data {
+        PatternData::Candles { candles } => {
+            let open = candles
+                .select_candle_field("open")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let high = candles
+                .select_candle_field("high")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let low = candles
+                .select_candle_field("low")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let close = candles
+                .select_candle_field("close")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            (open, high, low, close)
+        }
+    };
+
+    let size = open.len();
+    let near_period = input.params.near_period.unwrap_or(5);
+    let near_factor = 0.2;
+    let lookback_total = 2 + near_period;
+
+    if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
+        return Err(PatternError::NotEnoughData {
+            len: size,
+            pattern: input.params.pattern_type.clone(),
+        });
+    }
+
+    let mut out = vec![0i8; size];
+    let mut near_period_total = 0.0;
+
+    #[inline(always)]
+    fn gap_up(current_open: f64, current_close: f64, prev_open: f64, prev_close: f64) -> bool {
+        current_open.min(current_close) > prev_open.max(prev_close)
+    }
+
+    #[inline(always)]
+    fn gap_down(current_open: f64, current_close: f64, prev_open: f64, prev_close: f64) -> bool {
+        current_open.max(current_close) < prev_open.min(prev_close)
+    }
+
+    #[inline(always)]
+    fn candle_average(sum: f64, period: usize) -> f64 {
+        if period == 0 {
+            0.0
+        } else {
+            sum / period as f64
+        }
+    }
+
+    let mut start_idx = lookback_total;
+    let mut near_trailing_idx = start_idx - near_period;
+
+    let mut i = near_trailing_idx;
+    while i < start_idx {
+        near_period_total += high[i - 1] - low[i - 1];
+        i += 1;
+    }
+
+    while start_idx < size {
+        let first = start_idx - 2;
+        let second = start_idx - 1;
+        let near = near_factor * candle_average(near_period_total, near_period);
+        let similar_bodies = (real_body(open[second], close[second])
+            - real_body(open[start_idx], close[start_idx]))
+        .abs()
+            < near;
+
+        let upside = gap_up(open[second], close[second], open[first], close[first])
+            && candle_color(open[second], close[second]) == 1
+            && candle_color(open[start_idx], close[start_idx]) == -1
+            && open[start_idx] < close[second]
+            && open[start_idx] > open[second]
+            && close[start_idx] < open[second]
+            && close[start_idx] > close[first].max(open[first]);
+        let downside = gap_down(open[second], close[second], open[first], close[first])
+            && candle_color(open[second], close[second]) == -1
+            && candle_color(open[start_idx], close[start_idx]) == 1
+            && open[start_idx] < open[second]
+            && open[start_idx] > close[second]
+            && close[start_idx] > open[second]
+            && close[start_idx] < close[first].min(open[first]);
+
+        if (upside || downside) && similar_bodies {
+            out[start_idx] = (candle_color(open[second], close[second]) * 100) as i8;
+        }
+
+        near_period_total += (high[second] - low[second])
+            - (high[near_trailing_idx - 1] - low[near_trailing_idx - 1]);
+
+        start_idx += 1;
+        near_trailing_idx += 1;
+    }
+
+    Ok(PatternOutput { values: out })
+}
+
+/// Upside gap two crows: a long white candle, a short black candle whose body gaps up
+/// from it, and a second black candle that opens above the short one's open and closes
+/// below its close but still above the white candle's close. Long and short bodies are
+/// measured against the `params.body_long_period` and `params.body_short_period`
+/// (default 10 each) average bodies. Returns -100 when the pattern is found.
+#[inline]
+pub fn cdlupsidegap2crows(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    let (open, close, measure) = match &input.data {
+        PatternData::Candles { candles } => {
+            let open = candles
+                .select_candle_field("open")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let close = candles
+                .select_candle_field("close")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let measure = measure_close(candles, &input.params)?;
+            (open, close, measure)
+        }
+    };
+
+    let size = open.len();
+    let body_long_period = input.params.body_long_period.unwrap_or(10);
+    let body_short_period = input.params.body_short_period.unwrap_or(10);
+    let lookback_total = 2 + body_long_period.max(body_short_period);
+
+    if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
+        return Err(PatternError::NotEnoughData {
+            len: size,
+            pattern: input.params.pattern_type.clone(),
+        });
+    }
+
+    let mut out = vec![0i8; size];
+    let mut body_long_period_total = 0.0;
+    let mut body_short_period_total = 0.0;
+
+    #[inline(always)]
+    fn gap_up(current_open: f64, current_close: f64, prev_open: f64, prev_close: f64) -> bool {
+        current_open.min(current_close) > prev_open.max(prev_close)
+    }
+
+    #[inline(always)]
+    fn candle_average(sum: f64, period: usize) -> f64 {
+        if period == 0 {
+            0.0
+        } else {
+            sum / period as f64
+        }
+    }
+
+    let mut start_idx = lookback_total;
+    let mut body_long_trailing_idx = start_idx - 2 - body_long_period;
+    let mut body_short_trailing_idx = start_idx - 1 - body_short_period;
+
+    let mut i = body_long_trailing_idx;
+    while i < start_idx - 2 {
+        body_long_period_total += real_body(open[i], measure[i]);
+        i += 1;
+    }
+
+    i = body_short_trailing_idx;
+    while i < start_idx - 1 {
+        body_short_period_total += real_body(open[i], measure[i]);
+        i += 1;
+    }
+
+    while start_idx < size {
+        let first = start_idx - 2;
+        let second = start_idx - 1;
+
+        if candle_color(open[first], close[first]) == 1
+            && real_body(open[first], close[first])
+                > candle_average(body_long_period_total, body_long_period)
+            && candle_color(open[second], close[second]) == -1
+            && real_body(open[second], close[second])
+                <= candle_average(body_short_period_total, body_short_period)
+            && gap_up(open[second], close[second], open[first], close[first])
+            && candle_color(open[start_idx], close[start_idx]) == -1
+            && open[start_idx] > open[second]
+            && close[start_idx] < close[second]
+            && close[start_idx] > close[first]
+        {
+            out[start_idx] = -100;
+        }
+
+        body_long_period_total += real_body(open[first], measure[first])
+            - real_body(
+                open[body_long_trailing_idx],
+                measure[body_long_trailing_idx],
+            );
+        body_short_period_total += real_body(open[second], measure[second])
+            - real_body(
+                open[body_short_trailing_idx],
+                measure[body_short_trailing_idx],
+            );
+
+        start_idx += 1;
+        body_long_trailing_idx += 1;
+        body_short_trailing_idx += 1;
+    }
+
+    Ok(PatternOutput { values: out })
+}
+
+/// Harami: a long candle followed by a short candle whose body sits inside the long
+/// body, hinting that the move is stalling. The long and short thresholds are the
+/// `params.body_period` (default 10) average bodies before each candle. Returns
+/// `-100 * color` of the long candle when the small body is strictly inside it, as
+/// TA-Lib 0.4.0 does. Honors `params.trend_filter`.
+#[inline]
+pub fn cdlharami(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    harami_pattern(input, false)
+}
+
+/// Harami cross: a harami whose second candle is a doji, i.e. its body is at most 0.1
+/// times the `params.body_period` (default 10) average high-low range. Scored like
+/// [`cdlharami`] and also honors `params.trend_filter`.
+#[inline]
+pub fn cdlharamicross(input: &PatternInput) -> Result<PatternOutput, PatternError> {
+    harami_pattern(input, true)
+}
+
+#[inline]
+fn harami_pattern(input: &PatternInput, cross: bool) -> Result<PatternOutput, PatternError> {
+    let (open, high, low, close, measure) = match &input.data {
+        PatternData::Candles { candles } => {
+            let open = candles
+                .select_candle_field("open")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let high = candles
+                .select_candle_field("high")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let low = candles
+                .select_candle_field("low")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let close = candles
+                .select_candle_field("close")
+                .map_err(|e| PatternError::CandleFieldError(e.to_string()))?;
+            let measure = measure_close(candles, &input.params)?;
+            (open, high, low, close, measure)
+        }
+    };
+
+    let size = open.len();
+    let body_period = input.params.body_period.unwrap_or(10);
+    let doji_factor = 0.1;
+    let lookback_total = 1 + body_period;
+
+    if size < lookback_total {
+        not_enough_valid_data(
+            input.params.pattern_type.display_name(),
+            lookback_total,
+            size,
+        );
+        return Err(PatternError::NotEnoughData {
+            len: size,
+            pattern: input.params.pattern_type.clone(),
+        });
+    }
+
+    #[inline(always)]
+    fn candle_average(sum: f64, period: usize) -> f64 {
+        if period == 0 {
+            0.0
+        } else {
+            sum / period as f64
+        }
+    }
+
+    let small_body_range = |i: usize| {
+        if cross {
+            high[i] - low[i]
+        } else {
+            real_body(open[i], measure[i])
+        }
+    };
+
+    let mut out = vec![0i8; size];
+    let mut body_long_period_total = 0.0;
+    let mut small_body_period_total = 0.0;
+
+    let mut start_idx = lookback_total;
+    let mut body_long_trailing_idx = start_idx - 1 - body_period;
+    let mut small_body_trailing_idx = start_idx - body_period;
+
+    let mut i = body_long_trailing_idx;
+    while i < start_idx - 1 {
+        body_long_period_total += real_body(open[i], measure[i]);
+        i += 1;
+    }
+
+    i = small_body_trailing_idx;
+    while i < start_idx {
+        small_body_period_total += small_body_range(i);
+        i += 1;
+    }
+
+    while start_idx < size {
+        let first = start_idx - 1;
+        let small_body_limit = if cross {
+            doji_factor * candle_average(small_body_period_total, body_period)
+        } else {
+            candle_average(small_body_period_total, body_period)
+        };
+
+        if real_body(open[first], close[first])
+            > candle_average(body_long_period_total, body_period)
+            && real_body(open[start_idx], close[start_idx]) <= small_body_limit
+            && body_inside(open[start_idx], close[start_idx], open[first], close[first])
+        {
+            out[start_idx] = (-candle_color(open[first], close[first]) * 100) as i8;
+        }
+
+        body_long_period_total += real_body(open[first], measure[first])
+            - real_body(
+                open[body_long_trailing_idx],
+                measure[body_long_trailing_idx],
+            );
+        small_body_period_total +=
+            small_body_range(start_idx) - small_body_range(small_body_trailing_idx);
+
+        start_idx += 1;
+        body_long_trailing_idx += 1;
+        small_body_trailing_idx += 1;
+    }
+
+    apply_trend_filter(&mut out, close, &input.params, 2)?;
+
+    Ok(PatternOutput { values: out })
+}
+
+/// Keeps pattern signals only on bars whose volume is at or above its `vol_period` SMA.
+///
+/// Signals on bars where the volume average is not yet available (warmup), or where it
+/// cannot be computed at all, are zeroed because the volume cannot be confirmed.
+#[inline]
+pub fn confirm_with_volume(
+    output: &PatternOutput,
+    candles: &Candles,
+    vol_period: usize,
+) -> Vec<i8> {
+    let volume = &candles.volume;
+    let sma_input = SmaInput::from_slice(
+        volume,
+        SmaParams {
+            period: Some(vol_period),
+        },
+    );
+    let volume_avg = match sma(&sma_input) {
+        Ok(result) => result.values,
+        Err(_) => return vec![0; output.values.len()],
+    };
+
+    output
+        .values
+        .iter()
+        .enumerate()
+        .map(|(i, &signal)| match (volume.get(i), volume_avg.get(i)) {
+            (Some(&vol), Some(&avg)) if !avg.is_nan() && vol >= avg => signal,
+            _ => 0,
+        })
+        .collect()
+}
+
+/// Sums pattern scores bar by bar, e.g. to build a reversal-strength line from a basket
+/// of patterns. Scores are widened to `i32` so several ±100 hits cannot overflow.
+/// An empty `outputs` slice yields an empty vector.
+#[inline]
+pub fn combine(outputs: &[PatternOutput]) -> Result<Vec<i32>, PatternError> {
+    let len = match outputs.first() {
+        Some(first) => first.values.len(),
+        None => return Ok(Vec::new()),
+    };
+    let mut total = vec![0i32; len];
+    for output in outputs {
+        if output.values.len() != len {
+            return Err(PatternError::LengthMismatch {
+                expected: len,
+                actual: output.values.len(),
+            });
+        }
+        for (sum, &score) in total.iter_mut().zip(&output.values) {
+            *sum += score as i32;
+        }
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::data_loader::read_candles_from_csv;
+
+    fn fired_indices(values: &[i8]) -> Vec<usize> {
+        values
+            .iter()
+            .enumerate()
+            .filter(|(_, &v)| v != 0)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    #[test]
+    fn test_cdlmorningstar_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlMorningStar);
+        let result = cdlmorningstar(&input).expect("Failed to calculate CDLMORNINGSTAR");
+        assert_eq!(result.values.len(), candles.close.len());
+
+        let fired = fired_indices(&result.values);
+        assert_eq!(fired.len(), 41, "Unexpected number of morning star signals");
+        assert_eq!(&fired[..6], &[14, 272, 660, 1066, 1454, 2231]);
+        assert_eq!(&fired[fired.len() - 3..], &[14668, 15148, 15311]);
+        assert!(fired.iter().all(|&i| result.values[i] == 100));
+    }
+
+    #[test]
+    fn test_cdleveningstar_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlEveningStar);
+        let result = cdleveningstar(&input).expect("Failed to calculate CDLEVENINGSTAR");
+        assert_eq!(result.values.len(), candles.close.len());
+
+        let fired = fired_indices(&result.values);
+        assert_eq!(fired.len(), 38, "Unexpected number of evening star signals");
+        assert_eq!(&fired[..6], &[285, 772, 879, 1069, 1707, 2075]);
+        assert_eq!(&fired[fired.len() - 3..], &[14325, 14682, 15295]);
+        assert!(fired.iter().all(|&i| result.values[i] == -100));
+    }
+
+    #[test]
+    fn test_star_patterns_not_enough_data() {
+        let candles = Candles::new(
+            vec![1, 2, 3],
+            vec![1.0, 2.0, 3.0],
+            vec![1.5, 2.5, 3.5],
+            vec![0.5, 1.5, 2.5],
+            vec![1.2, 2.2, 3.2],
+            vec![10.0, 10.0, 10.0],
+        );
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlMorningStar);
+        assert!(cdlmorningstar(&input).is_err());
+        assert!(cdleveningstar(&input).is_err());
+    }
+
+    #[test]
+    fn test_star_patterns_reject_invalid_penetration() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        for penetration in [-0.1, f64::NAN, f64::INFINITY] {
+            let params = PatternParams {
+                pattern_type: PatternType::CdlMorningStar,
+                penetration,
+                ..Default::default()
+            };
+            let input = PatternInput::from_candles(&candles, params);
+            assert!(matches!(
+                cdlmorningstar(&input),
+                Err(PatternError::InvalidPenetration { .. })
+            ));
+            assert!(matches!(
+                cdleveningstar(&input),
+                Err(PatternError::InvalidPenetration { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_confirm_with_volume_suppresses_low_volume_hits() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlEngulfing);
+        let result = cdlengulfing(&input).expect("Failed to calculate CDLENGULFING");
+
+        assert_eq!(result.values[20], 100);
+        assert_eq!(result.values[41], 100);
+
+        let confirmed = confirm_with_volume(&result, &candles, 20);
+        assert_eq!(confirmed.len(), result.values.len());
+        assert_eq!(
+            confirmed[20], 0,
+            "Low-volume engulfing should be suppressed"
+        );
+        assert_eq!(confirmed[41], 100, "High-volume engulfing should be kept");
+        assert!(confirmed[..19].iter().all(|&v| v == 0));
+    }
+
+    fn stars_in_south_candles(at: &[usize], len: usize) -> Candles {
+        let mut open = vec![100.0; len];
+        let mut high = vec![101.5; len];
+        let mut low = vec![99.5; len];
+        let mut close = vec![101.0; len];
+        for &k in at {
+            for (j, (o, h, l, c)) in [
+                (110.0, 110.5, 99.0, 105.0),
+                (107.0, 107.5, 101.0, 104.0),
+                (103.5, 103.55, 102.95, 103.0),
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let idx = k + j - 2;
+                open[idx] = o;
+                high[idx] = h;
+                low[idx] = l;
+                close[idx] = c;
+            }
+        }
+        Candles::new(
+            (0..len as i64).collect(),
+            open,
+            high,
+            low,
+            close,
+            vec![1.0; len],
+        )
+    }
+
+    #[test]
+    fn test_cdl3starsinsouth_warmup() {
+        let candles = stars_in_south_candles(&[12, 40], 50);
+        let input = PatternInput::with_default_candles(&candles, PatternType::Cdl3StarsInSouth);
+        let result = cdl3starsinsouth(&input).expect("Failed to calculate CDL3STARSINSOUTH");
+        assert_eq!(fired_indices(&result.values), vec![12, 40]);
+        assert!(result.values.iter().all(|&v| v == 0 || v == 100));
+    }
+
+    #[test]
+    fn test_cdl3starsinsouth_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::Cdl3StarsInSouth);
+        let result = cdl3starsinsouth(&input).expect("Failed to calculate CDL3STARSINSOUTH");
+        assert_eq!(result.values.len(), candles.close.len());
+        assert!(
+            fired_indices(&result.values).is_empty(),
+            "TA-Lib finds no three stars in the south on this file"
+        );
+    }
+
+    fn neck_candles(last_close: f64) -> Candles {
+        let mut open = vec![100.0; 10];
+        let mut high = vec![101.5; 10];
+        let mut low = vec![99.5; 10];
+        let mut close = vec![101.0; 10];
+        open.extend_from_slice(&[110.0, 99.0]);
+        high.extend_from_slice(&[110.5, last_close.max(99.0) + 0.5]);
+        low.extend_from_slice(&[99.5, 98.5]);
+        close.extend_from_slice(&[100.0, last_close]);
+        Candles::new((0..12).collect(), open, high, low, close, vec![1.0; 12])
+    }
+
+    fn two_crows_candles(baseline_high: f64) -> Candles {
+        let mut open = vec![100.0; 12];
+        let mut high = vec![baseline_high; 12];
+        let mut low = vec![99.5; 12];
+        let mut close = vec![101.0; 12];
+        open.extend_from_slice(&[100.0, 106.0, 105.5]);
+        high.extend_from_slice(&[104.5, 106.5, 106.0]);
+        low.extend_from_slice(&[99.5, 104.5, 101.5]);
+        close.extend_from_slice(&[104.0, 105.0, 102.0]);
+        Candles::new((0..15).collect(), open, high, low, close, vec![1.0; 15])
+    }
+
+    #[test]
+    fn test_cdl2crows_measure_source() {
+        let run = |candles: &Candles, measure_source: Option<&str>| {
+            let params = PatternParams {
+                pattern_type: PatternType::Cdl2Crows,
+                measure_source: measure_source.map(String::from),
+                ..Default::default()
+            };
+            cdl2crows(&PatternInput::from_candles(candles, params)).map(|r| r.values)
+        };
+
+        let synthetic = two_crows_candles(112.0);
+        let default = run(&synthetic, None).expect("Failed to calculate CDL2CROWS");
+        assert_eq!(fired_indices(&default), vec![14]);
+        assert_eq!(default[14], -100);
+        assert_eq!(run(&synthetic, Some("close")).unwrap(), default);
+        assert!(
+            fired_indices(&run(&synthetic, Some("hlc3")).unwrap()).is_empty(),
+            "Bodies measured off hlc3 are longer, so the white candle is no longer long"
+        );
+        assert!(matches!(
+            run(&synthetic, Some("bogus")),
+            Err(PatternError::CandleFieldError(_))
+        ));
+    }
+
+    #[test]
+    fn test_measure_source_applies_to_all_patterns() {
+        type PatternFn = fn(&PatternInput) -> Result<PatternOutput, PatternError>;
+        let patterns: [(PatternType, PatternFn); 36] = [
+            (PatternType::Cdl2Crows, cdl2crows),
+            (PatternType::Cdl3BlackCrows, cdl3blackcrows),
+            (PatternType::Cdl3Inside, cdl3inside),
+            (PatternType::Cdl3LineStrike, cdl3linestrike),
+            (PatternType::Cdl3Outside, cdl3outside),
+            (PatternType::Cdl3StarsInSouth, cdl3starsinsouth),
+            (PatternType::Cdl3WhiteSoldiers, cdl3whitesoldiers),
+            (PatternType::CdlAbandonedBaby, cdlabandonedbaby),
+            (PatternType::CdlAdvanceBlock, cdladvanceblock),
+            (PatternType::CdlBeltHold, cdlbelthold),
+            (PatternType::CdlBreakaway, cdlbreakaway),
+            (PatternType::CdlClosingMarubozu, cdlclosingmarubozu),
+            (PatternType::CdlConcealBabySwall, cdlconcealbabyswall),
+            (PatternType::CdlCounterAttack, cdlcounterattack),
+            (PatternType::CdlDarkCloudCover, cdldarkcloudcover),
+            (PatternType::CdlDoji, cdldoji),
+            (PatternType::CdlDojiStar, cdldojistar),
+            (PatternType::CdlDragonflyDoji, cdldragonflydoji),
+            (PatternType::CdlEngulfing, cdlengulfing),
+            (PatternType::CdlEveningDojiStar, cdleveningdojistar),
+            (PatternType::CdlEveningStar, cdleveningstar),
+            (PatternType::CdlMorningStar, cdlmorningstar),
+            (PatternType::CdlPiercing, cdlpiercing),
+            (PatternType::CdlOnNeck, cdlonneck),
+            (PatternType::CdlInNeck, cdlinneck),
+            (PatternType::CdlThrusting, cdlthrusting),
+            (PatternType::CdlHammer, cdlhammer),
+            (PatternType::CdlHangingMan, cdlhangingman),
+            (PatternType::CdlInvertedHammer, cdlinvertedhammer),
+            (PatternType::CdlShootingStar, cdlshootingstar),
+            (PatternType::CdlMarubozu, cdlmarubozu),
+            (PatternType::CdlSpinningTop, cdlspinningtop),
+            (PatternType::CdlTasukiGap, cdltasukigap),
+            (PatternType::CdlUpsideGap2Crows, cdlupsidegap2crows),
+            (PatternType::CdlHarami, cdlharami),
+            (PatternType::CdlHaramiCross, cdlharamicross),
+        ];
+        let body_independent = [
+            PatternType::Cdl3BlackCrows.display_name(),
+            PatternType::Cdl3Outside.display_name(),
+            PatternType::CdlConcealBabySwall.display_name(),
+            PatternType::CdlEngulfing.display_name(),
+            PatternType::CdlTasukiGap.display_name(),
+        ];
+
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let mut changed = Vec::new();
+        for (pattern_type, run) in patterns {
+            let with_source = |measure_source: Option<&str>| {
+                let params = PatternParams {
+                    pattern_type: pattern_type.clone(),
+                    measure_source: measure_source.map(String::from),
+                    ..Default::default()
+                };
+                run(&PatternInput::from_candles(&candles, params))
+                    .expect("Failed to calculate pattern")
+                    .values
+            };
+            let default = with_source(None);
+            assert_eq!(
+                with_source(Some("close")),
+                default,
+                "{pattern_type:?}: measuring off close should reproduce the default"
+            );
+            let name = pattern_type.display_name();
+            if with_source(Some("hlc3")) != default {
+                changed.push(name);
+            }
+        }
+        assert!(changed.iter().all(|p| !body_independent.contains(p)));
+        assert!(
+            changed.len() >= 15,
+            "Only {changed:?} reacted to measure_source"
+        );
+    }
+
+    fn counterattack_candles() -> Candles {
+        let mut open = vec![100.0_f64; 22];
+        let mut close = vec![100.1; 10];
+        close.extend_from_slice(&[102.0; 10]);
+        open[20] = 110.0;
+        close.push(100.0);
+        open[21] = 88.0;
+        close.push(102.0);
+        let high = open
+            .iter()
+            .zip(&close)
+            .map(|(o, c)| o.max(*c) + 0.5)
+            .collect();
+        let low = open
+            .iter()
+            .zip(&close)
+            .map(|(o, c)| o.min(*c) - 0.5)
+            .collect();
+        Candles::new((0..22).collect(), open, high, low, close, vec![1.0; 22])
+    }
+
+    #[test]
+    fn test_cdlcounterattack_equal_period() {
+        let candles = counterattack_candles();
+        let run = |equal_period: Option<usize>| {
+            let params = PatternParams {
+                pattern_type: PatternType::CdlCounterAttack,
+                equal_period,
+                ..Default::default()
+            };
+            cdlcounterattack(&PatternInput::from_candles(&candles, params))
+                .expect("Failed to calculate CDLCOUNTERATTACK")
+                .values
+        };
+
+        assert_eq!(
+            run(None)[21],
+            100,
+            "Closes 2.0 apart are equal over 10 bars"
+        );
+        assert_eq!(run(Some(10)), run(None));
+        assert_eq!(
+            run(Some(20))[21],
+            0,
+            "Including the small early bodies tightens the tolerance"
+        );
+        assert_eq!(run(Some(0))[21], 0, "Zero period requires identical closes");
+    }
+
+    #[test]
+    fn test_cdlpiercing_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlPiercing);
+        let result = cdlpiercing(&input).expect("Failed to calculate CDLPIERCING");
+        assert_eq!(result.values.len(), candles.close.len());
+        assert!(
+            fired_indices(&result.values).is_empty(),
+            "Continuous crypto bars almost never gap below the prior low"
+        );
+
+        let candles = neck_candles(106.0);
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlPiercing);
+        let result = cdlpiercing(&input).expect("Failed to calculate CDLPIERCING");
+        assert_eq!(fired_indices(&result.values), vec![11]);
+        assert_eq!(result.values[11], 100);
+
+        let params = PatternParams {
+            pattern_type: PatternType::CdlPiercing,
+            penetration: 0.7,
+            ..Default::default()
+        };
+        let result = cdlpiercing(&PatternInput::from_candles(&candles, params))
+            .expect("Failed to calculate CDLPIERCING");
+        assert!(fired_indices(&result.values).is_empty());
+
+        let params = PatternParams {
+            pattern_type: PatternType::CdlPiercing,
+            penetration: -0.5,
+            ..Default::default()
+        };
+        assert!(matches!(
+            cdlpiercing(&PatternInput::from_candles(&candles, params)),
+            Err(PatternError::InvalidPenetration { .. })
+        ));
+    }
+
+    #[test]
+    fn test_neck_patterns() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlThrusting);
+        let result = cdlthrusting(&input).expect("Failed to calculate CDLTHRUSTING");
+        assert_eq!(
+            fired_indices(&result.values),
+            vec![8880, 12204, 12960, 13336]
+        );
+        assert!(fired_indices(&cdlonneck(&input).unwrap().values).is_empty());
+        assert!(fired_indices(&cdlinneck(&input).unwrap().values).is_empty());
+
+        let cases: [(f64, [i8; 3]); 3] = [
+            (99.5, [-100, 0, 0]),
+            (100.05, [0, -100, 0]),
+            (103.0, [0, 0, -100]),
+        ];
+        for (last_close, expected) in cases {
+            let candles = neck_candles(last_close);
+            let input = PatternInput::with_default_candles(&candles, PatternType::CdlOnNeck);
+            let on = cdlonneck(&input).expect("Failed to calculate CDLONNECK");
+            let inn = cdlinneck(&input).expect("Failed to calculate CDLINNECK");
+            let thr = cdlthrusting(&input).expect("Failed to calculate CDLTHRUSTING");
+            assert_eq!(
+                [on.values[11], inn.values[11], thr.values[11]],
+                expected,
+                "Unexpected neck signals for close {}",
+                last_close
+            );
+            assert!(on.values[..11].iter().all(|&v| v == 0));
+        }
+
+        let short = Candles::new(
+            vec![1, 2],
+            vec![1.0, 2.0],
+            vec![1.5, 2.5],
+            vec![0.5, 1.5],
+            vec![1.2, 2.2],
+            vec![10.0, 10.0],
+        );
+        let input = PatternInput::with_default_candles(&short, PatternType::CdlInNeck);
+        assert!(cdlinneck(&input).is_err());
+        assert!(cdlpiercing(&input).is_err());
+    }
+
+    #[test]
+    fn test_cdl3outside_trend_filter() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::Cdl3Outside);
+        let unfiltered = cdl3outside(&input).expect("Failed to calculate CDL3OUTSIDE");
+
+        let params = PatternParams {
+            pattern_type: PatternType::Cdl3Outside,
+            trend_filter: Some(TrendFilter { ma_period: 20 }),
+            ..Default::default()
+        };
+        let filtered = cdl3outside(&PatternInput::from_candles(&candles, params))
+            .expect("Failed to calculate filtered CDL3OUTSIDE");
+
+        let all_hits = fired_indices(&unfiltered.values);
+        let kept = fired_indices(&filtered.values);
+        assert!(!kept.is_empty());
+        assert!(kept.len() < all_hits.len());
+
+        let trend = sma(&SmaInput::from_slice(
+            &candles.close,
+            SmaParams { period: Some(20) },
+        ))
+        .unwrap()
+        .values;
+        for &i in &kept {
+            assert_eq!(filtered.values[i], unfiltered.values[i]);
+            let r = i - 3;
+            if filtered.values[i] > 0 {
+                assert!(
+                    candles.close[r] < trend[r],
+                    "Bullish hit at {} not after a downtrend",
+                    i
+                );
+            } else {
+                assert!(
+                    candles.close[r] > trend[r],
+                    "Bearish hit at {} not after an uptrend",
+                    i
+                );
+            }
+        }
+
+        let params = PatternParams {
+            pattern_type: PatternType::CdlEngulfing,
+            trend_filter: Some(TrendFilter { ma_period: 0 }),
+            ..Default::default()
+        };
+        assert!(matches!(
+            cdlengulfing(&PatternInput::from_candles(&candles, params)),
+            Err(PatternError::InvalidTrendFilter { .. })
+        ));
+    }
+
+    #[test]
+    fn test_morning_star_trend_filter() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let params = PatternParams {
+            pattern_type: PatternType::CdlMorningStar,
+            trend_filter: Some(TrendFilter { ma_period: 20 }),
+            ..Default::default()
+        };
+        let filtered = cdlmorningstar(&PatternInput::from_candles(&candles, params))
+            .expect("Failed to calculate filtered CDLMORNINGSTAR");
+        let unfiltered = cdlmorningstar(&PatternInput::with_default_candles(
+            &candles,
+            PatternType::CdlMorningStar,
+        ))
+        .unwrap();
+
+        assert_eq!(unfiltered.values[272], 100);
+        assert_eq!(
+            filtered.values[272], 0,
+            "Morning star in an uptrend should be suppressed"
+        );
+        assert_eq!(unfiltered.values[660], 100);
+        assert_eq!(
+            filtered.values[660], 100,
+            "Morning star after a downtrend should survive"
+        );
+        assert_eq!(
+            filtered.values[14], 0,
+            "No trend is known before the SMA warms up"
+        );
+    }
+
+    #[test]
+    fn test_cdlhammer_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlHammer);
+        let result = cdlhammer(&input).expect("Failed to calculate CDLHAMMER");
+        assert_eq!(result.values.len(), candles.close.len());
+
+        // Expected hits from TA-Lib 0.4.0 `TA_CDLHAMMER` on the same candles.
+        let hits = fired_indices(&result.values);
+        assert_eq!(hits.len(), 490);
+        assert_eq!(
+            hits[..12],
+            [88, 148, 181, 201, 211, 299, 388, 435, 439, 479, 508, 541]
+        );
+        assert_eq!(hits[hits.len() - 4..], [15394, 15402, 15516, 15572]);
+        assert!(hits.iter().all(|&i| result.values[i] == 100));
+        assert!(result.values[..11].iter().all(|&v| v == 0));
+    }
+
+    #[test]
+    fn test_hammer_family() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlHangingMan);
+
+        // Expected hits from TA-Lib 0.4.0 `TA_CDLHANGINGMAN`, `TA_CDLINVERTEDHAMMER` and
+        // `TA_CDLSHOOTINGSTAR` on the same candles.
+        let hanging = cdlhangingman(&input).expect("Failed to calculate CDLHANGINGMAN");
+        let hits = fired_indices(&hanging.values);
+        assert_eq!(hits.len(), 203);
+        assert_eq!(hits[..4], [207, 208, 229, 260]);
+        assert!(hits.iter().all(|&i| hanging.values[i] == -100));
+
+        let inverted = cdlinvertedhammer(&input).expect("Failed to calculate CDLINVERTEDHAMMER");
+        let hits = fired_indices(&inverted.values);
+        assert_eq!(hits.len(), 49);
+        assert_eq!(hits[..4], [526, 1334, 2023, 2026]);
+        assert!(hits.iter().all(|&i| inverted.values[i] == 100));
+
+        let shooting = cdlshootingstar(&input).expect("Failed to calculate CDLSHOOTINGSTAR");
+        let hits = fired_indices(&shooting.values);
+        assert_eq!(hits.len(), 121);
+        assert_eq!(hits[..4], [748, 786, 952, 1084]);
+        assert!(hits.iter().all(|&i| shooting.values[i] == -100));
+
+        let params = PatternParams {
+            pattern_type: PatternType::CdlHammer,
+            trend_filter: Some(TrendFilter { ma_period: 20 }),
+            ..Default::default()
+        };
+        let filtered = cdlhammer(&PatternInput::from_candles(&candles, params))
+            .expect("Failed to calculate filtered CDLHAMMER");
+        let kept = fired_indices(&filtered.values);
+        assert!(!kept.is_empty() && kept.len() < 490);
+
+        let short = Candles::new(
+            vec![1, 2],
+            vec![1.0, 2.0],
+            vec![1.5, 2.5],
+            vec![0.5, 1.5],
+            vec![1.2, 2.2],
+            vec![10.0, 10.0],
+        );
+        let input = PatternInput::with_default_candles(&short, PatternType::CdlHammer);
+        assert!(matches!(
+            cdlhammer(&input),
+            Err(PatternError::NotEnoughData { len: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_hammer_family_periods() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let run = |pattern_type: PatternType, params: PatternParams| {
+            let params = PatternParams {
+                pattern_type,
+                ..params
+            };
+            let input = PatternInput::from_candles(&candles, params);
+            let values = match input.params.pattern_type {
+                PatternType::CdlShootingStar => cdlshootingstar(&input),
+                _ => cdlhammer(&input),
+            };
+            fired_indices(&values.expect("Failed to calculate hammer family").values)
+        };
+
+        // Expected hits from TA-Lib 0.4.0 with the matching `TA_SetCandleSettings` period
+        // (BodyShort, ShadowVeryShort or Near) and its default range type and factor.
+        let hits = run(
+            PatternType::CdlHammer,
+            PatternParams {
+                body_short_period: Some(5),
+                ..Default::default()
+            },
+        );
+        assert_eq!(hits.len(), 458);
+        assert_eq!(hits[hits.len() - 3..], [15402, 15516, 15572]);
+
+        let hits = run(
+            PatternType::CdlHammer,
+            PatternParams {
+                shadow_very_short_period: Some(4),
+                ..Default::default()
+            },
+        );
+        assert_eq!(hits.len(), 456);
+        assert_eq!(hits[..6], [88, 148, 181, 201, 211, 245]);
+        assert_eq!(hits[hits.len() - 3..], [15496, 15516, 15572]);
+
+        let hits = run(
+            PatternType::CdlHammer,
+            PatternParams {
+                near_period: Some(12),
+                ..Default::default()
+            },
+        );
+        assert_eq!(hits.len(), 513);
+        assert_eq!(hits[..6], [84, 88, 148, 201, 211, 299]);
+        assert_eq!(hits[hits.len() - 3..], [15491, 15516, 15572]);
+
+        let hits = run(
+            PatternType::CdlShootingStar,
+            PatternParams {
+                body_short_period: Some(5),
+                ..Default::default()
+            },
+        );
+        assert_eq!(hits.len(), 115);
+        assert_eq!(hits[hits.len() - 3..], [15061, 15373, 15505]);
+    }
+
+    #[test]
+    fn test_combine_pattern_outputs() {
+        let first = PatternOutput {
+            values: vec![0, 100, -100, 0],
+        };
+        let second = PatternOutput {
+            values: vec![0, 80, 0, 100],
+        };
+        let combined = combine(&[first.clone(), second]).expect("combine");
+        assert_eq!(combined, vec![0, 180, -100, 100]);
+
+        let short = PatternOutput { values: vec![100] };
+        assert!(matches!(
+            combine(&[first, short]),
+            Err(PatternError::LengthMismatch {
+                expected: 4,
+                actual: 1
+            })
+        ));
+        assert!(combine(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cdlmarubozu_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlMarubozu);
+        let result = cdlmarubozu(&input).expect("Failed to calculate CDLMARUBOZU");
+        assert_eq!(result.values.len(), candles.close.len());
+
+        // Expected hits from TA-Lib 0.4.0 `TA_CDLMARUBOZU` on the same candles.
+        let hits = fired_indices(&result.values);
+        assert_eq!(hits.len(), 416);
+        assert_eq!(hits.iter().filter(|&&i| result.values[i] > 0).count(), 250);
+        assert_eq!(hits[..6], [46, 81, 103, 131, 210, 228]);
+        assert_eq!(result.values[210], -100);
+        assert_eq!(result.values[228], 100);
+        assert_eq!(hits[hits.len() - 3..], [15442, 15484, 15553]);
+    }
+
+    #[test]
+    fn test_cdlspinningtop_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlSpinningTop);
+        let result = cdlspinningtop(&input).expect("Failed to calculate CDLSPINNINGTOP");
+        assert_eq!(result.values.len(), candles.close.len());
+
+        // Expected hits from TA-Lib 0.4.0 `TA_CDLSPINNINGTOP` on the same candles.
+        let hits = fired_indices(&result.values);
+        assert_eq!(hits.len(), 3987);
+        assert_eq!(hits.iter().filter(|&&i| result.values[i] > 0).count(), 1980);
+        assert_eq!(hits[..6], [13, 19, 28, 31, 36, 38]);
+        assert_eq!(result.values[13], -100);
+        assert_eq!(result.values[28], 100);
+        assert_eq!(hits[hits.len() - 3..], [15563, 15565, 15572]);
+        assert!(result.values[..10].iter().all(|&v| v == 0));
+    }
+
+    #[test]
+    fn test_marubozu_and_spinning_top_periods() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = |pattern_type: PatternType, params: PatternParams| {
+            PatternInput::from_candles(
+                &candles,
+                PatternParams {
+                    pattern_type,
+                    ..params
+                },
+            )
+        };
+
+        // Expected hits from TA-Lib 0.4.0 with the matching `TA_SetCandleSettings` period
+        // (BodyLong, ShadowVeryShort or BodyShort) and its default range type and factor.
+        let result = cdlmarubozu(&input(
+            PatternType::CdlMarubozu,
+            PatternParams {
+                body_long_period: Some(6),
+                ..Default::default()
+            },
+        ))
+        .expect("Failed to calculate CDLMARUBOZU");
+        let hits = fired_indices(&result.values);
+        assert_eq!(hits.len(), 477);
+        assert_eq!(hits[hits.len() - 3..], [15485, 15535, 15553]);
+
+        let result = cdlmarubozu(&input(
+            PatternType::CdlMarubozu,
+            PatternParams {
+                shadow_very_short_period: Some(4),
+                ..Default::default()
+            },
+        ))
+        .expect("Failed to calculate CDLMARUBOZU");
+        let hits = fired_indices(&result.values);
+        assert_eq!(hits.len(), 402);
+        assert_eq!(hits[..6], [46, 228, 246, 250, 253, 288]);
+        assert_eq!(hits[hits.len() - 3..], [15315, 15442, 15553]);
+
+        let result = cdlspinningtop(&input(
+            PatternType::CdlSpinningTop,
+            PatternParams {
+                body_short_period: Some(5),
+                ..Default::default()
+            },
+        ))
+        .expect("Failed to calculate CDLSPINNINGTOP");
+        let hits = fired_indices(&result.values);
+        assert_eq!(hits.len(), 3939);
+        assert_eq!(hits[..6], [9, 13, 19, 28, 31, 36]);
+        assert!(result.values[..5].iter().all(|&v| v == 0));
+    }
+
+    #[test]
+    fn test_cdltasukigap_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlTasukiGap);
+        let result = cdltasukigap(&input).expect("Failed to calculate CDLTASUKIGAP");
+        assert_eq!(result.values.len(), candles.close.len());
+
+        // Expected hits from TA-Lib 0.4.0 `TA_CDLTASUKIGAP` on the same candles.
+        assert_eq!(fired_indices(&result.values), vec![4617, 9557, 13776]);
+        assert_eq!(result.values[4617], -100);
+        assert_eq!(result.values[9557], 100);
+        assert_eq!(result.values[13776], 100);
+    }
+
+    fn hits(values: &[i8]) -> Vec<(usize, i8)> {
+        fired_indices(values)
+            .into_iter()
+            .map(|i| (i, values[i]))
+            .collect()
+    }
+
+    #[test]
+    fn test_cdlharami_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlHarami);
+        let result = cdlharami(&input).expect("Failed to calculate CDLHARAMI");
+        assert_eq!(result.values.len(), candles.close.len());
+
+        // Expected hits from TA-Lib 0.4.0 `TA_CDLHARAMI` on the same candles.
+        let hits = hits(&result.values);
+        assert_eq!(hits.len(), 593);
+        assert!(hits.iter().all(|(_, v)| v.abs() == 100));
+        assert_eq!(
+            hits[..6],
+            [
+                (28, 100),
+                (38, 100),
+                (40, -100),
+                (45, -100),
+                (54, 100),
+                (58, 100)
+            ]
+        );
+        assert_eq!(
+            hits[hits.len() - 4..],
+            [(15485, -100), (15495, -100), (15509, -100), (15515, -100)]
+        );
+    }
+
+    #[test]
+    fn test_cdlharamicross_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlHaramiCross);
+        let result = cdlharamicross(&input).expect("Failed to calculate CDLHARAMICROSS");
+        assert_eq!(result.values.len(), candles.close.len());
+
+        // Expected hits from TA-Lib 0.4.0 `TA_CDLHARAMICROSS` on the same candles.
+        let hits = hits(&result.values);
+        assert_eq!(hits.len(), 168);
+        assert!(hits.iter().all(|(_, v)| v.abs() == 100));
+        assert_eq!(
+            hits[..6],
+            [
+                (40, -100),
+                (45, -100),
+                (54, 100),
+                (58, 100),
+                (275, 100),
+                (307, 100)
+            ]
+        );
+        assert_eq!(
+            hits[hits.len() - 4..],
+            [(14976, -100), (15089, -100), (15242, 100), (15436, -100)]
+        );
+
+        let harami = cdlharami(&input).expect("Failed to calculate CDLHARAMI");
+        for (i, v) in hits {
+            assert_eq!(harami.values[i], v, "Harami cross at {i} is not a harami");
+        }
+    }
+
+    #[test]
+    fn test_cdlharami_body_period() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let default = cdlharami(&PatternInput::with_default_candles(
+            &candles,
+            PatternType::CdlHarami,
+        ))
+        .expect("Failed to calculate CDLHARAMI");
+        let explicit = cdlharami(&PatternInput::from_candles(
+            &candles,
+            PatternParams {
+                pattern_type: PatternType::CdlHarami,
+                body_period: Some(10),
+                ..Default::default()
+            },
+        ))
+        .expect("Failed to calculate CDLHARAMI");
+        assert_eq!(explicit.values, default.values);
+
+        let short = cdlharami(&PatternInput::from_candles(
+            &candles,
+            PatternParams {
+                pattern_type: PatternType::CdlHarami,
+                body_period: Some(5),
+                ..Default::default()
+            },
+        ))
+        .expect("Failed to calculate CDLHARAMI");
+        assert_ne!(short.values, default.values);
+
+        let tiny = Candles::new(
+            vec![0; 5],
+            vec![1.0; 5],
+            vec![2.0; 5],
+            vec![0.5; 5],
+            vec![1.5; 5],
+            vec![1.0; 5],
+        );
+        assert!(matches!(
+            cdlharamicross(&PatternInput::with_default_candles(
+                &tiny,
+                PatternType::CdlHaramiCross
+            )),
+            Err(PatternError::NotEnoughData { len: 5, .. })
+        ));
+
+        let exact = Candles::new(
+            vec![0; 11],
+            vec![1.0; 11],
+            vec![2.0; 11],
+            vec![0.5; 11],
+            vec![1.5; 11],
+            vec![1.0; 11],
+        );
+        let result = cdlharami(&PatternInput::with_default_candles(
+            &exact,
+            PatternType::CdlHarami,
+        ))
+        .expect("Input of exactly the lookback length should be accepted");
+        assert!(result.values.iter().all(|&v| v == 0));
+    }
+
+    fn upside_gap_two_crows_candles(at: &[usize], len: usize) -> Candles {
+        let mut open = vec![100.0_f64; len];
+        let mut close = vec![101.0; len];
+        for &k in at {
+            for (j, (o, c)) in [(100.0, 110.0), (112.0, 111.5), (112.5, 111.0)]
+                .into_iter()
+                .enumerate()
+            {
+                open[k + j - 2] = o;
+                close[k + j - 2] = c;
+            }
+        }
+        let high = open
+            .iter()
+            .zip(&close)
+            .map(|(o, c)| o.max(*c) + 0.5)
+            .collect();
+        let low = open
+            .iter()
+            .zip(&close)
+            .map(|(o, c)| o.min(*c) - 0.5)
+            .collect();
+        Candles::new(
+            (0..len as i64).collect(),
+            open,
+            high,
+            low,
+            close,
+            vec![1.0; len],
+        )
+    }
+
+    #[test]
+    fn test_cdlupsidegap2crows_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlUpsideGap2Crows);
+        let result = cdlupsidegap2crows(&input).expect("Failed to calculate CDLUPSIDEGAP2CROWS");
+        assert_eq!(result.values.len(), candles.close.len());
+        assert!(
+            fired_indices(&result.values).is_empty(),
+            "TA-Lib finds no upside gap two crows on this file"
+        );
+
+        let synthetic = upside_gap_two_crows_candles(&[14, 40], 50);
+        let input = PatternInput::with_default_candles(&synthetic, PatternType::CdlUpsideGap2Crows);
+        let result = cdlupsidegap2crows(&input).expect("Failed to calculate CDLUPSIDEGAP2CROWS");
+        assert_eq!(fired_indices(&result.values), vec![14, 40]);
+        assert!(result.values.iter().all(|&v| v == 0 || v == -100));
+    }
+
+    #[test]
+    fn test_gap_pattern_periods() {
+        // Upside tasuki gap at 20 whose bodies differ by 0.4, against a 1.5 bar range.
+        // The 20-wide range at bar 10 only enters a 12-bar "near" average.
+        let mut open = vec![100.0; 30];
+        let mut high = vec![101.0; 30];
+        let mut low = vec![99.5; 30];
+        let mut close = vec![100.5; 30];
+        for (k, o, h, l, c) in [
+            (10, 100.0, 110.0, 90.0, 100.5),
+            (18, 100.0, 101.2, 99.8, 101.0),
+            (19, 102.0, 104.2, 101.9, 104.0),
+            (20, 103.5, 103.6, 101.8, 101.9),
+        ] {
+            (open[k], high[k], low[k], close[k]) = (o, h, l, c);
+        }
+        let tasuki = Candles::new((0..30).collect(), open, high, low, close, vec![1.0; 30]);
+        let run = |near_period: Option<usize>| {
+            let params = PatternParams {
+                pattern_type: PatternType::CdlTasukiGap,
+                near_period,
+                ..Default::default()
+            };
+            cdltasukigap(&PatternInput::from_candles(&tasuki, params))
+                .map(|r| hits(&r.values))
+                .expect("Failed to calculate CDLTASUKIGAP")
+        };
+        // Expected hits from TA-Lib 0.4.0 `TA_CDLTASUKIGAP` with the Near period set.
+        assert!(run(None).is_empty());
+        assert_eq!(run(Some(12)), vec![(20, 100)]);
+
+        // A 120-point body at bar 5 inflates the default 10-bar long-body average seen by
+        // the white candle at 12, but drops out of a 5-bar average.
+        let mut crows = upside_gap_two_crows_candles(&[14, 40], 50);
+        crows.close[5] = 220.0;
+        crows.high[5] = 220.5;
+        let run = |body_long_period: Option<usize>| {
+            let params = PatternParams {
+                pattern_type: PatternType::CdlUpsideGap2Crows,
+                body_long_period,
+                ..Default::default()
+            };
+            cdlupsidegap2crows(&PatternInput::from_candles(&crows, params))
+                .map(|r| hits(&r.values))
+                .expect("Failed to calculate CDLUPSIDEGAP2CROWS")
+        };
+        // Expected hits from TA-Lib 0.4.0 `TA_CDLUPSIDEGAP2CROWS` with the BodyLong period
+        // set.
+        assert_eq!(run(None), vec![(40, -100)]);
+        assert_eq!(run(Some(5)), vec![(14, -100), (40, -100)]);
+    }
+
+    #[test]
+    fn test_gap_patterns_not_enough_data() {
+        let candles = upside_gap_two_crows_candles(&[], 6);
+        let input = PatternInput::with_default_candles(&candles, PatternType::CdlTasukiGap);
+        assert!(matches!(
+            cdltasukigap(&input),
+            Err(PatternError::NotEnoughData { len: 6, .. })
+        ));
+        assert!(matches!(
+            cdlupsidegap2crows(&input),
+            Err(PatternError::NotEnoughData { len: 6, .. })
+        ));
+    }
+
+    #[test]
+    fn test_pattern_type_display_name_and_category() {
+        assert_eq!(PatternType::Cdl2Crows.display_name(), "Two Crows");
+        assert_eq!(
+            PatternType::Cdl3WhiteSoldiers.category(),
+            PatternCategory::BullishReversal
+        );
+        assert_eq!(
+            PatternType::CdlEveningStar.category(),
+            PatternCategory::BearishReversal
+        );
+        assert_eq!(
+            PatternType::CdlEngulfing.category(),
+            PatternCategory::BullishReversal
+        );
+        assert_eq!(
+            PatternType::CdlRiseFall3Methods.category(),
+            PatternCategory::Continuation
+        );
+        assert_eq!(PatternType::CdlDoji.category(), PatternCategory::Indecision);
+        for pattern in [
+            PatternType::CdlMarubozu,
+            PatternType::CdlLongLine,
+            PatternType::CdlClosingMarubozu,
+        ] {
+            assert_eq!(pattern.category(), PatternCategory::Continuation);
+            assert_eq!(pattern.category_for(-100), PatternCategory::Continuation);
+        }
+
+        assert_eq!(
+            PatternType::CdlEngulfing.category_for(100),
+            PatternCategory::BullishReversal
+        );
+        assert_eq!(
+            PatternType::CdlEngulfing.category_for(-100),
+            PatternCategory::BearishReversal
+        );
+        assert_eq!(
+            PatternType::CdlHarami.category_for(-100),
+            PatternCategory::BearishReversal
+        );
+        assert_eq!(
+            PatternType::CdlHangingMan.category_for(-100),
+            PatternCategory::BearishReversal
+        );
+        assert_eq!(
+            PatternType::CdlDoji.category_for(100),
+            PatternCategory::Indecision
+        );
+        assert_eq!(PatternType::CdlTasukiGap.display_name(), "Tasuki Gap");
+    }
+}
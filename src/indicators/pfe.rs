@@ -19,6 +19,7 @@
 ///   with leading `NaN` until the indicator can be calculated.
 /// - **`Err(PfeError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::f64;
 use thiserror::Error;
 
@@ -134,6 +135,7 @@ pub fn pfe(input: &PfeInput) -> Result<PfeOutput, PfeError> {
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("pfe", period, data.len() - first_valid_idx);
         return Err(PfeError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -21,6 +21,7 @@
 ///   each matching the length of the input.
 /// - **`Err(PivotError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -172,6 +173,7 @@ pub fn pivot(input: &PivotInput) -> Result<PivotOutput, PivotError> {
     };
 
     if first_valid_idx >= len {
+        not_enough_valid_data("pivot", 1, 0);
         return Err(PivotError::NotEnoughValidData);
     }
 
@@ -21,6 +21,7 @@
 ///   points have accumulated.
 /// - **`Err(PmaError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum PmaData<'a> {
@@ -107,6 +108,7 @@ pub fn pma(input: &PmaInput) -> Result<PmaOutput, PmaError> {
     };
 
     if (data.len() - first_valid_idx) < 7 {
+        not_enough_valid_data("pma", 7, data.len() - first_valid_idx);
         return Err(PmaError::NotEnoughValidData {
             valid: data.len() - first_valid_idx,
         });
@@ -22,6 +22,7 @@ use crate::indicators::moving_averages::ma::{ma, MaData};
 ///   with leading `NaN`s until the slower period window is filled.
 /// - **`Err(PpoError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::error::Error;
 use thiserror::Error;
 
@@ -154,6 +155,7 @@ pub fn ppo(input: &PpoInput) -> Result<PpoOutput, PpoError> {
     let needed = slow_period;
     let valid = data.len() - first_valid_idx;
     if valid < needed {
+        not_enough_valid_data("ppo", needed, valid);
         return Err(PpoError::NotEnoughValidData { needed, valid });
     }
 
@@ -18,6 +18,7 @@
 ///   with leading `NaN`s until the first valid (non-`NaN`) data point.
 /// - **`Err(PviError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum PviData<'a> {
@@ -145,6 +146,7 @@ pub fn pvi(input: &PviInput) -> Result<PviOutput, PviError> {
     };
 
     if (close.len() - first_valid_idx) < 2 {
+        not_enough_valid_data("pvi", 2, close.len() - first_valid_idx);
         return Err(PviError::NotEnoughValidData);
     }
 
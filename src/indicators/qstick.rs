@@ -19,6 +19,7 @@
 ///   with leading `NaN`s until the Qstick window is filled.
 /// - **`Err(QstickError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -150,6 +151,7 @@ pub fn qstick(input: &QstickInput) -> Result<QstickOutput, QstickError> {
     };
 
     if (data_len - first_valid_idx) < period {
+        not_enough_valid_data("qstick", period, data_len - first_valid_idx);
         return Err(QstickError::NotEnoughValidData {
             needed: period,
             valid: data_len - first_valid_idx,
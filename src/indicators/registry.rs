@@ -0,0 +1,586 @@
+/// # Indicator Registry
+///
+/// Runs indicators by name with numeric parameters supplied in a map, for config-driven
+/// runners (e.g. `{indicator: "rsi", period: 14}`) that cannot dispatch at compile time.
+///
+/// Parameters missing from the map fall back to each indicator's defaults; unknown keys
+/// are ignored. Single-source indicators read `close`, except `cci` which reads `hlc3`.
+///
+/// [`tail_compute`] runs a registered indicator over only the bars needed for its last
+/// `n` values, e.g. for a dashboard showing the most recent bars of a long history.
+///
+/// [`compute_all`] runs a list of registered indicators over the same candles, reporting
+/// progress after each one, e.g. to drive a CLI progress bar.
+///
+/// ## Errors
+/// - **UnknownIndicator**: registry: `name` is not registered.
+/// - **InvalidParam**: registry: A count-like parameter (e.g. `period`) is negative,
+///   fractional, or not finite.
+/// - **Indicator**: registry: The underlying indicator returned an error.
+///
+/// ## Returns
+/// - **`Ok(IndicatorResult)`** with one series, or several named series for multi-output
+///   indicators such as `macd` or `bollinger_bands`.
+/// - **`Err(RegistryError)`** otherwise.
+use crate::indicators::adx::{adx, AdxInput, AdxParams};
+use crate::indicators::atr::{atr, AtrInput, AtrParams};
+use crate::indicators::bollinger_bands::{
+    bollinger_bands, BollingerBandsInput, BollingerBandsParams,
+};
+use crate::indicators::cci::{cci, CciInput, CciParams};
+use crate::indicators::cmo::{cmo, CmoInput, CmoParams};
+use crate::indicators::ema::{ema, EmaInput, EmaParams};
+use crate::indicators::macd::{macd, MacdInput, MacdParams};
+use crate::indicators::mfi::{mfi, MfiInput, MfiParams};
+use crate::indicators::mom::{mom, MomInput, MomParams};
+use crate::indicators::obv::{obv, ObvInput};
+use crate::indicators::roc::{roc, RocInput, RocParams};
+use crate::indicators::rsi::{rsi, RsiInput, RsiParams};
+use crate::indicators::sma::{sma, SmaInput, SmaParams};
+use crate::indicators::stddev::{stddev, StdDevInput, StdDevParams};
+use crate::indicators::stoch::{stoch, StochInput, StochParams};
+use crate::indicators::willr::{willr, WillrInput, WillrParams};
+use crate::indicators::wma::{wma, WmaInput, WmaParams};
+use crate::utilities::data_loader::Candles;
+use std::collections::HashMap;
+use std::error::Error;
+use thiserror::Error;
+
+#[derive(Debug, Clone)]
+pub enum IndicatorResult {
+    Single(Vec<f64>),
+    Multi(Vec<(&'static str, Vec<f64>)>),
+}
+
+impl IndicatorResult {
+    /// Returns the series named `name`. A `Single` result answers to any name.
+    pub fn series(&self, name: &str) -> Option<&[f64]> {
+        match self {
+            IndicatorResult::Single(values) => Some(values),
+            IndicatorResult::Multi(series) => series
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, values)| values.as_slice()),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    #[error("registry: Unknown indicator '{name}'")]
+    UnknownIndicator { name: String },
+    #[error("registry: Invalid value for parameter '{param}': {value}")]
+    InvalidParam { param: String, value: f64 },
+    #[error("registry: {0}")]
+    Indicator(Box<dyn Error>),
+}
+
+/// How an indicator's value at a bar depends on earlier bars, used by [`tail_compute`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndicatorKind {
+    /// Depends only on the last `lookback` bars (with default parameters).
+    Windowed { lookback: usize },
+    /// Recursively smoothed with a `lookback`-bar length (with default parameters); old
+    /// bars decay but never fully drop out.
+    Recursive { lookback: usize },
+    /// Running total from the first bar, so any tail needs the full history.
+    Cumulative,
+}
+
+/// Recursive indicators are warmed up over this many multiples of their lookback.
+const RECURSIVE_WARMUP_FACTOR: usize = 30;
+
+type ParamMap = HashMap<String, f64>;
+type ComputeFn = fn(&Candles, &ParamMap) -> Result<IndicatorResult, RegistryError>;
+
+fn indicator_error<E: Error + 'static>(e: E) -> RegistryError {
+    RegistryError::Indicator(Box::new(e))
+}
+
+fn usize_param(params: &ParamMap, key: &str) -> Result<Option<usize>, RegistryError> {
+    match params.get(key) {
+        None => Ok(None),
+        Some(&v) if v.is_finite() && v >= 0.0 && v.fract() == 0.0 => Ok(Some(v as usize)),
+        Some(&v) => Err(RegistryError::InvalidParam {
+            param: key.to_string(),
+            value: v,
+        }),
+    }
+}
+
+fn f64_param(params: &ParamMap, key: &str) -> Option<f64> {
+    params.get(key).copied()
+}
+
+const REGISTRY: &[(&str, IndicatorKind, ComputeFn)] = &[
+    (
+        "adx",
+        IndicatorKind::Recursive { lookback: 28 },
+        |candles, params| {
+            let params = AdxParams {
+                period: usize_param(params, "period")?,
+            };
+            let output = adx(&AdxInput::from_candles(candles, params)).map_err(indicator_error)?;
+            Ok(IndicatorResult::Single(output.values))
+        },
+    ),
+    (
+        "atr",
+        IndicatorKind::Recursive { lookback: 14 },
+        |candles, params| {
+            let params = AtrParams {
+                length: usize_param(params, "period")?,
+                ..AtrParams::default()
+            };
+            let output = atr(&AtrInput::from_candles(candles, params)).map_err(indicator_error)?;
+            Ok(IndicatorResult::Single(output.values))
+        },
+    ),
+    (
+        "bollinger_bands",
+        IndicatorKind::Windowed { lookback: 20 },
+        |candles, params| {
+            let params = BollingerBandsParams {
+                period: usize_param(params, "period")?,
+                devup: f64_param(params, "devup"),
+                devdn: f64_param(params, "devdn"),
+                ddof: usize_param(params, "ddof")?,
+                ..BollingerBandsParams::default()
+            };
+            let input = BollingerBandsInput::from_candles(candles, "close", params);
+            let output = bollinger_bands(&input).map_err(indicator_error)?;
+            Ok(IndicatorResult::Multi(vec![
+                ("upper", output.upper_band),
+                ("middle", output.middle_band),
+                ("lower", output.lower_band),
+            ]))
+        },
+    ),
+    (
+        "cci",
+        IndicatorKind::Windowed { lookback: 14 },
+        |candles, params| {
+            let params = CciParams {
+                period: usize_param(params, "period")?,
+                constant: f64_param(params, "constant"),
+                ..CciParams::default()
+            };
+            let output =
+                cci(&CciInput::from_candles(candles, "hlc3", params)).map_err(indicator_error)?;
+            Ok(IndicatorResult::Single(output.values))
+        },
+    ),
+    (
+        "cmo",
+        IndicatorKind::Recursive { lookback: 14 },
+        |candles, params| {
+            let params = CmoParams {
+                period: usize_param(params, "period")?,
+            };
+            let output =
+                cmo(&CmoInput::from_candles(candles, "close", params)).map_err(indicator_error)?;
+            Ok(IndicatorResult::Single(output.values))
+        },
+    ),
+    (
+        "ema",
+        IndicatorKind::Recursive { lookback: 9 },
+        |candles, params| {
+            let params = EmaParams {
+                period: usize_param(params, "period")?,
+                ..EmaParams::default()
+            };
+            let output =
+                ema(&EmaInput::from_candles(candles, "close", params)).map_err(indicator_error)?;
+            Ok(IndicatorResult::Single(output.values))
+        },
+    ),
+    (
+        "macd",
+        IndicatorKind::Recursive { lookback: 35 },
+        |candles, params| {
+            let params = MacdParams {
+                fast_period: usize_param(params, "fast_period")?,
+                slow_period: usize_param(params, "slow_period")?,
+                signal_period: usize_param(params, "signal_period")?,
+                ..MacdParams::default()
+            };
+            let output = macd(&MacdInput::from_candles(candles, "close", params))
+                .map_err(indicator_error)?;
+            Ok(IndicatorResult::Multi(vec![
+                ("macd", output.macd),
+                ("signal", output.signal),
+                ("hist", output.hist),
+            ]))
+        },
+    ),
+    (
+        "mfi",
+        IndicatorKind::Windowed { lookback: 15 },
+        |candles, params| {
+            let params = MfiParams {
+                period: usize_param(params, "period")?,
+            };
+            let output = mfi(&MfiInput::from_candles(candles, params)).map_err(indicator_error)?;
+            Ok(IndicatorResult::Single(output.values))
+        },
+    ),
+    (
+        "mom",
+        IndicatorKind::Windowed { lookback: 11 },
+        |candles, params| {
+            let params = MomParams {
+                period: usize_param(params, "period")?,
+            };
+            let output =
+                mom(&MomInput::from_candles(candles, "close", params)).map_err(indicator_error)?;
+            Ok(IndicatorResult::Single(output.values))
+        },
+    ),
+    ("obv", IndicatorKind::Cumulative, |candles, _| {
+        let output = obv(&ObvInput::with_default_candles(candles)).map_err(indicator_error)?;
+        Ok(IndicatorResult::Single(output.values))
+    }),
+    (
+        "roc",
+        IndicatorKind::Windowed { lookback: 10 },
+        |candles, params| {
+            let params = RocParams {
+                period: usize_param(params, "period")?,
+            };
+            let output =
+                roc(&RocInput::from_candles(candles, "close", params)).map_err(indicator_error)?;
+            Ok(IndicatorResult::Single(output.values))
+        },
+    ),
+    (
+        "rsi",
+        IndicatorKind::Recursive { lookback: 15 },
+        |candles, params| {
+            let params = RsiParams {
+                period: usize_param(params, "period")?,
+            };
+            let output =
+                rsi(&RsiInput::from_candles(candles, "close", params)).map_err(indicator_error)?;
+            Ok(IndicatorResult::Single(output.values))
+        },
+    ),
+    (
+        "sma",
+        IndicatorKind::Windowed { lookback: 9 },
+        |candles, params| {
+            let params = SmaParams {
+                period: usize_param(params, "period")?,
+            };
+            let output =
+                sma(&SmaInput::from_candles(candles, "close", params)).map_err(indicator_error)?;
+            Ok(IndicatorResult::Single(output.values))
+        },
+    ),
+    (
+        "stddev",
+        IndicatorKind::Windowed { lookback: 5 },
+        |candles, params| {
+            let params = StdDevParams {
+                period: usize_param(params, "period")?,
+                nbdev: f64_param(params, "nbdev"),
+            };
+            let output = stddev(&StdDevInput::from_candles(candles, "close", params))
+                .map_err(indicator_error)?;
+            Ok(IndicatorResult::Single(output.values))
+        },
+    ),
+    (
+        "stoch",
+        IndicatorKind::Windowed { lookback: 20 },
+        |candles, params| {
+            let params = StochParams {
+                fastk_period: usize_param(params, "fastk_period")?,
+                slowk_period: usize_param(params, "slowk_period")?,
+                slowd_period: usize_param(params, "slowd_period")?,
+                ..StochParams::default()
+            };
+            let output =
+                stoch(&StochInput::from_candles(candles, params)).map_err(indicator_error)?;
+            Ok(IndicatorResult::Multi(vec![
+                ("k", output.k),
+                ("d", output.d),
+            ]))
+        },
+    ),
+    (
+        "willr",
+        IndicatorKind::Windowed { lookback: 14 },
+        |candles, params| {
+            let params = WillrParams {
+                period: usize_param(params, "period")?,
+            };
+            let output =
+                willr(&WillrInput::from_candles(candles, params)).map_err(indicator_error)?;
+            Ok(IndicatorResult::Single(output.values))
+        },
+    ),
+    (
+        "wma",
+        IndicatorKind::Windowed { lookback: 30 },
+        |candles, params| {
+            let params = WmaParams {
+                period: usize_param(params, "period")?,
+            };
+            let output =
+                wma(&WmaInput::from_candles(candles, "close", params)).map_err(indicator_error)?;
+            Ok(IndicatorResult::Single(output.values))
+        },
+    ),
+];
+
+/// Names accepted by [`compute_named`], in alphabetical order.
+pub fn registered_indicators() -> Vec<&'static str> {
+    REGISTRY.iter().map(|(name, _, _)| *name).collect()
+}
+
+#[inline]
+pub fn compute_named(
+    name: &str,
+    candles: &Candles,
+    params: &HashMap<String, f64>,
+) -> Result<IndicatorResult, RegistryError> {
+    let (_, _, compute) = lookup(name)?;
+    compute(candles, params)
+}
+
+/// Runs each `(name, params)` request through [`compute_named`], in order, and returns
+/// one result per request; a failing indicator does not stop the rest.
+///
+/// When given, `progress` is called as `progress(completed, total)` after every
+/// indicator finishes, successfully or not, so the last call is `(total, total)`.
+#[inline]
+pub fn compute_all(
+    candles: &Candles,
+    requests: &[(&str, HashMap<String, f64>)],
+    progress: Option<&dyn Fn(usize, usize)>,
+) -> Vec<Result<IndicatorResult, RegistryError>> {
+    let total = requests.len();
+    requests
+        .iter()
+        .enumerate()
+        .map(|(i, (name, params))| {
+            let result = compute_named(name, candles, params);
+            if let Some(progress) = progress {
+                progress(i + 1, total);
+            }
+            result
+        })
+        .collect()
+}
+
+fn lookup(name: &str) -> Result<&'static (&'static str, IndicatorKind, ComputeFn), RegistryError> {
+    let key = name.to_lowercase();
+    REGISTRY
+        .iter()
+        .find(|(registered, _, _)| *registered == key)
+        .ok_or_else(|| RegistryError::UnknownIndicator {
+            name: name.to_string(),
+        })
+}
+
+/// Returns how `name` depends on past bars, or `None` if it is not registered.
+pub fn indicator_kind(name: &str) -> Option<IndicatorKind> {
+    lookup(name).ok().map(|(_, kind, _)| *kind)
+}
+
+/// Computes `name` over only the bars needed for its last `n` values and returns those
+/// `n` values (or the whole series when `n` covers it).
+///
+/// Windowed indicators run over the last `n + lookback` bars, which reproduces the full
+/// run exactly. Recursive ones get a warmup of `RECURSIVE_WARMUP_FACTOR * lookback` extra
+/// bars, enough for the seed to decay well below `1e-6`. Cumulative ones (OBV) always run
+/// over the full history. The lookback is the indicator's default plus every count-like
+/// value in `params`, so it errs on the long side when periods are overridden.
+#[inline]
+pub fn tail_compute(
+    name: &str,
+    candles: &Candles,
+    params: &HashMap<String, f64>,
+    n: usize,
+) -> Result<IndicatorResult, RegistryError> {
+    let (_, kind, compute) = lookup(name)?;
+    let len = candles.close.len();
+    let extra: usize = params
+        .values()
+        .filter(|v| v.is_finite() && **v >= 0.0 && v.fract() == 0.0)
+        .map(|&v| v as usize)
+        .sum();
+    let warmup = match *kind {
+        IndicatorKind::Windowed { lookback } => lookback + extra,
+        IndicatorKind::Recursive { lookback } => RECURSIVE_WARMUP_FACTOR * (lookback + extra),
+        IndicatorKind::Cumulative => len,
+    };
+
+    let start = len.saturating_sub(n.saturating_add(warmup));
+    let result = if start == 0 {
+        compute(candles, params)?
+    } else {
+        let tail = Candles::new(
+            candles.timestamp[start..].to_vec(),
+            candles.open[start..].to_vec(),
+            candles.high[start..].to_vec(),
+            candles.low[start..].to_vec(),
+            candles.close[start..].to_vec(),
+            candles.volume[start..].to_vec(),
+        );
+        compute(&tail, params)?
+    };
+
+    let keep_last = |values: Vec<f64>| values[values.len().saturating_sub(n)..].to_vec();
+    Ok(match result {
+        IndicatorResult::Single(values) => IndicatorResult::Single(keep_last(values)),
+        IndicatorResult::Multi(series) => IndicatorResult::Multi(
+            series
+                .into_iter()
+                .map(|(name, values)| (name, keep_last(values)))
+                .collect(),
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::data_loader::read_candles_from_csv;
+
+    #[test]
+    fn test_compute_named_sma_matches_typed_call() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let params = HashMap::from([("period".to_string(), 20.0)]);
+
+        let named = compute_named("sma", &candles, &params).expect("Failed named SMA");
+        let typed = sma(&SmaInput::from_candles(
+            &candles,
+            "close",
+            SmaParams { period: Some(20) },
+        ))
+        .expect("Failed typed SMA");
+
+        match named {
+            IndicatorResult::Single(values) => {
+                assert_eq!(values.len(), typed.values.len());
+                for (i, (a, b)) in values.iter().zip(&typed.values).enumerate() {
+                    assert!((a.is_nan() && b.is_nan()) || a == b, "Mismatch at {}", i);
+                }
+            }
+            IndicatorResult::Multi(_) => panic!("Expected a single series for sma"),
+        }
+    }
+
+    #[test]
+    fn test_compute_named_errors_and_multi_output() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let empty = HashMap::new();
+
+        assert!(matches!(
+            compute_named("not_an_indicator", &candles, &empty),
+            Err(RegistryError::UnknownIndicator { .. })
+        ));
+
+        let fractional = HashMap::from([("period".to_string(), 14.5)]);
+        assert!(matches!(
+            compute_named("rsi", &candles, &fractional),
+            Err(RegistryError::InvalidParam { .. })
+        ));
+
+        let zero = HashMap::from([("period".to_string(), 0.0)]);
+        assert!(matches!(
+            compute_named("sma", &candles, &zero),
+            Err(RegistryError::Indicator(_))
+        ));
+
+        let macd = compute_named("MACD", &candles, &empty).expect("Failed named MACD");
+        assert_eq!(macd.series("hist").unwrap().len(), candles.close.len());
+        assert!(macd.series("upper").is_none());
+
+        let names = registered_indicators();
+        assert!(names.len() >= 15);
+        assert!(names.windows(2).all(|w| w[0] < w[1]));
+        for name in names {
+            compute_named(name, &candles, &empty)
+                .unwrap_or_else(|e| panic!("Default run of {} failed: {}", name, e));
+        }
+    }
+
+    #[test]
+    fn test_tail_compute_matches_full_run() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let n = 500;
+
+        for (name, params) in [
+            ("ema", HashMap::new()),
+            ("ema", HashMap::from([("period".to_string(), 50.0)])),
+            ("sma", HashMap::from([("period".to_string(), 20.0)])),
+            ("rsi", HashMap::new()),
+            ("macd", HashMap::new()),
+            ("obv", HashMap::new()),
+        ] {
+            let full = compute_named(name, &candles, &params).expect("full run");
+            let tail = tail_compute(name, &candles, &params, n).expect("tail run");
+            let series = if name == "macd" { "hist" } else { name };
+            let full = full.series(series).unwrap();
+            let tail = tail.series(series).unwrap();
+            assert_eq!(tail.len(), n);
+            for (i, (a, b)) in tail.iter().zip(&full[full.len() - n..]).enumerate() {
+                assert!(
+                    (a - b).abs() < 1e-6,
+                    "{} tail mismatch at {}: {} vs {}",
+                    name,
+                    i,
+                    a,
+                    b
+                );
+            }
+        }
+
+        assert_eq!(
+            indicator_kind("EMA"),
+            Some(IndicatorKind::Recursive { lookback: 9 })
+        );
+        assert_eq!(indicator_kind("obv"), Some(IndicatorKind::Cumulative));
+        assert!(indicator_kind("nope").is_none());
+
+        let everything = tail_compute("sma", &candles, &HashMap::new(), usize::MAX)
+            .expect("tail longer than data");
+        assert_eq!(everything.series("sma").unwrap().len(), candles.close.len());
+    }
+
+    #[test]
+    fn test_compute_all_reports_progress() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let requests = [
+            ("sma", HashMap::from([("period".to_string(), 20.0)])),
+            ("not_an_indicator", HashMap::new()),
+            ("rsi", HashMap::new()),
+            ("macd", HashMap::new()),
+        ];
+
+        let calls = std::cell::RefCell::new(Vec::new());
+        let record = |completed: usize, total: usize| calls.borrow_mut().push((completed, total));
+        let results = compute_all(&candles, &requests, Some(&record));
+
+        assert_eq!(results.len(), requests.len());
+        assert!(matches!(
+            results[1],
+            Err(RegistryError::UnknownIndicator { .. })
+        ));
+        assert_eq!(
+            results[0].as_ref().unwrap().series("sma").unwrap().len(),
+            candles.close.len()
+        );
+        assert_eq!(calls.into_inner(), vec![(1, 4), (2, 4), (3, 4), (4, 4)]);
+
+        let silent = compute_all(&candles, &requests[..1], None);
+        assert!(silent[0].is_ok());
+    }
+}
@@ -7,6 +7,15 @@
 /// \[Formula\]
 /// \[ ROC[i] = ((price[i] / price[i - period]) - 1) * 100 \]
 ///
+/// ROC is a percent change. See `rocp` for the same change as a proportion and
+/// `rocr`/`rocr100` for the plain ratio.
+///
+/// ## Example
+/// ```text
+/// prices = [100.0, 110.0], period = 1
+/// ROC  = 10.0
+/// ```
+///
 /// ## Parameters
 /// - **period**: The lookback window (number of data points). Defaults to 9.
 ///
@@ -22,6 +31,7 @@
 ///   with leading `NaN`s until the first valid ROC value.
 /// - **`Err(RocError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -124,6 +134,7 @@ pub fn roc(input: &RocInput) -> Result<RocOutput, RocError> {
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("roc", period, data.len() - first_valid_idx);
         return Err(RocError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -134,6 +145,7 @@ pub fn roc(input: &RocInput) -> Result<RocOutput, RocError> {
 
     let start_idx = first_valid_idx + period;
     if start_idx > data.len() - 1 {
+        not_enough_valid_data("roc", period, data.len() - first_valid_idx);
         return Err(RocError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
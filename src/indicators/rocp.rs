@@ -8,6 +8,15 @@
 ///
 /// This indicator is centered around 0 and can be positive or negative.
 ///
+/// ROCP is a proportional change (ROC / 100). See `roc` for the percent form and
+/// `rocr`/`rocr100` for the plain ratio.
+///
+/// ## Example
+/// ```text
+/// prices = [100.0, 110.0], period = 1
+/// ROCP = 0.10
+/// ```
+///
 /// ## Parameters
 /// - **period**: The lookback window (number of data points). Defaults to 9.
 ///
@@ -23,6 +32,7 @@
 ///   with leading `NaN`s until the moving window is filled.
 /// - **`Err(RocpError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -124,6 +134,7 @@ pub fn rocp(input: &RocpInput) -> Result<RocpOutput, RocpError> {
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("rocp", period, data.len() - first_valid_idx);
         return Err(RocpError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
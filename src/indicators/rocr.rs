@@ -8,6 +8,15 @@
 /// \[Formula\]
 /// \[ ROCR[i] = price[i] / price[i - period] \]
 ///
+/// ROCR is a plain ratio (ROCP + 1). See `rocr100` for the same ratio scaled by 100,
+/// and `roc`/`rocp` for the percent and proportional changes.
+///
+/// ## Example
+/// ```text
+/// prices = [100.0, 110.0], period = 1
+/// ROCR = 1.10
+/// ```
+///
 /// ## Parameters
 /// - **period**: The lookback window (number of data points). Defaults to 9.
 ///
@@ -23,6 +32,7 @@
 ///   with leading `NaN`s until the first valid ROCR value.
 /// - **`Err(RocrError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -126,6 +136,7 @@ pub fn rocr(input: &RocrInput) -> Result<RocrOutput, RocrError> {
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("rocr", period, data.len() - first_valid_idx);
         return Err(RocrError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -0,0 +1,224 @@
+/// # Rate of Change Ratio 100 Scale (ROCR100)
+///
+/// ROCR100 is the Rate of Change Ratio expressed on a 100 scale. It is centered
+/// around 100: a value of 100 means no change, >100 means an increase, and <100
+/// indicates a decrease. The ratio itself is computed by [`rocr`](crate::indicators::rocr::rocr)
+/// and scaled by 100.
+///
+/// \[Formula\]
+/// \[ ROCR100[i] = (price[i] / price[i - period]) * 100 \]
+///
+/// ## Example
+/// ```text
+/// prices = [100.0, 110.0], period = 1
+/// ROC     = 10.0    (percent change)
+/// ROCP    = 0.10    (proportional change)
+/// ROCR    = 1.10    (ratio)
+/// ROCR100 = 110.0   (ratio * 100)
+/// ```
+///
+/// ## Parameters
+/// - **period**: The lookback window (number of data points). Defaults to 9.
+///
+/// ## Errors
+/// - **EmptyData**: rocr100: Input data slice is empty.
+/// - **InvalidPeriod**: rocr100: `period` is zero or exceeds the data length.
+/// - **NotEnoughValidData**: rocr100: Fewer than `period` valid (non-`NaN`) data points remain
+///   after the first valid index.
+/// - **AllValuesNaN**: rocr100: All input data values are `NaN`.
+///
+/// ## Returns
+/// - **`Ok(Rocr100Output)`** on success, containing a `Vec<f64>` matching the input length,
+///   with leading `NaN`s until the first valid ROCR100 value.
+/// - **`Err(Rocr100Error)`** otherwise.
+use crate::indicators::rocr::{rocr, RocrError, RocrInput, RocrParams};
+use crate::utilities::data_loader::{Candles, SourceData};
+use thiserror::Error;
+
+pub type Rocr100Data<'a> = SourceData<'a>;
+
+#[derive(Debug, Clone)]
+pub struct Rocr100Output {
+    pub values: Vec<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Rocr100Params {
+    pub period: Option<usize>,
+}
+
+impl Default for Rocr100Params {
+    fn default() -> Self {
+        Self { period: Some(9) }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rocr100Input<'a> {
+    pub data: Rocr100Data<'a>,
+    pub params: Rocr100Params,
+}
+
+impl<'a> Rocr100Input<'a> {
+    pub fn from_candles(candles: &'a Candles, source: &'a str, params: Rocr100Params) -> Self {
+        Self {
+            data: Rocr100Data::Candles { candles, source },
+            params,
+        }
+    }
+
+    pub fn from_slice(slice: &'a [f64], params: Rocr100Params) -> Self {
+        Self {
+            data: Rocr100Data::Slice(slice),
+            params,
+        }
+    }
+
+    pub fn with_default_candles(candles: &'a Candles) -> Self {
+        Self {
+            data: Rocr100Data::Candles {
+                candles,
+                source: "close",
+            },
+            params: Rocr100Params::default(),
+        }
+    }
+
+    pub fn get_period(&self) -> usize {
+        self.params
+            .period
+            .unwrap_or_else(|| Rocr100Params::default().period.unwrap())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Rocr100Error {
+    #[error("rocr100: Empty data provided.")]
+    EmptyData,
+    #[error("rocr100: Invalid period: period = {period}, data length = {data_len}")]
+    InvalidPeriod { period: usize, data_len: usize },
+    #[error("rocr100: Not enough valid data: needed = {needed}, valid = {valid}")]
+    NotEnoughValidData { needed: usize, valid: usize },
+    #[error("rocr100: All values are NaN.")]
+    AllValuesNaN,
+}
+
+impl From<RocrError> for Rocr100Error {
+    fn from(err: RocrError) -> Self {
+        match err {
+            RocrError::EmptyData => Rocr100Error::EmptyData,
+            RocrError::InvalidPeriod { period, data_len } => {
+                Rocr100Error::InvalidPeriod { period, data_len }
+            }
+            RocrError::NotEnoughValidData { needed, valid } => {
+                Rocr100Error::NotEnoughValidData { needed, valid }
+            }
+            RocrError::AllValuesNaN => Rocr100Error::AllValuesNaN,
+        }
+    }
+}
+
+#[inline]
+pub fn rocr100(input: &Rocr100Input) -> Result<Rocr100Output, Rocr100Error> {
+    let data: &[f64] = input.data.resolve();
+
+    let rocr_input = RocrInput::from_slice(
+        data,
+        RocrParams {
+            period: Some(input.get_period()),
+        },
+    );
+    let mut values = rocr(&rocr_input)?.values;
+    for v in values.iter_mut() {
+        *v *= 100.0;
+    }
+
+    Ok(Rocr100Output { values })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::data_loader::read_candles_from_csv;
+
+    #[test]
+    fn test_rocr100_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let params = Rocr100Params { period: Some(10) };
+        let input = Rocr100Input::from_candles(&candles, "close", params);
+        let result = rocr100(&input).expect("Failed to calculate ROCR100");
+        assert_eq!(result.values.len(), candles.close.len());
+
+        let expected_last_five = [
+            99.77448290950706,
+            99.44380965183492,
+            99.67247986764135,
+            99.50545846019277,
+            98.4954072979463,
+        ];
+        let start_idx = result.values.len() - 5;
+        for (i, &val) in result.values[start_idx..].iter().enumerate() {
+            let exp = expected_last_five[i];
+            assert!(
+                (val - exp).abs() < 1e-6,
+                "ROCR100 mismatch at index {}: expected {}, got {}",
+                i,
+                exp,
+                val
+            );
+        }
+
+        for i in 0..10 {
+            assert!(result.values[i].is_nan(), "Expected NaN at index {}", i);
+        }
+    }
+
+    #[test]
+    fn test_rocr100_matches_rocr_times_100() {
+        let data = [100.0, 110.0, 99.0, 121.0, 0.0, 50.0];
+        let ratio = rocr(&RocrInput::from_slice(
+            &data,
+            RocrParams { period: Some(1) },
+        ))
+        .expect("Failed ROCR");
+        let scaled = rocr100(&Rocr100Input::from_slice(
+            &data,
+            Rocr100Params { period: Some(1) },
+        ))
+        .expect("Failed ROCR100");
+        assert!(scaled.values[0].is_nan());
+        for i in 1..data.len() {
+            assert!((scaled.values[i] - ratio.values[i] * 100.0).abs() < 1e-12);
+        }
+        assert!((scaled.values[1] - 110.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rocr100_invalid_period() {
+        let data = [1.0, 2.0, 3.0];
+        let zero = rocr100(&Rocr100Input::from_slice(
+            &data,
+            Rocr100Params { period: Some(0) },
+        ));
+        assert!(matches!(zero, Err(Rocr100Error::InvalidPeriod { .. })));
+        let too_long = rocr100(&Rocr100Input::from_slice(
+            &data,
+            Rocr100Params { period: Some(4) },
+        ));
+        assert!(matches!(too_long, Err(Rocr100Error::InvalidPeriod { .. })));
+    }
+
+    #[test]
+    fn test_rocr100_input_with_default_candles() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = Rocr100Input::with_default_candles(&candles);
+        assert_eq!(input.get_period(), 9);
+        match input.data {
+            Rocr100Data::Candles { source, .. } => assert_eq!(source, "close"),
+            _ => panic!("Expected Rocr100Data::Candles variant"),
+        }
+    }
+}
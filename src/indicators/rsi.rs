@@ -16,18 +16,13 @@
 /// ## Returns
 /// - **`Ok(RsiOutput)`** on success, containing a `Vec<f64>` of length matching
 ///   the input data. The first `period` bars remain `NaN` until the RSI
-///   “warms up.”
+///   “warms up.” The Wilder-smoothed `avg_gain` and `avg_loss` series behind each
+///   value are returned alongside, with the same warmup.
 /// - **`Err(RsiError)`** otherwise.
-use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::data_loader::{Candles, SourceData};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
-#[derive(Debug, Clone)]
-pub enum RsiData<'a> {
-    Candles {
-        candles: &'a Candles,
-        source: &'a str,
-    },
-    Slice(&'a [f64]),
-}
+pub type RsiData<'a> = SourceData<'a>;
 
 #[derive(Debug, Clone)]
 pub struct RsiParams {
@@ -76,11 +71,36 @@ impl<'a> RsiInput<'a> {
             .period
             .unwrap_or_else(|| RsiParams::default().period.unwrap())
     }
+
+    /// Copies the resolved source into an [`OwnedRsiInput`] that no longer borrows the
+    /// candles or slice it was built from.
+    pub fn into_owned(self) -> OwnedRsiInput {
+        OwnedRsiInput {
+            data: self.data.resolve().to_vec(),
+            params: self.params,
+        }
+    }
+}
+
+/// A [`RsiInput`] holding its own copy of the source, so it can be stored past the
+/// lifetime of the candles or sent to another thread.
+#[derive(Debug, Clone)]
+pub struct OwnedRsiInput {
+    pub data: Vec<f64>,
+    pub params: RsiParams,
+}
+
+impl OwnedRsiInput {
+    pub fn compute(&self) -> Result<RsiOutput, RsiError> {
+        rsi(&RsiInput::from_slice(&self.data, self.params.clone()))
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct RsiOutput {
     pub values: Vec<f64>,
+    pub avg_gain: Vec<f64>,
+    pub avg_loss: Vec<f64>,
 }
 
 use thiserror::Error;
@@ -101,11 +121,8 @@ pub enum RsiError {
 }
 
 #[inline]
-pub fn rsi(input: &RsiInput) -> Result<RsiOutput, RsiError> {
-    let data: &[f64] = match &input.data {
-        RsiData::Candles { candles, source } => source_type(candles, source),
-        RsiData::Slice(slice) => slice,
-    };
+fn validate_rsi<'a>(input: &'a RsiInput) -> Result<(&'a [f64], usize, usize), RsiError> {
+    let data: &[f64] = input.data.resolve();
     let period = input.get_period();
     let len = data.len();
     let first_valid_idx = match data.iter().position(|&x| !x.is_nan()) {
@@ -117,6 +134,7 @@ pub fn rsi(input: &RsiInput) -> Result<RsiOutput, RsiError> {
         return Err(RsiError::NoData);
     }
     if len < period {
+        not_enough_valid_data("rsi", period, len);
         return Err(RsiError::NotEnoughData {
             needed: period,
             found: len,
@@ -128,8 +146,20 @@ pub fn rsi(input: &RsiInput) -> Result<RsiOutput, RsiError> {
             data_len: len,
         });
     }
-    let mut rsi_values = vec![f64::NAN; len];
 
+    Ok((data, period, first_valid_idx))
+}
+
+/// Runs the Wilder recursion, passing each `(index, rsi, avg_gain, avg_loss)` from the
+/// warmup on to `emit`, so [`rsi`] and [`rsi_last`] share the exact same arithmetic.
+#[inline(always)]
+fn rsi_pass(
+    data: &[f64],
+    period: usize,
+    first_valid_idx: usize,
+    mut emit: impl FnMut(usize, f64, f64, f64),
+) {
+    let len = data.len();
     let inv_period = 1.0 / period as f64;
     let beta = 1.0 - inv_period;
 
@@ -153,7 +183,7 @@ pub fn rsi(input: &RsiInput) -> Result<RsiOutput, RsiError> {
     } else {
         100.0 * avg_gain / (avg_gain + avg_loss)
     };
-    rsi_values[first_valid_idx + period] = initial_rsi;
+    emit(first_valid_idx + period, initial_rsi, avg_gain, avg_loss);
 
     for i in (first_valid_idx + period + 1)..len {
         let delta = data[i] - data[i - 1];
@@ -169,10 +199,41 @@ pub fn rsi(input: &RsiInput) -> Result<RsiOutput, RsiError> {
             100.0 * avg_gain / (avg_gain + avg_loss)
         };
 
-        rsi_values[i] = current_rsi;
+        emit(i, current_rsi, avg_gain, avg_loss);
     }
+}
+
+#[inline]
+pub fn rsi(input: &RsiInput) -> Result<RsiOutput, RsiError> {
+    let (data, period, first_valid_idx) = validate_rsi(input)?;
+    let len = data.len();
+
+    let mut rsi_values = vec![f64::NAN; len];
+    let mut avg_gain_values = vec![f64::NAN; len];
+    let mut avg_loss_values = vec![f64::NAN; len];
+
+    rsi_pass(data, period, first_valid_idx, |i, value, gain, loss| {
+        rsi_values[i] = value;
+        avg_gain_values[i] = gain;
+        avg_loss_values[i] = loss;
+    });
+
+    Ok(RsiOutput {
+        values: rsi_values,
+        avg_gain: avg_gain_values,
+        avg_loss: avg_loss_values,
+    })
+}
+
+/// The last value of [`rsi`] without allocating the three output series. The Wilder
+/// recursion still walks the whole input; returns `None` when the last value is `NaN`.
+#[inline]
+pub fn rsi_last(input: &RsiInput) -> Result<Option<f64>, RsiError> {
+    let (data, period, first_valid_idx) = validate_rsi(input)?;
 
-    Ok(RsiOutput { values: rsi_values })
+    let mut last = f64::NAN;
+    rsi_pass(data, period, first_valid_idx, |_, value, _, _| last = value);
+    Ok(Some(last).filter(|v| !v.is_nan()))
 }
 
 #[cfg(test)]
@@ -327,4 +388,76 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_rsi_source_data_variants_match() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let params = RsiParams { period: Some(14) };
+
+        let candles_input = RsiInput::from_candles(&candles, "hl2", params.clone());
+        let slice_input = RsiInput::from_slice(&candles.hl2, params);
+        assert_eq!(candles_input.data.resolve(), candles.hl2.as_slice());
+        assert_eq!(slice_input.data.resolve(), candles.hl2.as_slice());
+
+        let from_candles = rsi(&candles_input).expect("Failed rsi from candles");
+        let from_slice = rsi(&slice_input).expect("Failed rsi from slice");
+        assert_eq!(from_candles.values.len(), candles.close.len());
+        for (i, (a, b)) in from_candles
+            .values
+            .iter()
+            .zip(&from_slice.values)
+            .enumerate()
+        {
+            assert!(
+                (a.is_nan() && b.is_nan()) || a == b,
+                "Rsi variant mismatch at {}: {} vs {}",
+                i,
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn test_rsi_avg_gain_loss_reproduce_values() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = RsiInput::with_default_candles(&candles);
+        let result = rsi(&input).expect("Failed to calculate RSI");
+        assert_eq!(result.avg_gain.len(), result.values.len());
+        assert_eq!(result.avg_loss.len(), result.values.len());
+
+        let mut checked = 0;
+        for i in 0..result.values.len() {
+            if result.values[i].is_nan() {
+                assert!(result.avg_gain[i].is_nan() && result.avg_loss[i].is_nan());
+                continue;
+            }
+            if result.avg_loss[i] == 0.0 {
+                continue;
+            }
+            let rs = result.avg_gain[i] / result.avg_loss[i];
+            let expected = 100.0 - 100.0 / (1.0 + rs);
+            assert!(
+                (result.values[i] - expected).abs() < 1e-9,
+                "RSI mismatch at {}: {} vs {}",
+                i,
+                result.values[i],
+                expected
+            );
+            checked += 1;
+        }
+        assert!(checked > 15000);
+    }
+
+    #[test]
+    fn test_rsi_last_matches_full() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = RsiInput::from_candles(&candles, "close", RsiParams::default());
+        let full = rsi(&input).expect("Failed to calculate RSI");
+        let last = rsi_last(&input).expect("Failed to calculate last RSI");
+        assert_eq!(last, full.values.last().copied());
+    }
 }
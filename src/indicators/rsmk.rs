@@ -26,6 +26,7 @@ use crate::indicators::moving_averages::ma::{ma, MaData};
 ///   with leading `NaN`s until each respective moving average window is filled.
 /// - **`Err(RsmkError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::error::Error;
 use thiserror::Error;
 
@@ -212,6 +213,11 @@ pub fn rsmk(input: &RsmkInput) -> Result<RsmkOutput, RsmkError> {
 
     let valid_points = a.len() - first_valid_idx;
     if valid_points < lookback.max(period).max(signal_period) {
+        not_enough_valid_data(
+            "rsmk",
+            lookback.max(period).max(signal_period),
+            valid_points,
+        );
         return Err(RsmkError::NotEnoughValidData {
             needed: lookback.max(period).max(signal_period),
             valid: valid_points,
@@ -19,6 +19,7 @@
 ///   with leading `NaN`s until the RSX calculation window is filled.
 /// - **`Err(RsxError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -121,6 +122,7 @@ pub fn rsx(input: &RsxInput) -> Result<RsxOutput, RsxError> {
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("rsx", period, data.len() - first_valid_idx);
         return Err(RsxError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -24,6 +24,7 @@
 ///   with leading `NaN`s until the indicator can be computed.
 /// - **`Err(RviError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum RviData<'a> {
@@ -163,6 +164,7 @@ pub fn rvi(input: &RviInput) -> Result<RviOutput, RviError> {
 
     let max_needed = period.saturating_sub(1) + ma_len.saturating_sub(1);
     if (data.len() - first_valid_idx) <= max_needed {
+        not_enough_valid_data("rvi", max_needed + 1, data.len() - first_valid_idx);
         return Err(RviError::NotEnoughValidData {
             needed: max_needed + 1,
             valid: data.len() - first_valid_idx,
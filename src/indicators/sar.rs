@@ -18,6 +18,7 @@
 ///   with leading `NaN`s until the calculation starts.
 /// - **`Err(SarError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -124,6 +125,7 @@ pub fn sar(input: &SarInput) -> Result<SarOutput, SarError> {
     };
 
     if (high.len() - first_valid_idx) < 2 {
+        not_enough_valid_data("sar", 2, high.len() - first_valid_idx);
         return Err(SarError::NotEnoughValidData {
             needed: 2,
             valid: high.len() - first_valid_idx,
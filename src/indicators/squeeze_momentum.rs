@@ -25,6 +25,7 @@
 /// - **`Err(SqueezeMomentumError)`** otherwise.
 use crate::indicators::sma::{sma, SmaData, SmaInput, SmaParams};
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -177,6 +178,7 @@ pub fn squeeze_momentum(
     };
     let needed = length_bb.max(length_kc);
     if (high.len() - first_valid_idx) < needed {
+        not_enough_valid_data("squeeze_momentum", needed, high.len() - first_valid_idx);
         return Err(SqueezeMomentumError::NotEnoughValidData {
             needed,
             valid: high.len() - first_valid_idx,
@@ -27,6 +27,7 @@ use crate::indicators::utility_functions::{max_rolling, min_rolling};
 ///   with leading `NaN`s until calculations can begin.
 /// - **`Err(StcError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::error::Error;
 use thiserror::Error;
 
@@ -179,6 +180,7 @@ pub fn stc(input: &StcInput) -> Result<StcOutput, StcError> {
     let valid_len = data.len() - first_valid_idx;
 
     if valid_len < needed {
+        not_enough_valid_data("stc", needed, valid_len);
         return Err(StcError::NotEnoughValidData {
             needed,
             valid: valid_len,
@@ -221,6 +223,7 @@ pub fn stc(input: &StcInput) -> Result<StcOutput, StcError> {
         &stok,
         EmaParams {
             period: Some(d_period),
+            ..EmaParams::default()
         },
     );
     let d_ema_output = match ema(&d_ema_input) {
@@ -251,6 +254,7 @@ pub fn stc(input: &StcInput) -> Result<StcOutput, StcError> {
         &kd,
         EmaParams {
             period: Some(d_period),
+            ..EmaParams::default()
         },
     );
     let kd_ema_output = match ema(&kd_ema_input) {
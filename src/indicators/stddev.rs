@@ -20,6 +20,7 @@
 ///   with leading `NaN`s until the rolling window is filled.
 /// - **`Err(StdDevError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -133,6 +134,7 @@ pub fn stddev(input: &StdDevInput) -> Result<StdDevOutput, StdDevError> {
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("stddev", period, data.len() - first_valid_idx);
         return Err(StdDevError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -26,6 +26,7 @@ use crate::indicators::utility_functions::{max_rolling, min_rolling};
 ///   with leading `NaN`s until each component can be calculated.
 /// - **`Err(StochError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::error::Error;
 use thiserror::Error;
 
@@ -213,6 +214,7 @@ pub fn stoch(input: &StochInput) -> Result<StochOutput, StochError> {
     };
 
     if (data_len - first_valid_idx) < fastk_period {
+        not_enough_valid_data("stoch", fastk_period, data_len - first_valid_idx);
         return Err(StochError::NotEnoughValidData {
             needed: fastk_period,
             valid: data_len - first_valid_idx,
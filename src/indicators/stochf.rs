@@ -22,6 +22,7 @@ use crate::indicators::sma::{sma, SmaData, SmaInput, SmaOutput, SmaParams};
 ///   with leading `NaN`s until each series can be computed.
 /// - **`Err(StochfError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -183,6 +184,7 @@ pub fn stochf(input: &StochfInput) -> Result<StochfOutput, StochfError> {
     };
 
     if (len - first_valid_idx) < fastk_period {
+        not_enough_valid_data("stochf", fastk_period, len - first_valid_idx);
         return Err(StochfError::NotEnoughValidData {
             needed: fastk_period,
             valid: len - first_valid_idx,
@@ -237,7 +239,9 @@ pub fn stochf(input: &StochfInput) -> Result<StochfOutput, StochfError> {
             period: Some(fastd_period),
         },
     };
-    let SmaOutput { values: d_result } = match sma(&d_input) {
+    let SmaOutput {
+        values: d_result, ..
+    } = match sma(&d_input) {
         Ok(res) => res,
         Err(_) => {
             return Err(StochfError::NotEnoughValidData {
@@ -24,6 +24,7 @@ use crate::indicators::atr::{atr, AtrData, AtrError, AtrInput, AtrOutput, AtrPar
 ///   until the calculation window is filled.
 /// - **`Err(SuperTrendError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum SuperTrendData<'a> {
@@ -159,6 +160,7 @@ pub fn supertrend(input: &SuperTrendInput) -> Result<SuperTrendOutput, SuperTren
         None => return Err(SuperTrendError::AllValuesNaN),
     };
     if (len - first_valid_idx) < period {
+        not_enough_valid_data("supertrend", period, len - first_valid_idx);
         return Err(SuperTrendError::NotEnoughValidData {
             needed: period,
             valid: len - first_valid_idx,
@@ -171,6 +173,7 @@ pub fn supertrend(input: &SuperTrendInput) -> Result<SuperTrendOutput, SuperTren
         &close[first_valid_idx..],
         AtrParams {
             length: Some(period),
+            ..AtrParams::default()
         },
     );
     let AtrOutput { values: atr_values } = atr(&atr_input)?;
@@ -7,8 +7,11 @@
 /// Each subsequent pass for EMA2 and EMA3 also uses that same initialization pattern, ensuring
 /// accurate alignment for the final TRIX values.
 ///
+/// The signal line is a `signal_period` SMA of TRIX.
+///
 /// ## Parameters
 /// - **period**: The EMA window size. Defaults to 18.
+/// - **signal_period**: Smoothing period for the signal line. Defaults to 9.
 ///
 /// ## Errors
 /// - **EmptyData**: trix: Input data slice is empty.
@@ -16,12 +19,21 @@
 /// - **NotEnoughValidData**: trix: Fewer than `3*(period - 1) + 1` valid data points remain
 ///   after the first valid index for triple-EMA + 1-bar ROC.
 /// - **AllValuesNaN**: trix: All input data values are `NaN`.
+/// - **Sma(...)**: trix: Propagated error from the signal line SMA (e.g. `signal_period`
+///   is zero or longer than the TRIX series).
 ///
 /// ## Returns
-/// - **`Ok(TrixOutput)`** on success, matching the input length,
-///   with `NaN` until triple-EMA is fully initialized plus 1 bar for the ROC.
+/// - **`Ok(TrixOutput)`** on success, containing two `Vec<f64>`:
+///   - `trix`: TRIX in percent, `NaN` until triple-EMA is fully initialized plus 1 bar
+///     for the ROC.
+///   - `signal`: The signal line, `NaN` for a further `signal_period - 1` bars.
+///
+///   Both match the input length. `trix` replaces the single `values` field of earlier
+///   versions.
 /// - **`Err(TrixError)`** otherwise.
+use crate::indicators::sma::{sma, SmaError, SmaInput, SmaParams};
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -35,17 +47,22 @@ pub enum TrixData<'a> {
 
 #[derive(Debug, Clone)]
 pub struct TrixOutput {
-    pub values: Vec<f64>,
+    pub trix: Vec<f64>,
+    pub signal: Vec<f64>,
 }
 
 #[derive(Debug, Clone)]
 pub struct TrixParams {
     pub period: Option<usize>,
+    pub signal_period: Option<usize>,
 }
 
 impl Default for TrixParams {
     fn default() -> Self {
-        Self { period: Some(18) }
+        Self {
+            period: Some(18),
+            signal_period: Some(9),
+        }
     }
 }
 
@@ -85,6 +102,12 @@ impl<'a> TrixInput<'a> {
             .period
             .unwrap_or_else(|| TrixParams::default().period.unwrap())
     }
+
+    pub fn get_signal_period(&self) -> usize {
+        self.params
+            .signal_period
+            .unwrap_or_else(|| TrixParams::default().signal_period.unwrap())
+    }
 }
 
 #[derive(Debug, Error)]
@@ -97,6 +120,8 @@ pub enum TrixError {
     NotEnoughValidData { needed: usize, valid: usize },
     #[error("trix: All values are NaN.")]
     AllValuesNaN,
+    #[error("trix: {0}")]
+    Sma(#[from] SmaError),
 }
 
 #[inline]
@@ -155,6 +180,7 @@ pub fn trix(input: &TrixInput) -> Result<TrixOutput, TrixError> {
     let needed = 3 * (period - 1) + 1;
     let valid_len = data.len() - first_valid_idx;
     if valid_len < needed {
+        not_enough_valid_data("trix", needed, valid_len);
         return Err(TrixError::NotEnoughValidData {
             needed,
             valid: valid_len,
@@ -173,8 +199,17 @@ pub fn trix(input: &TrixInput) -> Result<TrixOutput, TrixError> {
         }
     }
 
+    let signal = sma(&SmaInput::from_slice(
+        &trix_values,
+        SmaParams {
+            period: Some(input.get_signal_period()),
+        },
+    ))?
+    .values;
+
     Ok(TrixOutput {
-        values: trix_values,
+        trix: trix_values,
+        signal,
     })
 }
 
@@ -188,62 +223,66 @@ mod tests {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
         let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
 
-        let default_params = TrixParams { period: None };
+        let default_params = TrixParams {
+            period: None,
+            ..TrixParams::default()
+        };
         let input_default = TrixInput::from_candles(&candles, "close", default_params);
         let output_default = trix(&input_default).expect("Failed TRIX with default params");
-        assert_eq!(output_default.values.len(), candles.close.len());
+        assert_eq!(output_default.trix.len(), candles.close.len());
 
-        let params_period_14 = TrixParams { period: Some(14) };
+        let params_period_14 = TrixParams {
+            period: Some(14),
+            ..TrixParams::default()
+        };
         let input_period_14 = TrixInput::from_candles(&candles, "hl2", params_period_14);
         let output_period_14 =
             trix(&input_period_14).expect("Failed TRIX with period=14, source=hl2");
-        assert_eq!(output_period_14.values.len(), candles.close.len());
+        assert_eq!(output_period_14.trix.len(), candles.close.len());
 
-        let params_custom = TrixParams { period: Some(20) };
+        let params_custom = TrixParams {
+            period: Some(20),
+            ..TrixParams::default()
+        };
         let input_custom = TrixInput::from_candles(&candles, "hlc3", params_custom);
         let output_custom = trix(&input_custom).expect("Failed TRIX fully custom");
-        assert_eq!(output_custom.values.len(), candles.close.len());
+        assert_eq!(output_custom.trix.len(), candles.close.len());
     }
 
     #[test]
-    #[ignore]
     fn test_trix_accuracy() {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
         let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
-        let close_prices = candles
-            .select_candle_field("close")
-            .expect("Failed to extract close prices");
-
-        let params = TrixParams { period: Some(18) };
-        let input = TrixInput::from_candles(&candles, "close", params);
-        let trix_result = trix(&input).expect("Failed to calculate TRIX");
-
-        assert_eq!(
-            trix_result.values.len(),
-            close_prices.len(),
-            "TRIX length mismatch"
-        );
-
-        let expected_last_five = [
-            -16.03083789275206,
-            -15.93477668222043,
-            -15.794825711480387,
-            -15.587573840557534,
-            -15.416073398576424,
+        let input = TrixInput::with_default_candles(&candles);
+        let result = trix(&input).expect("Failed to calculate TRIX");
+        assert_eq!(result.trix.len(), candles.close.len());
+        assert_eq!(result.signal.len(), candles.close.len());
+
+        // Reference: TA-Lib 0.4.0 `TA_TRIX(close, 18)`, and `TA_SMA(.., 9)` over its output
+        // for the signal line.
+        let expected_trix = [
+            -0.1603083789275206,
+            -0.1593477668222043,
+            -0.15794825711480387,
+            -0.15587573840557534,
+            -0.15416073398576424,
         ];
-        assert!(trix_result.values.len() >= 5, "TRIX length too short");
-        let start_index = trix_result.values.len() - 5;
-        let result_last_five = &trix_result.values[start_index..];
-        for (i, &value) in result_last_five.iter().enumerate() {
-            let expected_value = expected_last_five[i];
-            assert!(
-                (value - expected_value).abs() < 1e-6,
-                "TRIX mismatch at index {}: expected {}, got {}",
-                i,
-                expected_value,
-                value
-            );
-        }
+        let expected_signal = [
+            -0.14958052513537945,
+            -0.15291386794204687,
+            -0.15533398452462924,
+            -0.1569327819788827,
+            -0.15762681126724068,
+        ];
+        let start = result.trix.len() - 5;
+        crate::assert_slice_close!(result.trix[start..], expected_trix, 1e-10);
+        crate::assert_slice_close!(result.signal[start..], expected_signal, 1e-10);
+
+        assert!(result.trix[..52].iter().all(|v| v.is_nan()));
+        assert!(!result.trix[52].is_nan());
+        assert!(result.signal[..60].iter().all(|v| v.is_nan()));
+        let first_window = result.trix[52..=60].iter().sum::<f64>() / 9.0;
+        assert!((result.signal[60] - first_window).abs() < 1e-12);
     }
 
     #[test]
@@ -269,9 +308,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_trix_invalid_signal_period() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let params = TrixParams {
+            signal_period: Some(0),
+            ..TrixParams::default()
+        };
+        let input = TrixInput::from_candles(&candles, "close", params);
+        assert!(matches!(trix(&input), Err(TrixError::Sma(_))));
+    }
+
     #[test]
     fn test_trix_empty_data() {
-        let params = TrixParams { period: Some(18) };
+        let params = TrixParams {
+            period: Some(18),
+            ..TrixParams::default()
+        };
         let input_data: [f64; 0] = [];
         let input = TrixInput::from_slice(&input_data, params);
         let result = trix(&input);
@@ -280,7 +334,10 @@ mod tests {
 
     #[test]
     fn test_trix_zero_period() {
-        let params = TrixParams { period: Some(0) };
+        let params = TrixParams {
+            period: Some(0),
+            ..TrixParams::default()
+        };
         let input_data = [1.0, 2.0, 3.0];
         let input = TrixInput::from_slice(&input_data, params);
         let result = trix(&input);
@@ -289,7 +346,10 @@ mod tests {
 
     #[test]
     fn test_trix_period_exceeds_length() {
-        let params = TrixParams { period: Some(100) };
+        let params = TrixParams {
+            period: Some(100),
+            ..TrixParams::default()
+        };
         let input_data = [1.0, 2.0, 3.0];
         let input = TrixInput::from_slice(&input_data, params);
         let result = trix(&input);
@@ -298,7 +358,10 @@ mod tests {
 
     #[test]
     fn test_trix_all_nan() {
-        let params = TrixParams { period: Some(18) };
+        let params = TrixParams {
+            period: Some(18),
+            ..TrixParams::default()
+        };
         let input_data = [f64::NAN, f64::NAN, f64::NAN];
         let input = TrixInput::from_slice(&input_data, params);
         let result = trix(&input);
@@ -307,7 +370,10 @@ mod tests {
 
     #[test]
     fn test_trix_not_enough_valid_data() {
-        let params = TrixParams { period: Some(18) };
+        let params = TrixParams {
+            period: Some(18),
+            ..TrixParams::default()
+        };
         let input_data = [f64::NAN; 30];
         let mut valid_data = input_data.clone();
         valid_data[25] = 50.0;
@@ -321,7 +387,10 @@ mod tests {
 
     #[test]
     fn test_trix_small_dataset() {
-        let params = TrixParams { period: Some(18) };
+        let params = TrixParams {
+            period: Some(18),
+            ..TrixParams::default()
+        };
         let input_data = [1.0, 2.0, 3.0, 4.0, 5.0];
         let input = TrixInput::from_slice(&input_data, params);
         let result = trix(&input);
@@ -332,12 +401,20 @@ mod tests {
     fn test_trix_reinput() {
         let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
         let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
-        let params = TrixParams { period: Some(10) };
+        let params = TrixParams {
+            period: Some(10),
+            ..TrixParams::default()
+        };
         let input = TrixInput::from_candles(&candles, "close", params);
         let first_result = trix(&input).expect("First TRIX calculation failed");
-        let second_input =
-            TrixInput::from_slice(&first_result.values, TrixParams { period: Some(10) });
+        let second_input = TrixInput::from_slice(
+            &first_result.trix,
+            TrixParams {
+                period: Some(10),
+                ..TrixParams::default()
+            },
+        );
         let second_result = trix(&second_input).expect("Second TRIX calculation failed");
-        assert_eq!(first_result.values.len(), second_result.values.len());
+        assert_eq!(first_result.trix.len(), second_result.trix.len());
     }
 }
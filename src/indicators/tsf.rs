@@ -19,6 +19,7 @@
 ///   with leading `NaN`s until the forecast window is filled.
 /// - **`Err(TsfError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum TsfData<'a> {
@@ -121,6 +122,7 @@ pub fn tsf(input: &TsfInput) -> Result<TsfOutput, TsfError> {
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("tsf", period, data.len() - first_valid_idx);
         return Err(TsfError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -26,6 +26,7 @@ use crate::indicators::ema::{ema, EmaData, EmaError, EmaInput, EmaOutput, EmaPar
 /// - **`Err(TsiError)`** otherwise.
 use crate::indicators::mom::{mom, MomData, MomError, MomInput, MomOutput, MomParams};
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -154,6 +155,7 @@ pub fn tsi(input: &TsiInput) -> Result<TsiOutput, TsiError> {
 
     let needed = 1 + long_period + short_period;
     if (data.len() - first_valid_idx) < needed {
+        not_enough_valid_data("tsi", needed, data.len() - first_valid_idx);
         return Err(TsiError::NotEnoughValidData {
             needed,
             valid: data.len() - first_valid_idx,
@@ -174,6 +176,7 @@ pub fn tsi(input: &TsiInput) -> Result<TsiOutput, TsiError> {
         &mom_output.values,
         EmaParams {
             period: Some(long_period),
+            ..EmaParams::default()
         },
     );
     let ema_long_numer: EmaOutput = ema(&ema_long_input_numer)?;
@@ -182,6 +185,7 @@ pub fn tsi(input: &TsiInput) -> Result<TsiOutput, TsiError> {
         &ema_long_numer.values,
         EmaParams {
             period: Some(short_period),
+            ..EmaParams::default()
         },
     );
     let ema_short_numer: EmaOutput = ema(&ema_short_input_numer)?;
@@ -190,6 +194,7 @@ pub fn tsi(input: &TsiInput) -> Result<TsiOutput, TsiError> {
         &abs_mom_values,
         EmaParams {
             period: Some(long_period),
+            ..EmaParams::default()
         },
     );
     let ema_long_denom: EmaOutput = ema(&ema_long_input_denom)?;
@@ -198,6 +203,7 @@ pub fn tsi(input: &TsiInput) -> Result<TsiOutput, TsiError> {
         &ema_long_denom.values,
         EmaParams {
             period: Some(short_period),
+            ..EmaParams::default()
         },
     );
     let ema_short_denom: EmaOutput = ema(&ema_short_input_denom)?;
@@ -19,6 +19,7 @@
 ///   with initial values set to `false` until enough data is accumulated.
 /// - **`Err(TtmTrendError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -130,6 +131,7 @@ pub fn ttm_trend(input: &TtmTrendInput) -> Result<TtmTrendOutput, TtmTrendError>
     };
 
     if (data_len - first_valid_idx) < period {
+        not_enough_valid_data("ttm_trend", period, data_len - first_valid_idx);
         return Err(TtmTrendError::NotEnoughValidData {
             needed: period,
             valid: data_len - first_valid_idx,
@@ -21,6 +21,7 @@ use crate::indicators::utility_functions::max_rolling;
 ///   with leading `NaN`s until the UI window is filled.
 /// - **`Err(UiError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -134,6 +135,7 @@ pub fn ui(input: &UiInput) -> Result<UiOutput, UiError> {
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("ui", period, data.len() - first_valid_idx);
         return Err(UiError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
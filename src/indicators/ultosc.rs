@@ -4,16 +4,21 @@
 /// capture short-term, intermediate-term, and long-term market volatility. It combines these
 /// periods into one oscillator value ranging from 0 to 100. The formula calculates a running
 /// total of `closeMinusTrueLow` divided by `trueRange` for each of the specified periods,
-/// then combines these ratios with weights of 4 (short), 2 (medium), and 1 (long).
+/// then combines these ratios as a weighted average, by default with weights of 4 (short),
+/// 2 (medium), and 1 (long).
 ///
 /// ## Parameters
 /// - **timeperiod1**: The shortest window size (default = 7).
 /// - **timeperiod2**: The medium window size (default = 14).
 /// - **timeperiod3**: The longest window size (default = 28).
+/// - **weight1**, **weight2**, **weight3**: Weights of the three windows' ratios
+///   (defaults = 4, 2, 1).
 ///
 /// ## Errors
 /// - **EmptyData**: ultosc: Input data slice is empty.
 /// - **InvalidPeriods**: ultosc: One or more periods is zero or exceeds the data length.
+/// - **InvalidWeights**: ultosc: A weight is negative or not finite, or the weights sum to
+///   zero.
 /// - **NotEnoughValidData**: ultosc: Fewer than the largest period valid data points remain
 ///   after the first valid index (which requires i-1 to be valid).
 /// - **AllValuesNaN**: ultosc: All input data values (or their necessary preceding values) are `NaN`.
@@ -23,6 +28,7 @@
 ///   with leading `NaN`s until enough bars have passed to fill the largest window.
 /// - **`Err(UltOscError)`** otherwise.
 use crate::utilities::data_loader::Candles;
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -50,6 +56,9 @@ pub struct UltOscParams {
     pub timeperiod1: Option<usize>,
     pub timeperiod2: Option<usize>,
     pub timeperiod3: Option<usize>,
+    pub weight1: Option<f64>,
+    pub weight2: Option<f64>,
+    pub weight3: Option<f64>,
 }
 
 impl Default for UltOscParams {
@@ -58,6 +67,9 @@ impl Default for UltOscParams {
             timeperiod1: Some(7),
             timeperiod2: Some(14),
             timeperiod3: Some(28),
+            weight1: Some(4.0),
+            weight2: Some(2.0),
+            weight3: Some(1.0),
         }
     }
 }
@@ -128,6 +140,24 @@ impl<'a> UltOscInput<'a> {
             .timeperiod3
             .unwrap_or_else(|| UltOscParams::default().timeperiod3.unwrap())
     }
+
+    pub fn get_weight1(&self) -> f64 {
+        self.params
+            .weight1
+            .unwrap_or_else(|| UltOscParams::default().weight1.unwrap())
+    }
+
+    pub fn get_weight2(&self) -> f64 {
+        self.params
+            .weight2
+            .unwrap_or_else(|| UltOscParams::default().weight2.unwrap())
+    }
+
+    pub fn get_weight3(&self) -> f64 {
+        self.params
+            .weight3
+            .unwrap_or_else(|| UltOscParams::default().weight3.unwrap())
+    }
 }
 
 #[derive(Debug, Error)]
@@ -141,6 +171,8 @@ pub enum UltOscError {
         p3: usize,
         data_len: usize,
     },
+    #[error("ultosc: Invalid weights: w1 = {w1}, w2 = {w2}, w3 = {w3}")]
+    InvalidWeights { w1: f64, w2: f64, w3: f64 },
     #[error("ultosc: Not enough valid data: needed = {needed}, valid = {valid}")]
     NotEnoughValidData { needed: usize, valid: usize },
     #[error("ultosc: All values are NaN (or their preceding data is NaN).")]
@@ -181,6 +213,14 @@ pub fn ultosc(input: &UltOscInput) -> Result<UltOscOutput, UltOscError> {
         });
     }
 
+    let w1 = input.get_weight1();
+    let w2 = input.get_weight2();
+    let w3 = input.get_weight3();
+    let weight_sum = w1 + w2 + w3;
+    if [w1, w2, w3].iter().any(|w| !w.is_finite() || *w < 0.0) || weight_sum <= 0.0 {
+        return Err(UltOscError::InvalidWeights { w1, w2, w3 });
+    }
+
     let largest_period = p1.max(p2.max(p3));
 
     let first_possible = match (1..length).find(|&i| {
@@ -197,6 +237,11 @@ pub fn ultosc(input: &UltOscInput) -> Result<UltOscOutput, UltOscError> {
 
     let start_idx = first_possible + (largest_period - 1);
     if start_idx >= length {
+        not_enough_valid_data(
+            "ultosc",
+            largest_period,
+            length.saturating_sub(first_possible),
+        );
         return Err(UltOscError::NotEnoughValidData {
             needed: largest_period,
             valid: length.saturating_sub(first_possible),
@@ -269,17 +314,21 @@ pub fn ultosc(input: &UltOscInput) -> Result<UltOscOutput, UltOscError> {
         }
 
         let v1 = if sum1_b != 0.0 {
-            4.0 * (sum1_a / sum1_b)
+            w1 * (sum1_a / sum1_b)
         } else {
             0.0
         };
         let v2 = if sum2_b != 0.0 {
-            2.0 * (sum2_a / sum2_b)
+            w2 * (sum2_a / sum2_b)
         } else {
             0.0
         };
-        let v3 = if sum3_b != 0.0 { sum3_a / sum3_b } else { 0.0 };
-        out_values[today] = 100.0 * (v1 + v2 + v3) / 7.0;
+        let v3 = if sum3_b != 0.0 {
+            w3 * (sum3_a / sum3_b)
+        } else {
+            0.0
+        };
+        out_values[today] = 100.0 * (v1 + v2 + v3) / weight_sum;
 
         let trailing_1 = today as isize - (p1 as isize) + 1;
         if trailing_1 >= 0 && (trailing_1 as usize) < length {
@@ -320,6 +369,30 @@ pub fn ultosc(input: &UltOscInput) -> Result<UltOscOutput, UltOscError> {
     Ok(UltOscOutput { values: out_values })
 }
 
+/// Converts ULTOSC values into overbought/oversold crossover signals (classically 30 and
+/// 70).
+///
+/// Returns `1` on the bar where ULTOSC crosses up out of the `oversold` zone (previous value
+/// at or below `oversold`, current value above it) and `-1` where it crosses down out of the
+/// `overbought` zone. All other bars, including those involving `NaN`, are `0`.
+#[inline]
+pub fn ultosc_signals(values: &[f64], oversold: f64, overbought: f64) -> Vec<i8> {
+    let mut signals = vec![0i8; values.len()];
+    for i in 1..values.len() {
+        let prev = values[i - 1];
+        let curr = values[i];
+        if prev.is_nan() || curr.is_nan() {
+            continue;
+        }
+        if prev <= oversold && curr > oversold {
+            signals[i] = 1;
+        } else if prev >= overbought && curr < overbought {
+            signals[i] = -1;
+        }
+    }
+    signals
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -334,6 +407,9 @@ mod tests {
             timeperiod1: None,
             timeperiod2: None,
             timeperiod3: None,
+            weight1: None,
+            weight2: None,
+            weight3: None,
         };
         let input_default =
             UltOscInput::from_candles(&candles, "high", "low", "close", default_params);
@@ -344,6 +420,7 @@ mod tests {
             timeperiod1: Some(5),
             timeperiod2: Some(10),
             timeperiod3: Some(20),
+            ..UltOscParams::default()
         };
         let input_custom =
             UltOscInput::from_candles(&candles, "high", "low", "close", custom_params);
@@ -360,6 +437,7 @@ mod tests {
             timeperiod1: Some(7),
             timeperiod2: Some(14),
             timeperiod3: Some(28),
+            ..UltOscParams::default()
         };
         let input = UltOscInput::from_candles(&candles, "high", "low", "close", params);
         let result = ultosc(&input).expect("Failed to calculate ULTOSC");
@@ -386,6 +464,62 @@ mod tests {
                 val
             );
         }
+
+        let default_result = ultosc(&UltOscInput::with_default_candles(&candles))
+            .expect("Failed to calculate ULTOSC with defaults");
+        crate::assert_slice_close!(default_result.values, result.values, 0.0);
+    }
+
+    #[test]
+    fn test_ultosc_custom_periods_and_weights() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let params = UltOscParams {
+            timeperiod1: Some(5),
+            timeperiod2: Some(10),
+            timeperiod3: Some(20),
+            weight1: Some(1.0),
+            weight2: Some(1.0),
+            weight3: Some(1.0),
+        };
+        let input = UltOscInput::from_candles(&candles, "high", "low", "close", params);
+        let result = ultosc(&input).expect("Failed to calculate ULTOSC with custom params");
+        assert!(result.values[..20].iter().all(|v| v.is_nan()));
+        assert!(result.values[20..]
+            .iter()
+            .all(|v| v.is_finite() && (0.0..=100.0).contains(v)));
+    }
+
+    #[test]
+    fn test_ultosc_invalid_weights() {
+        let input_high = [1.0, 2.0, 3.0];
+        let input_low = [0.5, 1.5, 2.5];
+        let input_close = [0.8, 1.8, 2.8];
+        for (w1, w2, w3) in [(0.0, 0.0, 0.0), (-1.0, 2.0, 1.0), (f64::NAN, 2.0, 1.0)] {
+            let params = UltOscParams {
+                timeperiod1: Some(1),
+                timeperiod2: Some(2),
+                timeperiod3: Some(2),
+                weight1: Some(w1),
+                weight2: Some(w2),
+                weight3: Some(w3),
+            };
+            let input = UltOscInput::from_slices(&input_high, &input_low, &input_close, params);
+            assert!(matches!(
+                ultosc(&input),
+                Err(UltOscError::InvalidWeights { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_ultosc_signals() {
+        let values = [f64::NAN, 25.0, 35.0, 75.0, 65.0, 30.0, 30.0, 31.0];
+        assert_eq!(
+            ultosc_signals(&values, 30.0, 70.0),
+            vec![0, 0, 1, 0, -1, 0, 0, 1]
+        );
     }
 
     #[test]
@@ -407,6 +541,7 @@ mod tests {
             timeperiod1: Some(0),
             timeperiod2: Some(14),
             timeperiod3: Some(28),
+            ..UltOscParams::default()
         };
         let input = UltOscInput::from_slices(&input_high, &input_low, &input_close, params);
         let result = ultosc(&input);
@@ -422,6 +557,7 @@ mod tests {
             timeperiod1: Some(7),
             timeperiod2: Some(14),
             timeperiod3: Some(28),
+            ..UltOscParams::default()
         };
         let input = UltOscInput::from_slices(&input_high, &input_low, &input_close, params);
         let result = ultosc(&input);
@@ -1,3 +1,4 @@
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::collections::VecDeque;
 use thiserror::Error;
 
@@ -31,6 +32,7 @@ pub fn sum_rolling(data: &[f64], period: usize) -> Result<Vec<f64>, RollingError
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("sum_rolling", period, data.len() - first_valid_idx);
         return Err(RollingError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -74,6 +76,7 @@ pub fn max_rolling(data: &[f64], period: usize) -> Result<Vec<f64>, RollingError
         None => return Err(RollingError::AllValuesNaN),
     };
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("max_rolling", period, data.len() - first_valid_idx);
         return Err(RollingError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -136,6 +139,7 @@ pub fn min_rolling(data: &[f64], period: usize) -> Result<Vec<f64>, RollingError
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("min_rolling", period, data.len() - first_valid_idx);
         return Err(RollingError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -180,6 +184,151 @@ pub fn min_rolling(data: &[f64], period: usize) -> Result<Vec<f64>, RollingError
     Ok(output)
 }
 
+/// Marks bars whose value is strictly greater than the `left` bars before it and the
+/// `right` bars after it. The pivot value is reported at the pivot bar itself, so the
+/// last `right` bars can never be confirmed.
+#[inline]
+pub fn pivot_highs(data: &[f64], left: usize, right: usize) -> Vec<Option<f64>> {
+    find_pivots(data, left, right, |candidate, other| candidate > other)
+}
+
+/// Marks bars whose value is strictly less than the `left` bars before it and the
+/// `right` bars after it. See [`pivot_highs`] for placement rules.
+#[inline]
+pub fn pivot_lows(data: &[f64], left: usize, right: usize) -> Vec<Option<f64>> {
+    find_pivots(data, left, right, |candidate, other| candidate < other)
+}
+
+#[inline]
+fn find_pivots<F>(data: &[f64], left: usize, right: usize, beats: F) -> Vec<Option<f64>>
+where
+    F: Fn(f64, f64) -> bool,
+{
+    let len = data.len();
+    let mut output = vec![None; len];
+    if len <= left + right {
+        return output;
+    }
+
+    for i in left..(len - right) {
+        let candidate = data[i];
+        if candidate.is_nan() {
+            continue;
+        }
+        let is_pivot = data[i - left..i]
+            .iter()
+            .chain(data[i + 1..=i + right].iter())
+            .all(|&other| !other.is_nan() && beats(candidate, other));
+        if is_pivot {
+            output[i] = Some(candidate);
+        }
+    }
+
+    output
+}
+
+/// Runs `compute` once per period and collects the successful results alongside their
+/// period. Periods for which `compute` returns an error are skipped.
+#[inline]
+pub fn sweep_periods<T, E, F>(
+    periods: impl IntoIterator<Item = usize>,
+    mut compute: F,
+) -> Vec<(usize, T)>
+where
+    F: FnMut(usize) -> Result<T, E>,
+{
+    periods
+        .into_iter()
+        .filter_map(|period| compute(period).ok().map(|output| (period, output)))
+        .collect()
+}
+
+/// Marks crossings of `a` over `b`: `1` on the bar where `a` moves from at or below `b`
+/// to strictly above it, `-1` on the opposite move, `0` otherwise (including bars where
+/// either series is `NaN` on the current or previous bar).
+#[inline]
+pub fn crossover_signals(a: &[f64], b: &[f64]) -> Vec<i8> {
+    let len = a.len().min(b.len());
+    let mut signals = vec![0i8; len];
+    for i in 1..len {
+        let (prev_a, prev_b, curr_a, curr_b) = (a[i - 1], b[i - 1], a[i], b[i]);
+        if prev_a.is_nan() || prev_b.is_nan() || curr_a.is_nan() || curr_b.is_nan() {
+            continue;
+        }
+        if prev_a <= prev_b && curr_a > curr_b {
+            signals[i] = 1;
+        } else if prev_a >= prev_b && curr_a < curr_b {
+            signals[i] = -1;
+        }
+    }
+    signals
+}
+
+/// Blends several per-bar signals into one score in `[-1, 1]`. Each vote is a signal
+/// series and its weight; only the sign of a signal counts, so `-1/0/1` votes and
+/// `-100/0/100` pattern outputs can be mixed. A bar's score is the weighted average of
+/// the votes covering it, clamped to `[-1, 1]`.
+///
+/// The output is as long as the longest vote, with all votes aligned at index 0. A vote
+/// shorter than the output does not take part past its end, and a bar whose
+/// contributing weights sum to zero (or that no vote covers) is `NaN`.
+#[inline]
+pub fn consensus(votes: &[(Vec<i8>, f64)]) -> Vec<f64> {
+    let len = votes
+        .iter()
+        .map(|(signal, _)| signal.len())
+        .max()
+        .unwrap_or(0);
+    let mut weighted = vec![0.0; len];
+    let mut total_weight = vec![0.0; len];
+    for (signal, weight) in votes {
+        for (i, &s) in signal.iter().enumerate() {
+            weighted[i] += weight * s.signum() as f64;
+            total_weight[i] += weight;
+        }
+    }
+    weighted
+        .iter()
+        .zip(&total_weight)
+        .map(|(&w, &t)| {
+            if t == 0.0 {
+                f64::NAN
+            } else {
+                (w / t).clamp(-1.0, 1.0)
+            }
+        })
+        .collect()
+}
+
+/// Distance between `close` and a stop level as a fraction of `close`:
+/// `|close - stop| / close`. Works for any stop series (`devstop`, `kaufmanstop`,
+/// `safezonestop`, ...) and makes stops comparable across assets. Bars where either
+/// input is `NaN` or `close` is zero yield `NaN`. If the slices differ in length, the
+/// shorter length is used.
+#[inline]
+pub fn stop_distance_pct(stop: &[f64], close: &[f64]) -> Vec<f64> {
+    stop.iter()
+        .zip(close)
+        .map(|(&s, &c)| {
+            if c == 0.0 {
+                f64::NAN
+            } else {
+                (c - s).abs() / c
+            }
+        })
+        .collect()
+}
+
+/// The finite values of `window` sorted ascending with [`f64::total_cmp`]. `NaN`s and
+/// infinities are dropped, so the result is deterministic whatever the window holds.
+/// Intended for median and percentile style indicators.
+#[inline]
+pub fn sorted_window(window: &[f64]) -> Vec<f64> {
+    let mut sorted: Vec<f64> = window.iter().cloned().filter(|v| v.is_finite()).collect();
+    sorted.sort_by(f64::total_cmp);
+    sorted
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,4 +424,91 @@ mod tests {
             err
         );
     }
+
+    #[test]
+    fn test_pivot_highs_and_lows_zigzag() {
+        let data = [1.0, 2.0, 3.0, 2.0, 1.0, 2.0, 3.0, 4.0, 3.0, 2.0, 3.0];
+        let highs = pivot_highs(&data, 2, 2);
+        let lows = pivot_lows(&data, 2, 2);
+        assert_eq!(highs.len(), data.len());
+        assert_eq!(lows.len(), data.len());
+
+        for (i, value) in highs.iter().enumerate() {
+            match i {
+                2 => assert_eq!(*value, Some(3.0)),
+                7 => assert_eq!(*value, Some(4.0)),
+                _ => assert!(value.is_none(), "Unexpected pivot high at index {}", i),
+            }
+        }
+        for (i, value) in lows.iter().enumerate() {
+            match i {
+                4 => assert_eq!(*value, Some(1.0)),
+                _ => assert!(value.is_none(), "Unexpected pivot low at index {}", i),
+            }
+        }
+    }
+
+    #[test]
+    fn test_pivot_requires_confirming_bars() {
+        let data = [1.0, 2.0, 3.0, 4.0, 5.0, 4.0];
+        let highs = pivot_highs(&data, 2, 2);
+        assert!(highs.iter().all(|v| v.is_none()));
+        let highs_short = pivot_highs(&data, 2, 1);
+        assert_eq!(highs_short[4], Some(5.0));
+    }
+
+    #[test]
+    fn test_crossover_signals() {
+        let a = [f64::NAN, 1.0, 3.0, 3.0, 2.0, 2.0, 1.0];
+        let b = [2.0, 2.0, 2.0, 2.0, 2.0, 2.0, 2.0];
+        let signals = crossover_signals(&a, &b);
+        assert_eq!(signals, vec![0, 0, 1, 0, 0, 0, -1]);
+    }
+
+    #[test]
+    fn test_consensus_opposing_votes_cancel() {
+        let buy = vec![1, 1, 0, -1];
+        let sell = vec![-1, -1, 0, 1];
+        let score = consensus(&[(buy, 1.0), (sell, 1.0)]);
+        assert_eq!(score, vec![0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_consensus_weights_and_alignment() {
+        let crossover = vec![1, 0, -1, 1];
+        let pattern = vec![100, -100, 0];
+        let score = consensus(&[(crossover, 3.0), (pattern, 1.0)]);
+        assert_eq!(score.len(), 4);
+        assert_eq!(score[0], 1.0);
+        assert_eq!(score[1], -0.25);
+        assert_eq!(score[2], -0.75);
+        assert_eq!(
+            score[3], 1.0,
+            "Past the shorter vote only the longer one counts"
+        );
+
+        let unweighted = consensus(&[(vec![1, -1], 0.0)]);
+        assert!(unweighted.iter().all(|v| v.is_nan()));
+        assert!(consensus(&[]).is_empty());
+        assert_eq!(
+            consensus(&[(vec![1, 1], 2.0), (vec![1, -1], -1.0)]),
+            vec![1.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn test_sorted_window_drops_nan() {
+        let window = [3.0, f64::NAN, -1.0, 2.0, -0.0, 0.0, f64::INFINITY];
+        let sorted = sorted_window(&window);
+        assert_eq!(sorted, vec![-1.0, -0.0, 0.0, 2.0, 3.0]);
+        assert!(sorted[1].is_sign_negative() && sorted[2].is_sign_positive());
+
+        let reordered = [0.0, 2.0, f64::NAN, -0.0, 3.0, -1.0];
+        let again = sorted_window(&reordered);
+        assert_eq!(
+            again.iter().map(|v| v.to_bits()).collect::<Vec<_>>(),
+            sorted.iter().map(|v| v.to_bits()).collect::<Vec<_>>()
+        );
+        assert!(sorted_window(&[f64::NAN, f64::NAN]).is_empty());
+    }
 }
@@ -22,6 +22,7 @@
 ///   with leading `NaN`s until the rolling variance window is filled.
 /// - **`Err(VarError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -132,6 +133,7 @@ pub fn var(input: &VarInput) -> Result<VarOutput, VarError> {
     };
 
     if (data_len - first_valid_idx) < period {
+        not_enough_valid_data("var", period, data_len - first_valid_idx);
         return Err(VarError::NotEnoughValidData {
             needed: period,
             valid: data_len - first_valid_idx,
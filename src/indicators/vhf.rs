@@ -0,0 +1,253 @@
+/// # Vertical Horizontal Filter (VHF)
+///
+/// VHF tells trending markets from ranging ones by comparing the net span of the window
+/// with the distance travelled inside it:
+///
+/// ```ignore
+/// vhf = (highest_close - lowest_close) / sum(|close[i] - close[i - 1]|)
+/// ```
+///
+/// The highest and lowest close are taken over the last `period` bars, and the sum
+/// covers the `period` one-bar changes ending at the current bar. High values mean
+/// price moved mostly in one direction (trending). Low values mean it covered a lot of
+/// ground without getting far (ranging).
+///
+/// ## Parameters
+/// - **period**: Window length. Defaults to 28.
+///
+/// ## Errors
+/// - **EmptyData**: vhf: Input data slice is empty.
+/// - **InvalidPeriod**: vhf: `period` is zero or leaves no room for a full window of
+///   changes (`period + 1` points are needed).
+/// - **AllValuesNaN**: vhf: All input data values are `NaN`.
+/// - **NotEnoughValidData**: vhf: Fewer than `period + 1` valid data points remain after
+///   the first valid index.
+/// - **Rolling(...)**: vhf: Propagated error from the rolling max/min.
+///
+/// ## Returns
+/// - **`Ok(VhfOutput)`** on success, containing a `Vec<f64>` matching the input length,
+///   with leading `NaN`s for the first `period` valid bars. Flat windows (no movement)
+///   are also `NaN`.
+/// - **`Err(VhfError)`** otherwise.
+use crate::indicators::utility_functions::{max_rolling, min_rolling, RollingError};
+use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
+use crate::utilities::series::rolling_sum;
+use thiserror::Error;
+
+#[derive(Debug, Clone)]
+pub enum VhfData<'a> {
+    Candles {
+        candles: &'a Candles,
+        source: &'a str,
+    },
+    Slice(&'a [f64]),
+}
+
+#[derive(Debug, Clone)]
+pub struct VhfOutput {
+    pub values: Vec<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct VhfParams {
+    pub period: Option<usize>,
+}
+
+impl Default for VhfParams {
+    fn default() -> Self {
+        Self { period: Some(28) }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VhfInput<'a> {
+    pub data: VhfData<'a>,
+    pub params: VhfParams,
+}
+
+impl<'a> VhfInput<'a> {
+    pub fn from_candles(candles: &'a Candles, source: &'a str, params: VhfParams) -> Self {
+        Self {
+            data: VhfData::Candles { candles, source },
+            params,
+        }
+    }
+
+    pub fn from_slice(slice: &'a [f64], params: VhfParams) -> Self {
+        Self {
+            data: VhfData::Slice(slice),
+            params,
+        }
+    }
+
+    pub fn with_default_candles(candles: &'a Candles) -> Self {
+        Self {
+            data: VhfData::Candles {
+                candles,
+                source: "close",
+            },
+            params: VhfParams::default(),
+        }
+    }
+
+    pub fn get_period(&self) -> usize {
+        self.params
+            .period
+            .unwrap_or_else(|| VhfParams::default().period.unwrap())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum VhfError {
+    #[error("vhf: Empty data provided.")]
+    EmptyData,
+    #[error("vhf: Invalid period: period = {period}, data length = {data_len}")]
+    InvalidPeriod { period: usize, data_len: usize },
+    #[error("vhf: All values are NaN.")]
+    AllValuesNaN,
+    #[error("vhf: Not enough valid data: needed = {needed}, valid = {valid}")]
+    NotEnoughValidData { needed: usize, valid: usize },
+    #[error("vhf: {0}")]
+    Rolling(#[from] RollingError),
+}
+
+#[inline]
+pub fn vhf(input: &VhfInput) -> Result<VhfOutput, VhfError> {
+    let data: &[f64] = match &input.data {
+        VhfData::Candles { candles, source } => source_type(candles, source),
+        VhfData::Slice(slice) => slice,
+    };
+
+    if data.is_empty() {
+        return Err(VhfError::EmptyData);
+    }
+
+    let period = input.get_period();
+    if period == 0 || period >= data.len() {
+        return Err(VhfError::InvalidPeriod {
+            period,
+            data_len: data.len(),
+        });
+    }
+
+    let first_valid_idx = match data.iter().position(|&x| !x.is_nan()) {
+        Some(idx) => idx,
+        None => return Err(VhfError::AllValuesNaN),
+    };
+
+    let needed = period + 1;
+    let valid_len = data.len() - first_valid_idx;
+    if valid_len < needed {
+        not_enough_valid_data("vhf", needed, valid_len);
+        return Err(VhfError::NotEnoughValidData {
+            needed,
+            valid: valid_len,
+        });
+    }
+
+    let highest = max_rolling(data, period)?;
+    let lowest = min_rolling(data, period)?;
+
+    let mut changes = vec![f64::NAN; data.len()];
+    for i in 1..data.len() {
+        changes[i] = (data[i] - data[i - 1]).abs();
+    }
+    let travelled = rolling_sum(&changes, period);
+
+    let mut values = vec![f64::NAN; data.len()];
+    for i in (first_valid_idx + period)..data.len() {
+        let path = travelled[i];
+        if !path.is_nan() && path != 0.0 {
+            values[i] = (highest[i] - lowest[i]) / path;
+        }
+    }
+
+    Ok(VhfOutput { values })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::data_loader::read_candles_from_csv;
+
+    #[test]
+    fn test_vhf_hand_computed() {
+        let data = [1.0, 3.0, 2.0, 5.0, 4.0, 6.0];
+        let input = VhfInput::from_slice(&data, VhfParams { period: Some(3) });
+        let result = vhf(&input).expect("Failed to calculate VHF");
+        assert_eq!(result.values.len(), data.len());
+        assert!(result.values[..3].iter().all(|v| v.is_nan()));
+        crate::assert_slice_close!(result.values[3..], [0.5, 0.6, 1.0 / 3.0], 1e-12);
+    }
+
+    #[test]
+    fn test_vhf_accuracy() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = VhfInput::with_default_candles(&candles);
+        let result = vhf(&input).expect("Failed to calculate VHF");
+        assert_eq!(result.values.len(), candles.close.len());
+
+        let expected_last_five = [
+            0.31620018057526117,
+            0.3239511067063099,
+            0.31662146967392024,
+            0.28646074475825123,
+            0.267097540064631,
+        ];
+        let start = result.values.len() - 5;
+        crate::assert_slice_close!(result.values[start..], expected_last_five, 1e-10);
+        assert!(result.values[..28].iter().all(|v| v.is_nan()));
+        assert!((result.values[28] - 0.30640891954760596).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_vhf_leading_nan_and_flat() {
+        let data = [f64::NAN, f64::NAN, 1.0, 2.0, 3.0, 3.0, 3.0, 3.0];
+        let input = VhfInput::from_slice(&data, VhfParams { period: Some(2) });
+        let result = vhf(&input).expect("Failed to calculate VHF");
+        assert!(result.values[..4].iter().all(|v| v.is_nan()));
+        assert_eq!(result.values[4], 0.5);
+        assert_eq!(result.values[5], 0.0);
+        assert!(result.values[6].is_nan(), "Flat window has no movement");
+        assert!(result.values[7].is_nan(), "Flat window has no movement");
+    }
+
+    #[test]
+    fn test_vhf_errors() {
+        let empty: [f64; 0] = [];
+        assert!(matches!(
+            vhf(&VhfInput::from_slice(&empty, VhfParams::default())),
+            Err(VhfError::EmptyData)
+        ));
+
+        let data = [1.0, 2.0, 3.0];
+        assert!(matches!(
+            vhf(&VhfInput::from_slice(&data, VhfParams { period: Some(0) })),
+            Err(VhfError::InvalidPeriod { .. })
+        ));
+        assert!(matches!(
+            vhf(&VhfInput::from_slice(&data, VhfParams { period: Some(3) })),
+            Err(VhfError::InvalidPeriod { .. })
+        ));
+
+        let nan = [f64::NAN; 5];
+        assert!(matches!(
+            vhf(&VhfInput::from_slice(&nan, VhfParams { period: Some(2) })),
+            Err(VhfError::AllValuesNaN)
+        ));
+
+        let sparse = [f64::NAN, f64::NAN, f64::NAN, 1.0, 2.0];
+        assert!(matches!(
+            vhf(&VhfInput::from_slice(
+                &sparse,
+                VhfParams { period: Some(2) }
+            )),
+            Err(VhfError::NotEnoughValidData {
+                needed: 3,
+                valid: 2
+            })
+        ));
+    }
+}
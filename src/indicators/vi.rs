@@ -21,7 +21,9 @@
 /// - **`Ok(ViOutput)`** on success, containing `plus` and `minus` vectors
 ///   matching the input length, with leading `NaN`s until the period window is filled.
 /// - **`Err(ViError)`** otherwise.
+use crate::indicators::utility_functions::crossover_signals;
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::f64;
 use thiserror::Error;
 
@@ -143,6 +145,7 @@ pub fn vi(input: &ViInput) -> Result<ViOutput, ViError> {
     };
 
     if (length - first_valid_idx) < period {
+        not_enough_valid_data("vi", period, length - first_valid_idx);
         return Err(ViError::NotEnoughValidData {
             needed: period,
             valid: length - first_valid_idx,
@@ -188,6 +191,13 @@ pub fn vi(input: &ViInput) -> Result<ViOutput, ViError> {
     Ok(ViOutput { plus, minus })
 }
 
+/// Converts the Vortex lines into crossover signals: `1` where VI+ crosses above VI-,
+/// `-1` where it crosses below, `0` otherwise.
+#[inline]
+pub fn vortex_signals(output: &ViOutput) -> Vec<i8> {
+    crossover_signals(&output.plus, &output.minus)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,4 +293,25 @@ mod tests {
         let result_small = vi(&input_small);
         assert!(result_small.is_err());
     }
+
+    #[test]
+    fn test_vortex_signals() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = ViInput::with_default_candles(&candles);
+        let output = vi(&input).expect("Failed to calculate VI");
+        let signals = vortex_signals(&output);
+        assert_eq!(signals.len(), candles.close.len());
+
+        let first_valid = output.plus.iter().position(|v| !v.is_nan()).unwrap();
+        let first_signal = signals.iter().position(|&s| s != 0).unwrap();
+        assert!(first_signal > first_valid);
+        assert!(signals[..=first_valid].iter().all(|&s| s == 0));
+
+        let flat = ViOutput {
+            plus: vec![1.0; 10],
+            minus: vec![1.0; 10],
+        };
+        assert!(vortex_signals(&flat).iter().all(|&s| s == 0));
+    }
 }
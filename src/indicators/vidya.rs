@@ -22,6 +22,7 @@
 ///   with leading `NaN`s until the computation can start.
 /// - **`Err(VidyaError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum VidyaData<'a> {
@@ -159,6 +160,7 @@ pub fn vidya(input: &VidyaInput) -> Result<VidyaOutput, VidyaError> {
     };
 
     if (data.len() - first_valid_idx) < long_period {
+        not_enough_valid_data("vidya", long_period, data.len() - first_valid_idx);
         return Err(VidyaError::NotEnoughValidData {
             needed: long_period,
             valid: data.len() - first_valid_idx,
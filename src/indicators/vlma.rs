@@ -32,6 +32,7 @@ use crate::indicators::moving_averages::ma::{ma, MaData};
 ///   with leading `NaN`s until the initial valid window is filled.
 /// - **`Err(VlmaError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::error::Error;
 
 #[derive(Debug, Clone)]
@@ -185,6 +186,7 @@ pub fn vlma(input: &VlmaInput) -> Result<VlmaOutput, VlmaError> {
     };
 
     if (data.len() - first_valid_idx) < max_period {
+        not_enough_valid_data("vlma", max_period, data.len() - first_valid_idx);
         return Err(VlmaError::NotEnoughValidData {
             needed: max_period,
             valid: data.len() - first_valid_idx,
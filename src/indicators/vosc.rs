@@ -29,6 +29,7 @@
 ///   with leading `NaN`s until the first valid index + `long_period` - 1.
 /// - **`Err(VoscError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -158,6 +159,7 @@ pub fn vosc(input: &VoscInput) -> Result<VoscOutput, VoscError> {
     };
 
     if (data.len() - first_valid_idx) < long_period {
+        not_enough_valid_data("vosc", long_period, data.len() - first_valid_idx);
         return Err(VoscError::NotEnoughValidData {
             needed: long_period,
             valid: data.len() - first_valid_idx,
@@ -21,6 +21,7 @@
 ///   with leading `NaN`s until the filter can be calculated.
 /// - **`Err(VossError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::f64::consts::PI;
 use thiserror::Error;
 
@@ -149,6 +150,7 @@ pub fn voss(input: &VossInput) -> Result<VossOutput, VossError> {
     let min_index = period.max(5).max(order);
 
     if (data.len() - first_valid_idx) < min_index {
+        not_enough_valid_data("voss", min_index, data.len() - first_valid_idx);
         return Err(VossError::NotEnoughValidData {
             needed: min_index,
             valid: data.len() - first_valid_idx,
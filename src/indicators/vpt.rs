@@ -21,6 +21,7 @@
 ///   Jesse's final array (with the same shifting behavior).
 /// - **`Err(VptError)`** on failure.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -112,6 +113,7 @@ pub fn vpt(input: &VptInput) -> Result<VptOutput, VptError> {
         return Err(VptError::AllValuesNaN);
     }
     if valid_count < 2 {
+        not_enough_valid_data("vpt", 2, valid_count);
         return Err(VptError::NotEnoughValidData);
     }
 
@@ -248,6 +248,7 @@ pub fn vwmacd(input: &VwmacdInput) -> Result<VwmacdOutput, VwmacdError> {
         &macd_values,
         EmaParams {
             period: Some(signal),
+            ..EmaParams::default()
         },
     ))?
     .values;
@@ -31,6 +31,7 @@ use crate::indicators::moving_averages::ema::{ema, EmaError, EmaInput, EmaParams
 /// - **`Err(WavetrendError)`** otherwise.
 use crate::indicators::moving_averages::sma::{sma, SmaError, SmaInput, SmaParams};
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use thiserror::Error;
 
 #[derive(Debug, Clone)]
@@ -191,6 +192,7 @@ pub fn wavetrend(input: &WavetrendInput) -> Result<WavetrendOutput, WavetrendErr
 
     let valid = data.len() - first_valid_idx;
     if valid < needed {
+        not_enough_valid_data("wavetrend", needed, valid);
         return Err(WavetrendError::NotEnoughValidData { needed, valid });
     }
 
@@ -200,6 +202,7 @@ pub fn wavetrend(input: &WavetrendInput) -> Result<WavetrendOutput, WavetrendErr
         data_valid,
         EmaParams {
             period: Some(channel_len),
+            ..EmaParams::default()
         },
     );
     let esa_output = ema(&esa_input)?;
@@ -216,6 +219,7 @@ pub fn wavetrend(input: &WavetrendInput) -> Result<WavetrendOutput, WavetrendErr
         &diff_esa,
         EmaParams {
             period: Some(channel_len),
+            ..EmaParams::default()
         },
     );
     let de_output = ema(&de_input)?;
@@ -235,6 +239,7 @@ pub fn wavetrend(input: &WavetrendInput) -> Result<WavetrendOutput, WavetrendErr
         &ci,
         EmaParams {
             period: Some(average_len),
+            ..EmaParams::default()
         },
     );
     let wt1_output = ema(&wt1_input)?;
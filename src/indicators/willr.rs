@@ -22,6 +22,7 @@
 ///   with leading `NaN`s until the calculation window is filled.
 /// - **`Err(WillrError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 
 #[derive(Debug, Clone)]
 pub enum WillrData<'a> {
@@ -141,6 +142,7 @@ pub fn willr(input: &WillrInput) -> Result<WillrOutput, WillrError> {
         };
 
     if (length - first_valid_idx) < period {
+        not_enough_valid_data("willr", period, length - first_valid_idx);
         return Err(WillrError::NotEnoughValidData {
             needed: period,
             valid: length - first_valid_idx,
@@ -185,6 +187,29 @@ pub fn willr(input: &WillrInput) -> Result<WillrOutput, WillrError> {
     })
 }
 
+/// Converts %R values into crossover signals.
+///
+/// Returns `1` on the bar where %R crosses up out of the `oversold` zone (previous value at
+/// or below `oversold`, current value above it) and `-1` where it crosses down out of the
+/// `overbought` zone. All other bars, including those involving `NaN`, are `0`.
+#[inline]
+pub fn willr_signals(values: &[f64], oversold: f64, overbought: f64) -> Vec<i8> {
+    let mut signals = vec![0i8; values.len()];
+    for i in 1..values.len() {
+        let prev = values[i - 1];
+        let curr = values[i];
+        if prev.is_nan() || curr.is_nan() {
+            continue;
+        }
+        if prev <= oversold && curr > oversold {
+            signals[i] = 1;
+        } else if prev >= overbought && curr < overbought {
+            signals[i] = -1;
+        }
+    }
+    signals
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -298,4 +323,22 @@ mod tests {
         let result = willr(&input);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_willr_signals_crossings() {
+        let values = [
+            f64::NAN,
+            -50.0,
+            -85.0,
+            -90.0,
+            -70.0,
+            -60.0,
+            -15.0,
+            -10.0,
+            -30.0,
+            -40.0,
+        ];
+        let signals = willr_signals(&values, -80.0, -20.0);
+        assert_eq!(signals, vec![0, 0, 0, 0, 1, 0, 0, 0, -1, 0]);
+    }
 }
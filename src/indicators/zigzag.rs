@@ -0,0 +1,337 @@
+/// # ZigZag
+///
+/// ZigZag filters out price moves smaller than a percentage threshold and connects the
+/// remaining swing highs and lows with straight lines, which makes the swing structure
+/// of a series easy to read (e.g. for chart patterns or wave counting).
+///
+/// Candidate swings are the bars found by [`pivot_highs`] on `high` and [`pivot_lows`]
+/// on `low` with `depth` bars on each side. Walking the candidates in order, a candidate
+/// of the same kind as the current tentative swing replaces it if it is more extreme,
+/// and a candidate of the opposite kind is accepted only when it lies at least
+/// `deviation_pct` percent beyond the tentative swing in the reversal direction (a new
+/// high above the last low, a new low below the last high). Accepting it confirms the
+/// tentative swing. An outside bar that is both a pivot high and a pivot low yields at
+/// most one swing. The last tentative swing is never confirmed, since no reversal of
+/// the required size has followed it yet.
+///
+/// ## Parameters
+/// - **deviation_pct**: Minimum move, in percent, between consecutive swings. Defaults to 5.0.
+/// - **depth**: Bars on each side a pivot must beat. Defaults to 12.
+///
+/// ## Errors
+/// - **EmptyData**: zigzag: Input data slice is empty.
+/// - **MismatchedLength**: zigzag: `high` and `low` slices have different lengths.
+/// - **InvalidDeviation**: zigzag: `deviation_pct` is not a positive finite number.
+/// - **InvalidDepth**: zigzag: `depth` is zero or not smaller than the data length.
+///
+/// ## Returns
+/// - **`Ok(ZigZagOutput)`** on success, containing:
+///   - `swings`: `Some(price)` at each confirmed swing bar (the high for swing highs,
+///     the low for swing lows) and `None` elsewhere.
+///   - `line`: The swings joined by linear interpolation, `NaN` before the first and
+///     after the last confirmed swing.
+/// - **`Err(ZigZagError)`** otherwise.
+use crate::indicators::utility_functions::{pivot_highs, pivot_lows};
+use crate::utilities::data_loader::Candles;
+use thiserror::Error;
+
+#[derive(Debug, Clone)]
+pub enum ZigZagData<'a> {
+    Candles { candles: &'a Candles },
+    Slices { high: &'a [f64], low: &'a [f64] },
+}
+
+#[derive(Debug, Clone)]
+pub struct ZigZagOutput {
+    pub swings: Vec<Option<f64>>,
+    pub line: Vec<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ZigZagParams {
+    pub deviation_pct: Option<f64>,
+    pub depth: Option<usize>,
+}
+
+impl Default for ZigZagParams {
+    fn default() -> Self {
+        Self {
+            deviation_pct: Some(5.0),
+            depth: Some(12),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ZigZagInput<'a> {
+    pub data: ZigZagData<'a>,
+    pub params: ZigZagParams,
+}
+
+impl<'a> ZigZagInput<'a> {
+    pub fn from_candles(candles: &'a Candles, params: ZigZagParams) -> Self {
+        Self {
+            data: ZigZagData::Candles { candles },
+            params,
+        }
+    }
+
+    pub fn from_slices(high: &'a [f64], low: &'a [f64], params: ZigZagParams) -> Self {
+        Self {
+            data: ZigZagData::Slices { high, low },
+            params,
+        }
+    }
+
+    pub fn with_default_candles(candles: &'a Candles) -> Self {
+        Self {
+            data: ZigZagData::Candles { candles },
+            params: ZigZagParams::default(),
+        }
+    }
+
+    pub fn get_deviation_pct(&self) -> f64 {
+        self.params
+            .deviation_pct
+            .unwrap_or_else(|| ZigZagParams::default().deviation_pct.unwrap())
+    }
+
+    pub fn get_depth(&self) -> usize {
+        self.params
+            .depth
+            .unwrap_or_else(|| ZigZagParams::default().depth.unwrap())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ZigZagError {
+    #[error("zigzag: Empty data provided.")]
+    EmptyData,
+    #[error(
+        "zigzag: High and low slices have different lengths: high = {high_len}, low = {low_len}"
+    )]
+    MismatchedLength { high_len: usize, low_len: usize },
+    #[error("zigzag: Invalid deviation: deviation_pct = {deviation_pct}")]
+    InvalidDeviation { deviation_pct: f64 },
+    #[error("zigzag: Invalid depth: depth = {depth}, data length = {data_len}")]
+    InvalidDepth { depth: usize, data_len: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SwingKind {
+    High,
+    Low,
+}
+
+#[inline]
+pub fn zigzag(input: &ZigZagInput) -> Result<ZigZagOutput, ZigZagError> {
+    let (high, low) = match &input.data {
+        ZigZagData::Candles { candles } => (candles.high.as_slice(), candles.low.as_slice()),
+        ZigZagData::Slices { high, low } => (*high, *low),
+    };
+
+    let len = high.len();
+    if len == 0 {
+        return Err(ZigZagError::EmptyData);
+    }
+    if low.len() != len {
+        return Err(ZigZagError::MismatchedLength {
+            high_len: len,
+            low_len: low.len(),
+        });
+    }
+
+    let deviation_pct = input.get_deviation_pct();
+    if !deviation_pct.is_finite() || deviation_pct <= 0.0 {
+        return Err(ZigZagError::InvalidDeviation { deviation_pct });
+    }
+    let depth = input.get_depth();
+    if depth == 0 || depth >= len {
+        return Err(ZigZagError::InvalidDepth {
+            depth,
+            data_len: len,
+        });
+    }
+
+    let highs = pivot_highs(high, depth, depth);
+    let lows = pivot_lows(low, depth, depth);
+    let threshold = deviation_pct / 100.0;
+
+    let mut confirmed: Vec<(usize, f64)> = Vec::new();
+    let mut tentative: Option<(usize, f64, SwingKind)> = None;
+
+    for i in 0..len {
+        for (pivot, kind) in [(highs[i], SwingKind::High), (lows[i], SwingKind::Low)] {
+            let Some(price) = pivot else {
+                continue;
+            };
+            tentative = match tentative {
+                None => Some((i, price, kind)),
+                Some((_, last, last_kind)) if last_kind == kind => {
+                    let more_extreme = match kind {
+                        SwingKind::High => price > last,
+                        SwingKind::Low => price < last,
+                    };
+                    if more_extreme {
+                        Some((i, price, kind))
+                    } else {
+                        tentative
+                    }
+                }
+                Some((last_idx, _, _)) if last_idx == i => tentative,
+                Some((last_idx, last, _)) => {
+                    let reversal = match kind {
+                        SwingKind::High => (price - last) / last,
+                        SwingKind::Low => (last - price) / last,
+                    };
+                    if reversal >= threshold {
+                        confirmed.push((last_idx, last));
+                        Some((i, price, kind))
+                    } else {
+                        tentative
+                    }
+                }
+            };
+        }
+    }
+
+    let mut swings = vec![None; len];
+    let mut line = vec![f64::NAN; len];
+    for &(idx, price) in &confirmed {
+        swings[idx] = Some(price);
+        line[idx] = price;
+    }
+    for pair in confirmed.windows(2) {
+        let (start, from) = pair[0];
+        let (end, to) = pair[1];
+        let step = (to - from) / (end - start) as f64;
+        for (offset, value) in line[start..end].iter_mut().enumerate() {
+            *value = from + step * offset as f64;
+        }
+    }
+
+    Ok(ZigZagOutput { swings, line })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::data_loader::read_candles_from_csv;
+
+    fn ramp(from: f64, to: f64, bars: usize) -> Vec<f64> {
+        (1..=bars)
+            .map(|i| from + (to - from) * i as f64 / bars as f64)
+            .collect()
+    }
+
+    fn swing_path() -> Vec<f64> {
+        let mut path = vec![100.0];
+        path.extend(ramp(100.0, 120.0, 20));
+        path.extend(ramp(120.0, 105.0, 20));
+        path.extend(ramp(105.0, 118.0, 8));
+        path.extend(ramp(118.0, 115.0, 4));
+        path.extend(ramp(115.0, 130.0, 8));
+        path.extend(ramp(130.0, 128.0, 5));
+        path
+    }
+
+    #[test]
+    fn test_zigzag_two_known_swings() {
+        let path = swing_path();
+        let params = ZigZagParams {
+            deviation_pct: Some(5.0),
+            depth: Some(4),
+        };
+        let input = ZigZagInput::from_slices(&path, &path, params);
+        let result = zigzag(&input).expect("Failed to calculate zigzag");
+
+        let marked: Vec<(usize, f64)> = result
+            .swings
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| s.map(|p| (i, p)))
+            .collect();
+        assert_eq!(marked, vec![(20, 120.0), (40, 105.0)]);
+
+        assert!(result.line[..20].iter().all(|v| v.is_nan()));
+        assert!((result.line[30] - 112.5).abs() < 1e-9);
+        assert_eq!(result.line[40], 105.0);
+        assert!(result.line[41..].iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_zigzag_small_threshold_keeps_wiggle() {
+        let path = swing_path();
+        let params = ZigZagParams {
+            deviation_pct: Some(1.0),
+            depth: Some(4),
+        };
+        let input = ZigZagInput::from_slices(&path, &path, params);
+        let result = zigzag(&input).expect("Failed to calculate zigzag");
+        assert_eq!(result.swings[48], Some(118.0));
+        assert_eq!(result.swings[52], Some(115.0));
+    }
+
+    #[test]
+    fn test_zigzag_swings_alternate_on_candles() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let input = ZigZagInput::with_default_candles(&candles);
+        let result = zigzag(&input).expect("Failed to calculate zigzag");
+        assert_eq!(result.swings.len(), candles.close.len());
+        assert_eq!(result.line.len(), candles.close.len());
+
+        let marked: Vec<(usize, f64)> = result
+            .swings
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| s.map(|p| (i, p)))
+            .collect();
+        assert!(marked.len() > 10);
+        for pair in marked.windows(2) {
+            let (a, b) = (pair[0].1, pair[1].1);
+            assert!(((b - a) / a).abs() >= 0.05, "Swing move below threshold");
+        }
+        for triple in marked.windows(3) {
+            let rising = triple[1].1 > triple[0].1;
+            assert_eq!(rising, triple[1].1 > triple[2].1, "Swings must alternate");
+        }
+    }
+
+    #[test]
+    fn test_zigzag_invalid_params() {
+        let path = swing_path();
+        let zero_dev = ZigZagInput::from_slices(
+            &path,
+            &path,
+            ZigZagParams {
+                deviation_pct: Some(0.0),
+                depth: None,
+            },
+        );
+        assert!(matches!(
+            zigzag(&zero_dev),
+            Err(ZigZagError::InvalidDeviation { .. })
+        ));
+
+        let zero_depth = ZigZagInput::from_slices(
+            &path,
+            &path,
+            ZigZagParams {
+                deviation_pct: None,
+                depth: Some(0),
+            },
+        );
+        assert!(matches!(
+            zigzag(&zero_depth),
+            Err(ZigZagError::InvalidDepth { .. })
+        ));
+
+        let short = [1.0, 2.0];
+        let mismatched = ZigZagInput::from_slices(&path, &short, ZigZagParams::default());
+        assert!(matches!(
+            zigzag(&mismatched),
+            Err(ZigZagError::MismatchedLength { .. })
+        ));
+    }
+}
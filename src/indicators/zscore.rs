@@ -8,7 +8,8 @@ use crate::indicators::moving_averages::ma::{ma, MaData};
 ///
 /// ## Parameters
 /// - **period**: The window size (number of data points). Defaults to 14.
-/// - **ma_type**: Type of moving average to use for the mean. Defaults to `"sma"`.
+/// - **ma_type**: Type of moving average to use for the mean, any type accepted by `ma`
+///   (e.g. `"ema"` for an exponentially weighted mean). Defaults to `"sma"`.
 /// - **nbdev**: The multiplier for the standard/mean/median absolute deviation. Defaults to `1.0`.
 /// - **devtype**: Which deviation function to use:
 ///   - `0` = Standard Deviation
@@ -30,6 +31,7 @@ use crate::indicators::moving_averages::ma::{ma, MaData};
 ///   with leading `NaN`s until the required window is filled.
 /// - **`Err(ZscoreError)`** otherwise.
 use crate::utilities::data_loader::{source_type, Candles};
+use crate::utilities::diagnostics::not_enough_valid_data;
 use std::error::Error;
 use thiserror::Error;
 
@@ -164,6 +166,7 @@ pub fn zscore(input: &ZscoreInput) -> Result<ZscoreOutput, ZscoreError> {
     };
 
     if (data.len() - first_valid_idx) < period {
+        not_enough_valid_data("zscore", period, data.len() - first_valid_idx);
         return Err(ZscoreError::NotEnoughValidData {
             needed: period,
             valid: data.len() - first_valid_idx,
@@ -189,13 +192,9 @@ pub fn zscore(input: &ZscoreInput) -> Result<ZscoreOutput, ZscoreError> {
     }
 
     let mut zscore_values = vec![f64::NAN; data.len()];
-    for i in first_valid_idx..data.len() {
-        let offset = i - first_valid_idx;
-        if offset < (period - 1) {
-            continue;
-        }
-        let mean = means[offset];
-        let sigma = sigmas[offset];
+    for i in (first_valid_idx + period - 1)..data.len() {
+        let mean = means[i];
+        let sigma = sigmas[i];
         let value = data[i];
         zscore_values[i] = if sigma == 0.0 || sigma.is_nan() {
             f64::NAN
@@ -281,6 +280,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_zscore_ema_mean() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let default = zscore(&ZscoreInput::with_default_candles(&candles))
+            .expect("Failed Zscore with default params");
+        let sma_params = ZscoreParams {
+            ma_type: Some("sma".to_string()),
+            ..ZscoreParams::default()
+        };
+        let sma_mean = zscore(&ZscoreInput::from_candles(&candles, "close", sma_params))
+            .expect("Failed Zscore with SMA mean");
+        crate::assert_slice_close!(default.values, sma_mean.values, 0.0);
+
+        let last = default.values.len() - 1;
+        assert!((default.values[last] - -1.431635486349353).abs() < 1e-9);
+
+        let ema_params = ZscoreParams {
+            ma_type: Some("ema".to_string()),
+            ..ZscoreParams::default()
+        };
+        let ema_mean = zscore(&ZscoreInput::from_candles(&candles, "close", ema_params))
+            .expect("Failed Zscore with EMA mean");
+        assert_eq!(ema_mean.values.len(), default.values.len());
+        assert!(
+            (ema_mean.values[last] - default.values[last]).abs() > 1e-3,
+            "EMA mean should change the z-score"
+        );
+    }
+
+    #[test]
+    fn test_zscore_leading_nan_alignment() {
+        let data: Vec<f64> = (0..40).map(|i| ((i * 7) % 11) as f64).collect();
+        let mut padded = vec![f64::NAN; 5];
+        padded.extend_from_slice(&data);
+
+        let clean = zscore(&ZscoreInput::from_slice(&data, ZscoreParams::default()))
+            .expect("Failed Zscore on clean data");
+        let shifted = zscore(&ZscoreInput::from_slice(&padded, ZscoreParams::default()))
+            .expect("Failed Zscore on NaN-prefixed data");
+        assert!(shifted.values[..18].iter().all(|v| v.is_nan()));
+        crate::assert_slice_close!(shifted.values[5..], clean.values, 1e-12);
+    }
+
     #[test]
     fn test_zscore_with_zero_period() {
         let input_data = [10.0, 20.0, 30.0];
@@ -0,0 +1,146 @@
+use crate::utilities::data_loader::Candles;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+/// Cheap identity of the data an indicator was computed on.
+///
+/// Two data sets with the same length and last timestamp (or last value, for slices)
+/// are treated as identical, which holds for the usual "same file, maybe appended" case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DataFingerprint {
+    pub len: usize,
+    pub last: i64,
+}
+
+impl DataFingerprint {
+    pub fn from_candles(candles: &Candles) -> Self {
+        Self {
+            len: candles.close.len(),
+            last: candles.timestamp.last().copied().unwrap_or(0),
+        }
+    }
+
+    pub fn from_slice(data: &[f64]) -> Self {
+        Self {
+            len: data.len(),
+            last: data.last().map_or(0, |v| v.to_bits() as i64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    name: String,
+    params_hash: u64,
+    fingerprint: DataFingerprint,
+}
+
+/// Thread-safe memo of indicator outputs keyed by indicator name, a hash of its
+/// parameters, and a [`DataFingerprint`].
+#[derive(Debug, Default)]
+pub struct IndicatorCache {
+    entries: Mutex<HashMap<CacheKey, Arc<Vec<f64>>>>,
+}
+
+impl IndicatorCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached values for `(name, params, fingerprint)` or runs `compute` and
+    /// stores its result. Errors from `compute` are returned and not cached.
+    ///
+    /// The lock is not held while `compute` runs, so two threads missing on the same key
+    /// may both compute; the first result to be inserted wins.
+    pub fn get_or_compute<P, E, F>(
+        &self,
+        name: &str,
+        params: &P,
+        fingerprint: DataFingerprint,
+        compute: F,
+    ) -> Result<Arc<Vec<f64>>, E>
+    where
+        P: Hash + ?Sized,
+        F: FnOnce() -> Result<Vec<f64>, E>,
+    {
+        let mut hasher = DefaultHasher::new();
+        params.hash(&mut hasher);
+        let key = CacheKey {
+            name: name.to_string(),
+            params_hash: hasher.finish(),
+            fingerprint,
+        };
+
+        if let Some(values) = self.entries.lock().unwrap().get(&key) {
+            return Ok(Arc::clone(values));
+        }
+
+        let values = Arc::new(compute()?);
+        let mut entries = self.entries.lock().unwrap();
+        Ok(Arc::clone(entries.entry(key).or_insert(values)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::sma::{sma, SmaError, SmaInput, SmaParams};
+    use crate::utilities::data_loader::read_candles_from_csv;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_cache_hit_skips_recompute() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let cache = IndicatorCache::new();
+        let calls = AtomicUsize::new(0);
+        let fingerprint = DataFingerprint::from_candles(&candles);
+
+        let run = |period: usize| {
+            cache.get_or_compute("sma", &(period, "close"), fingerprint, || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                let params = SmaParams {
+                    period: Some(period),
+                };
+                let input = SmaInput::from_candles(&candles, "close", params);
+                Ok::<_, SmaError>(sma(&input)?.values)
+            })
+        };
+
+        let first = run(9).expect("SMA failed");
+        let second = run(9).expect("SMA failed");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let other = run(10).expect("SMA failed");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(!Arc::ptr_eq(&first, &other));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_cache_does_not_store_errors() {
+        let cache = IndicatorCache::new();
+        let data = [1.0, 2.0];
+        let fingerprint = DataFingerprint::from_slice(&data);
+
+        let result: Result<_, &str> =
+            cache.get_or_compute("x", &1usize, fingerprint, || Err("boom"));
+        assert!(result.is_err());
+        assert!(cache.is_empty());
+    }
+}
@@ -2,8 +2,72 @@ extern crate csv;
 extern crate serde;
 
 use csv::ReaderBuilder;
+use std::borrow::Cow;
 use std::error::Error;
 use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DataLoaderError {
+    #[error("data_loader: High below low at index {index}: high = {high}, low = {low}")]
+    HighBelowLow { index: usize, high: f64, low: f64 },
+    #[error(
+        "data_loader: Open/close outside the high/low range at index {index}: open = {open}, high = {high}, low = {low}, close = {close}"
+    )]
+    PriceOutsideRange {
+        index: usize,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+    },
+    #[error(
+        "data_loader: Timestamps not increasing at index {index}: previous = {previous}, current = {current}"
+    )]
+    NonMonotonicTimestamp {
+        index: usize,
+        previous: i64,
+        current: i64,
+    },
+    #[error("data_loader: Unknown candle field '{name}'")]
+    UnknownField { name: String },
+    #[error(
+        "data_loader: Cannot concat out-of-order candles: last timestamp = {last}, next first timestamp = {first}"
+    )]
+    OutOfOrderConcat { last: i64, first: i64 },
+    #[error("data_loader: I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("data_loader: Not a candle binary file (bad magic bytes)")]
+    BadMagic,
+    #[error("data_loader: Truncated candle binary file: expected {expected} bytes, got {actual}")]
+    TruncatedBinary { expected: usize, actual: usize },
+    #[error("data_loader: Invalid bucket duration: {bucket_seconds} seconds")]
+    InvalidBucket { bucket_seconds: i64 },
+    #[error("data_loader: Invalid resample factor: factor = {factor}, base length = {base_len}")]
+    InvalidResampleFactor { factor: usize, base_len: usize },
+    #[error("data_loader: Timestamp out of range at index {index}: {timestamp}")]
+    InvalidTimestamp { index: usize, timestamp: i64 },
+    #[error(
+        "data_loader: Column length mismatch: {field} has {actual} rows, close has {expected}"
+    )]
+    LengthMismatch {
+        field: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+/// A single OHLCV bar. `timestamp` is in epoch milliseconds, like [`Candles::timestamp`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candle {
+    pub timestamp: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
 
 #[derive(Debug, Clone)]
 pub struct Candles {
@@ -14,6 +78,8 @@ pub struct Candles {
     pub close: Vec<f64>,
     pub volume: Vec<f64>,
 
+    /// `timestamp` as `f64` epoch milliseconds, so it can be selected like a price column.
+    pub timestamp_f64: Vec<f64>,
     pub hl2: Vec<f64>,
     pub hlc3: Vec<f64>,
     pub ohlc4: Vec<f64>,
@@ -36,6 +102,7 @@ impl Candles {
             low,
             close,
             volume,
+            timestamp_f64: Vec::new(),
             hl2: Vec::new(),
             hlc3: Vec::new(),
             ohlc4: Vec::new(),
@@ -46,6 +113,27 @@ impl Candles {
         candles
     }
 
+    /// Returns the bar at `index`, or `None` when out of bounds.
+    pub fn candle_at(&self, index: usize) -> Option<Candle> {
+        if index >= self.close.len() {
+            return None;
+        }
+        Some(Candle {
+            timestamp: self.timestamp[index],
+            open: self.open[index],
+            high: self.high[index],
+            low: self.low[index],
+            close: self.close[index],
+            volume: self.volume[index],
+        })
+    }
+
+    /// Index of the last bar whose timestamp is at or before `ts`, found by binary search
+    /// (timestamps are assumed sorted ascending). `None` when `ts` precedes the first bar.
+    pub fn index_at_or_before(&self, ts: i64) -> Option<usize> {
+        self.timestamp.partition_point(|&t| t <= ts).checked_sub(1)
+    }
+
     pub fn get_timestamp(&self) -> Result<&[i64], Box<dyn Error>> {
         Ok(&self.timestamp)
     }
@@ -96,6 +184,8 @@ impl Candles {
         }
     }
 
+    /// Borrows a raw price or volume column, or the timestamps as `f64` epoch milliseconds
+    /// (`"timestamp"`, alias `"time"`/`"date"`).
     pub fn select_candle_field(&self, field: &str) -> Result<&[f64], Box<dyn std::error::Error>> {
         match field.to_lowercase().as_str() {
             "open" => Ok(&self.open),
@@ -103,10 +193,169 @@ impl Candles {
             "low" => Ok(&self.low),
             "close" => Ok(&self.close),
             "volume" => Ok(&self.volume),
+            "timestamp" | "time" | "date" => Ok(&self.timestamp_f64),
             _ => Err(format!("Invalid field: {}", field).into()),
         }
     }
 
+    /// Returns any column by name: raw fields (open/high/low/close/volume) and `timestamp`
+    /// (alias `time`/`date`, as `f64` epoch milliseconds) are borrowed, and computed fields
+    /// (hl2/hlc3/ohlc4/hlcc4) are recalculated from the raw columns and returned owned.
+    pub fn column(&self, name: &str) -> Result<Cow<'_, [f64]>, DataLoaderError> {
+        match name.to_lowercase().as_str() {
+            "open" => Ok(Cow::Borrowed(&self.open)),
+            "high" => Ok(Cow::Borrowed(&self.high)),
+            "low" => Ok(Cow::Borrowed(&self.low)),
+            "close" => Ok(Cow::Borrowed(&self.close)),
+            "volume" => Ok(Cow::Borrowed(&self.volume)),
+            "hl2" => Ok(Cow::Owned(self.compute_hl2())),
+            "hlc3" => Ok(Cow::Owned(self.compute_hlc3())),
+            "ohlc4" => Ok(Cow::Owned(self.compute_ohlc4())),
+            "hlcc4" => Ok(Cow::Owned(self.compute_hlcc4())),
+            "timestamp" | "time" | "date" => Ok(Cow::Borrowed(&self.timestamp_f64)),
+            _ => Err(DataLoaderError::UnknownField {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Mutable access to a raw OHLCV column (`open`, `high`, `low`, `close`, `volume`) for
+    /// in-place preprocessing such as outlier clipping. Derived columns (`hl2`, `hlc3`, ...)
+    /// are not updated automatically; call [`Candles::refresh_derived_fields`] afterwards if
+    /// they are used.
+    pub fn field_mut(&mut self, name: &str) -> Result<&mut [f64], DataLoaderError> {
+        match name.to_lowercase().as_str() {
+            "open" => Ok(&mut self.open),
+            "high" => Ok(&mut self.high),
+            "low" => Ok(&mut self.low),
+            "close" => Ok(&mut self.close),
+            "volume" => Ok(&mut self.volume),
+            _ => Err(DataLoaderError::UnknownField {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Recomputes `hl2`, `hlc3`, `ohlc4` and `hlcc4` from the raw columns, e.g. after
+    /// editing them through [`Candles::field_mut`].
+    pub fn refresh_derived_fields(&mut self) {
+        self.precompute_fields();
+    }
+
+    /// Errors with [`DataLoaderError::LengthMismatch`] unless every raw column has as many
+    /// rows as `close`.
+    fn check_lengths(&self) -> Result<(), DataLoaderError> {
+        let expected = self.close.len();
+        for (field, actual) in [
+            ("timestamp", self.timestamp.len()),
+            ("open", self.open.len()),
+            ("high", self.high.len()),
+            ("low", self.low.len()),
+            ("volume", self.volume.len()),
+        ] {
+            if actual != expected {
+                return Err(DataLoaderError::LengthMismatch {
+                    field,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that every raw column has as many rows as `close`, then every row for
+    /// `high >= low`, open/close inside the high/low range, and strictly increasing
+    /// timestamps. Returns the first offending column or row as an error.
+    pub fn validate(&self) -> Result<(), DataLoaderError> {
+        self.check_lengths()?;
+        for i in 0..self.close.len() {
+            let (o, h, l, c) = (self.open[i], self.high[i], self.low[i], self.close[i]);
+            if h < l {
+                return Err(DataLoaderError::HighBelowLow {
+                    index: i,
+                    high: h,
+                    low: l,
+                });
+            }
+            if h < o.max(c) || l > o.min(c) {
+                return Err(DataLoaderError::PriceOutsideRange {
+                    index: i,
+                    open: o,
+                    high: h,
+                    low: l,
+                    close: c,
+                });
+            }
+            if i > 0 && self.timestamp[i] <= self.timestamp[i - 1] {
+                return Err(DataLoaderError::NonMonotonicTimestamp {
+                    index: i,
+                    previous: self.timestamp[i - 1],
+                    current: self.timestamp[i],
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends `other` to the end of `self`, e.g. to stitch together one CSV per year.
+    ///
+    /// The join must be chronological: `other`'s first timestamp has to be after `self`'s
+    /// last one. If both sides contain the same boundary bar (equal timestamps), `other`'s
+    /// copy is dropped so the bar appears only once. The derived fields are recomputed.
+    pub fn concat(&mut self, other: &Candles) -> Result<(), DataLoaderError> {
+        if other.timestamp.is_empty() {
+            return Ok(());
+        }
+        let mut start = 0;
+        if let Some(&last) = self.timestamp.last() {
+            let first = other.timestamp[0];
+            if first < last {
+                return Err(DataLoaderError::OutOfOrderConcat { last, first });
+            }
+            if first == last {
+                start = 1;
+            }
+        }
+
+        self.timestamp.extend_from_slice(&other.timestamp[start..]);
+        self.open.extend_from_slice(&other.open[start..]);
+        self.high.extend_from_slice(&other.high[start..]);
+        self.low.extend_from_slice(&other.low[start..]);
+        self.close.extend_from_slice(&other.close[start..]);
+        self.volume.extend_from_slice(&other.volume[start..]);
+        self.precompute_fields();
+        Ok(())
+    }
+
+    /// Removes rows whose timestamp equals the next row's, so each run of duplicate
+    /// timestamps keeps only its last row (the latest update from a merged feed). Only
+    /// adjacent duplicates are detected. Returns the number of rows removed; the derived
+    /// fields are recomputed when anything was removed.
+    pub fn dedup_timestamps(&mut self) -> usize {
+        let len = self.timestamp.len();
+        let keep: Vec<bool> = (0..len)
+            .map(|i| i + 1 == len || self.timestamp[i] != self.timestamp[i + 1])
+            .collect();
+        let removed = keep.iter().filter(|&&k| !k).count();
+        if removed == 0 {
+            return 0;
+        }
+
+        fn retain_rows<T: Copy>(values: &mut Vec<T>, keep: &[bool]) {
+            let mut rows = keep.iter();
+            values.retain(|_| *rows.next().unwrap());
+        }
+        retain_rows(&mut self.timestamp, &keep);
+        retain_rows(&mut self.open, &keep);
+        retain_rows(&mut self.high, &keep);
+        retain_rows(&mut self.low, &keep);
+        retain_rows(&mut self.close, &keep);
+        retain_rows(&mut self.volume, &keep);
+        self.precompute_fields();
+        removed
+    }
+
     fn precompute_fields(&mut self) {
         let len = self.high.len();
         let mut hl2 = Vec::with_capacity(len);
@@ -126,6 +375,7 @@ impl Candles {
             hlcc4.push((h + l + 2.0 * c) / 4.0);
         }
 
+        self.timestamp_f64 = self.timestamp.iter().map(|&t| t as f64).collect();
         self.hl2 = hl2;
         self.hlc3 = hlc3;
         self.ohlc4 = ohlc4;
@@ -157,6 +407,142 @@ pub fn read_candles_from_csv(file_path: &str) -> Result<Candles, Box<dyn Error>>
     Ok(Candles::new(timestamp, open, high, low, close, volume))
 }
 
+/// Same as [`read_candles_from_csv`], but rejects files that fail [`Candles::validate`].
+pub fn read_candles_from_csv_strict(file_path: &str) -> Result<Candles, Box<dyn Error>> {
+    let candles = read_candles_from_csv(file_path)?;
+    candles.validate()?;
+    Ok(candles)
+}
+
+const BIN_MAGIC: &[u8; 8] = b"RBCANDL1";
+const BIN_HEADER_LEN: usize = 16;
+
+/// Writes `candles` as a binary dump: the 8-byte magic `RBCANDL1`, the bar count as a
+/// little-endian `u64`, then the timestamp, open, high, low, close and volume columns as
+/// little-endian `f64`s, one column after the other. Derived fields are not stored.
+/// Fails with [`DataLoaderError::LengthMismatch`] if the raw columns differ in length.
+pub fn write_candles_to_bin(candles: &Candles, path: &str) -> Result<(), DataLoaderError> {
+    candles.check_lengths()?;
+    let count = candles.close.len();
+    let mut buf = Vec::with_capacity(BIN_HEADER_LEN + count * 6 * 8);
+    buf.extend_from_slice(BIN_MAGIC);
+    buf.extend_from_slice(&(count as u64).to_le_bytes());
+    for &ts in &candles.timestamp {
+        buf.extend_from_slice(&(ts as f64).to_le_bytes());
+    }
+    for column in [
+        &candles.open,
+        &candles.high,
+        &candles.low,
+        &candles.close,
+        &candles.volume,
+    ] {
+        for &v in column {
+            buf.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+    File::create(path)?.write_all(&buf)?;
+    Ok(())
+}
+
+/// Reads a file written by [`write_candles_to_bin`]. Much faster than parsing CSV for
+/// large histories.
+pub fn read_candles_from_bin(path: &str) -> Result<Candles, DataLoaderError> {
+    let mut bytes = Vec::new();
+    File::open(path)?.read_to_end(&mut bytes)?;
+    if bytes.len() < BIN_HEADER_LEN {
+        return Err(DataLoaderError::TruncatedBinary {
+            expected: BIN_HEADER_LEN,
+            actual: bytes.len(),
+        });
+    }
+    if &bytes[..8] != BIN_MAGIC {
+        return Err(DataLoaderError::BadMagic);
+    }
+    let count = u64::from_le_bytes(bytes[8..16].try_into().unwrap());
+    let expected = usize::try_from(count)
+        .ok()
+        .and_then(|n| n.checked_mul(6 * 8))
+        .and_then(|n| n.checked_add(BIN_HEADER_LEN));
+    let count = match expected {
+        Some(expected) if bytes.len() == expected => count as usize,
+        _ => {
+            return Err(DataLoaderError::TruncatedBinary {
+                expected: expected.unwrap_or(usize::MAX),
+                actual: bytes.len(),
+            })
+        }
+    };
+
+    let column = |k: usize| -> Vec<f64> {
+        let start = BIN_HEADER_LEN + k * count * 8;
+        bytes[start..start + count * 8]
+            .chunks_exact(8)
+            .map(|b| f64::from_le_bytes(b.try_into().unwrap()))
+            .collect()
+    };
+    let timestamp = column(0).into_iter().map(|t| t as i64).collect();
+
+    Ok(Candles::new(
+        timestamp,
+        column(1),
+        column(2),
+        column(3),
+        column(4),
+        column(5),
+    ))
+}
+
+/// Writes `candles` as a TradingView-importable CSV: a `time,open,high,low,close,volume`
+/// header, then one row per bar with `time` as an ISO-8601 UTC string
+/// (`2018-09-01T00:00:00Z`). Timestamps that are not whole seconds keep their milliseconds
+/// (`2018-09-01T00:00:00.250Z`). Derived fields are not written.
+///
+/// The candles are checked with [`Candles::validate`] first, so mismatched column lengths
+/// or malformed rows are reported as an error before anything is written.
+pub fn write_candles_tradingview_csv(candles: &Candles, path: &str) -> Result<(), DataLoaderError> {
+    candles.validate()?;
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "time,open,high,low,close,volume")?;
+    for (i, &ts) in candles.timestamp.iter().enumerate() {
+        let time = chrono::DateTime::from_timestamp_millis(ts)
+            .ok_or(DataLoaderError::InvalidTimestamp {
+                index: i,
+                timestamp: ts,
+            })?
+            .format("%Y-%m-%dT%H:%M:%S%.fZ");
+        writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            time,
+            candles.open[i],
+            candles.high[i],
+            candles.low[i],
+            candles.close[i],
+            candles.volume[i]
+        )?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+const VALID_SOURCES: &[&str] = &[
+    "open",
+    "high",
+    "low",
+    "close",
+    "volume",
+    "timestamp",
+    "hl2",
+    "hlc3",
+    "ohlc4",
+    "hlcc4",
+];
+
+pub fn valid_sources() -> &'static [&'static str] {
+    VALID_SOURCES
+}
+
 pub fn source_type<'a>(candles: &'a Candles, source: &str) -> &'a [f64] {
     let field = source.to_lowercase();
     match field.as_str() {
@@ -165,17 +551,65 @@ pub fn source_type<'a>(candles: &'a Candles, source: &str) -> &'a [f64] {
         "low" => &candles.low,
         "close" => &candles.close,
         "volume" => &candles.volume,
+        "timestamp" | "time" | "date" => &candles.timestamp_f64,
         "hl2" => &candles.hl2,
         "hlc3" => &candles.hlc3,
         "ohlc4" => &candles.ohlc4,
         "hlcc4" => &candles.hlcc4,
         _ => {
-            eprintln!("Warning: Invalid price source '{source}'. Defaulting to 'close'.");
+            eprintln!(
+                "Warning: Invalid price source '{source}'. Valid sources: {}. Defaulting to 'close'.",
+                valid_sources().join(", ")
+            );
             &candles.close
         }
     }
 }
 
+/// Price input shared by single-series indicators: either a candle field selected by
+/// `source` (see [`source_type`]) or a raw slice.
+#[derive(Debug, Clone)]
+pub enum SourceData<'a> {
+    Candles {
+        candles: &'a Candles,
+        source: &'a str,
+    },
+    Slice(&'a [f64]),
+}
+
+impl<'a> SourceData<'a> {
+    #[inline]
+    pub fn resolve(&self) -> &'a [f64] {
+        match self {
+            SourceData::Candles { candles, source } => source_type(candles, source),
+            SourceData::Slice(slice) => slice,
+        }
+    }
+}
+
+/// Borrowed OHLCV columns. Indicators that take `impl Into<CandleView>` work the same
+/// whether the data lives in a [`Candles`] or in caller-owned slices.
+#[derive(Debug, Clone, Copy)]
+pub struct CandleView<'a> {
+    pub open: &'a [f64],
+    pub high: &'a [f64],
+    pub low: &'a [f64],
+    pub close: &'a [f64],
+    pub volume: &'a [f64],
+}
+
+impl<'a> From<&'a Candles> for CandleView<'a> {
+    fn from(candles: &'a Candles) -> Self {
+        Self {
+            open: &candles.open,
+            high: &candles.high,
+            low: &candles.low,
+            close: &candles.close,
+            volume: &candles.volume,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -243,6 +677,88 @@ mod tests {
         compare_last_five(hlcc4, &expected_last_5_hlcc4, "HLCC4");
     }
 
+    #[test]
+    fn test_valid_sources() {
+        let sources = valid_sources();
+        assert!(
+            sources.contains(&"hl2"),
+            "Expected 'hl2' to be a valid source"
+        );
+        assert!(
+            sources.contains(&"close"),
+            "Expected 'close' to be a valid source"
+        );
+        assert!(
+            !sources.contains(&"bogus"),
+            "Did not expect 'bogus' to be valid"
+        );
+    }
+
+    #[test]
+    fn test_every_valid_source_resolves_to_its_column() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        for &name in valid_sources().iter().chain(&["time", "date", "HL2"]) {
+            let resolved = source_type(&candles, name);
+            let expected = candles
+                .column(name)
+                .unwrap_or_else(|e| panic!("'{name}' should be a known column: {e}"));
+            assert_eq!(resolved, &expected[..], "source_type('{name}') mismatch");
+            if name != "close" {
+                assert!(
+                    !std::ptr::eq(resolved, candles.close.as_slice()),
+                    "source_type('{name}') fell back to close"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_catches_bad_rows() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv_strict(file_path).expect("Sample data should be valid");
+        assert!(candles.validate().is_ok());
+
+        let bad_range = Candles::new(
+            vec![1, 2, 3],
+            vec![100.0, 200.0, 300.0],
+            vec![110.0, 170.0, 330.0],
+            vec![90.0, 180.0, 270.0],
+            vec![105.0, 190.0, 310.0],
+            vec![1000.0, 2000.0, 3000.0],
+        );
+        match bad_range.validate() {
+            Err(DataLoaderError::HighBelowLow { index, .. }) => assert_eq!(index, 1),
+            other => panic!("Expected HighBelowLow, got {:?}", other),
+        }
+
+        let bad_close = Candles::new(
+            vec![1, 2, 3],
+            vec![100.0, 200.0, 300.0],
+            vec![110.0, 220.0, 330.0],
+            vec![90.0, 180.0, 270.0],
+            vec![105.0, 190.0, 340.0],
+            vec![1000.0, 2000.0, 3000.0],
+        );
+        match bad_close.validate() {
+            Err(DataLoaderError::PriceOutsideRange { index, .. }) => assert_eq!(index, 2),
+            other => panic!("Expected PriceOutsideRange, got {:?}", other),
+        }
+
+        let bad_time = Candles::new(
+            vec![1, 3, 2],
+            vec![100.0, 200.0, 300.0],
+            vec![110.0, 220.0, 330.0],
+            vec![90.0, 180.0, 270.0],
+            vec![105.0, 190.0, 310.0],
+            vec![1000.0, 2000.0, 3000.0],
+        );
+        match bad_time.validate() {
+            Err(DataLoaderError::NonMonotonicTimestamp { index, .. }) => assert_eq!(index, 2),
+            other => panic!("Expected NonMonotonicTimestamp, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_precompute_fields_direct() {
         let timestamp = vec![1, 2, 3];
@@ -275,4 +791,352 @@ mod tests {
             assert!((actual - expected).abs() < 1e-4);
         }
     }
+
+    #[test]
+    fn test_column_borrows_raw_and_owns_computed() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let close = candles.column("close").expect("close column");
+        assert!(matches!(close, Cow::Borrowed(_)));
+        assert_eq!(close.as_ref(), candles.close.as_slice());
+
+        let hl2 = candles.column("HL2").expect("hl2 column");
+        assert!(matches!(hl2, Cow::Owned(_)));
+        assert_eq!(hl2.as_ref(), candles.hl2.as_slice());
+
+        assert!(matches!(
+            candles.column("vwap"),
+            Err(DataLoaderError::UnknownField { .. })
+        ));
+    }
+
+    #[test]
+    fn test_select_volume_and_timestamp_columns() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+
+        let volume = candles.select_candle_field("Volume").expect("volume field");
+        assert_eq!(volume.len(), candles.close.len());
+        assert_eq!(volume, candles.volume.as_slice());
+
+        let expected: Vec<f64> = candles.timestamp.iter().map(|&t| t as f64).collect();
+        for name in ["timestamp", "time", "date"] {
+            let ts = candles.select_candle_field(name).expect("timestamp field");
+            assert_eq!(ts.len(), candles.close.len());
+            assert_eq!(ts, expected.as_slice());
+
+            let column = candles.column(name).expect("timestamp column");
+            assert_eq!(&*column, expected.as_slice());
+            assert_eq!(source_type(&candles, name), expected.as_slice());
+        }
+    }
+
+    fn synthetic_candles(start_ts: i64, closes: &[f64]) -> Candles {
+        let n = closes.len();
+        let timestamp = (0..n as i64).map(|i| start_ts + i * 60_000).collect();
+        let high = closes.iter().map(|c| c + 1.0).collect();
+        let low = closes.iter().map(|c| c - 1.0).collect();
+        Candles::new(
+            timestamp,
+            closes.to_vec(),
+            high,
+            low,
+            closes.to_vec(),
+            vec![1.0; n],
+        )
+    }
+
+    #[test]
+    fn test_index_at_or_before() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let mid = candles.timestamp.len() / 2;
+        let ts = candles.timestamp[mid] + 60_000;
+
+        let idx = candles
+            .index_at_or_before(ts)
+            .expect("Timestamp is in range");
+        assert_eq!(idx, mid);
+        assert!(candles.timestamp[idx] <= ts && ts < candles.timestamp[idx + 1]);
+        assert_eq!(
+            candles.index_at_or_before(candles.timestamp[mid]),
+            Some(mid)
+        );
+
+        let first = candles.timestamp[0];
+        assert_eq!(candles.index_at_or_before(first), Some(0));
+        assert_eq!(candles.index_at_or_before(first - 1), None);
+        let last = candles.timestamp.len() - 1;
+        assert_eq!(candles.index_at_or_before(i64::MAX), Some(last));
+    }
+
+    #[test]
+    fn test_concat_appends_in_order() {
+        let mut first = synthetic_candles(0, &[10.0, 11.0, 12.0]);
+        let second = synthetic_candles(180_000, &[13.0, 14.0]);
+        first.concat(&second).expect("concat");
+
+        assert_eq!(first.close, vec![10.0, 11.0, 12.0, 13.0, 14.0]);
+        assert_eq!(first.timestamp.len(), 5);
+        assert_eq!(first.hl2.len(), 5);
+        assert_eq!(first.hl2[4], 14.0);
+        assert!(first.validate().is_ok());
+    }
+
+    #[test]
+    fn test_concat_dedups_boundary_bar() {
+        let mut first = synthetic_candles(0, &[10.0, 11.0, 12.0]);
+        let second = synthetic_candles(120_000, &[12.0, 13.0]);
+        first.concat(&second).expect("concat");
+
+        assert_eq!(first.close, vec![10.0, 11.0, 12.0, 13.0]);
+        assert_eq!(first.timestamp, vec![0, 60_000, 120_000, 180_000]);
+    }
+
+    #[test]
+    fn test_dedup_timestamps_keeps_last() {
+        let mut candles = synthetic_candles(0, &[10.0, 11.0, 12.0, 13.0]);
+        candles.timestamp[2] = candles.timestamp[1];
+        candles.timestamp[3] = candles.timestamp[1];
+        candles.refresh_derived_fields();
+
+        assert_eq!(candles.dedup_timestamps(), 2);
+        assert_eq!(candles.timestamp, vec![0, 60_000]);
+        assert_eq!(candles.close, vec![10.0, 13.0]);
+        for len in [
+            candles.open.len(),
+            candles.high.len(),
+            candles.low.len(),
+            candles.volume.len(),
+            candles.hl2.len(),
+            candles.hlc3.len(),
+            candles.ohlc4.len(),
+            candles.hlcc4.len(),
+        ] {
+            assert_eq!(len, 2);
+        }
+        assert_eq!(candles.hl2[1], 13.0);
+        assert!(candles.validate().is_ok());
+        assert_eq!(candles.dedup_timestamps(), 0);
+    }
+
+    #[test]
+    fn test_dedup_timestamps_on_file() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let clean = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let mut dirty = clean.clone();
+        dirty.timestamp.insert(100, dirty.timestamp[100]);
+        dirty.open.insert(100, 1.0);
+        dirty.high.insert(100, 1.0);
+        dirty.low.insert(100, 1.0);
+        dirty.close.insert(100, 1.0);
+        dirty.volume.insert(100, 1.0);
+        dirty.refresh_derived_fields();
+
+        assert_eq!(dirty.dedup_timestamps(), 1);
+        assert_eq!(dirty.timestamp, clean.timestamp);
+        assert_eq!(dirty.close, clean.close);
+        assert_eq!(dirty.hlc3, clean.hlc3);
+    }
+
+    #[test]
+    fn test_concat_out_of_order_errors() {
+        let mut later = synthetic_candles(600_000, &[10.0, 11.0]);
+        let earlier = synthetic_candles(0, &[8.0, 9.0]);
+        let err = later.concat(&earlier).unwrap_err();
+        assert!(matches!(
+            err,
+            DataLoaderError::OutOfOrderConcat {
+                last: 660_000,
+                first: 0
+            }
+        ));
+        assert_eq!(later.close.len(), 2);
+    }
+
+    #[test]
+    fn test_bin_round_trip() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let bin_path = std::env::temp_dir().join(format!("candles_{}.bin", std::process::id()));
+        let bin_path = bin_path.to_str().unwrap();
+
+        write_candles_to_bin(&candles, bin_path).expect("write bin");
+        let loaded = read_candles_from_bin(bin_path).expect("read bin");
+        std::fs::remove_file(bin_path).ok();
+
+        assert_eq!(loaded.close, candles.close);
+        assert_eq!(loaded.timestamp, candles.timestamp);
+        assert_eq!(loaded.volume, candles.volume);
+        assert_eq!(loaded.hlc3, candles.hlc3);
+    }
+
+    #[test]
+    fn test_bin_rejects_bad_files() {
+        let dir = std::env::temp_dir();
+        let bad_magic = dir.join(format!("bad_magic_{}.bin", std::process::id()));
+        std::fs::write(&bad_magic, b"NOTCANDLES\0\0\0\0\0\0").unwrap();
+        let err = read_candles_from_bin(bad_magic.to_str().unwrap()).unwrap_err();
+        std::fs::remove_file(&bad_magic).ok();
+        assert!(matches!(err, DataLoaderError::BadMagic));
+
+        let truncated = dir.join(format!("truncated_{}.bin", std::process::id()));
+        let mut bytes = BIN_MAGIC.to_vec();
+        bytes.extend_from_slice(&10u64.to_le_bytes());
+        bytes.extend_from_slice(&[0u8; 16]);
+        std::fs::write(&truncated, bytes).unwrap();
+        let err = read_candles_from_bin(truncated.to_str().unwrap()).unwrap_err();
+        std::fs::remove_file(&truncated).ok();
+        assert!(matches!(
+            err,
+            DataLoaderError::TruncatedBinary {
+                expected: 496,
+                actual: 32
+            }
+        ));
+
+        // 16 + count * 48 wraps to exactly 32 bytes in u64 arithmetic.
+        let wrapping = dir.join(format!("wrapping_{}.bin", std::process::id()));
+        let mut bytes = BIN_MAGIC.to_vec();
+        bytes.extend_from_slice(&0x0AAA_AAAA_AAAA_AAABu64.to_le_bytes());
+        bytes.extend_from_slice(&[0u8; 16]);
+        std::fs::write(&wrapping, bytes).unwrap();
+        let err = read_candles_from_bin(wrapping.to_str().unwrap()).unwrap_err();
+        std::fs::remove_file(&wrapping).ok();
+        assert!(matches!(
+            err,
+            DataLoaderError::TruncatedBinary {
+                expected: usize::MAX,
+                actual: 32
+            }
+        ));
+
+        let mismatched = dir.join(format!("mismatched_{}.bin", std::process::id()));
+        let mut candles = Candles::new(
+            vec![1, 2],
+            vec![100.0, 101.0],
+            vec![110.0, 111.0],
+            vec![90.0, 91.0],
+            vec![105.0, 106.0],
+            vec![1000.0, 2000.0],
+        );
+        candles.open.pop();
+        let err = write_candles_to_bin(&candles, mismatched.to_str().unwrap()).unwrap_err();
+        assert!(matches!(
+            err,
+            DataLoaderError::LengthMismatch {
+                field: "open",
+                expected: 2,
+                actual: 1
+            }
+        ));
+        assert!(!mismatched.exists());
+    }
+
+    #[test]
+    fn test_write_candles_tradingview_csv() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let csv_path = std::env::temp_dir().join(format!("candles_tv_{}.csv", std::process::id()));
+        let csv_path = csv_path.to_str().unwrap();
+
+        write_candles_tradingview_csv(&candles, csv_path).expect("write csv");
+        let written = std::fs::read_to_string(csv_path).expect("read csv");
+        std::fs::remove_file(csv_path).ok();
+
+        let mut lines = written.lines();
+        assert_eq!(lines.next(), Some("time,open,high,low,close,volume"));
+        let first: Vec<&str> = lines.next().expect("first row").split(',').collect();
+        assert_eq!(first.len(), 6);
+        let time = chrono::DateTime::parse_from_rfc3339(first[0]).expect("ISO-8601 time");
+        assert_eq!(time.timestamp_millis(), candles.timestamp[0]);
+        assert_eq!(first[4].parse::<f64>().unwrap(), candles.close[0]);
+        assert_eq!(written.lines().count(), candles.close.len() + 1);
+        assert!(first[0].ends_with(":00Z"));
+
+        let sub_second = Candles::new(
+            vec![1_535_760_000_250, 1_535_760_001_000],
+            vec![100.0, 101.0],
+            vec![110.0, 111.0],
+            vec![90.0, 91.0],
+            vec![105.0, 106.0],
+            vec![1000.0, 2000.0],
+        );
+        write_candles_tradingview_csv(&sub_second, csv_path).expect("write csv");
+        let written = std::fs::read_to_string(csv_path).expect("read csv");
+        std::fs::remove_file(csv_path).ok();
+        let times: Vec<&str> = written
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').next().unwrap())
+            .collect();
+        assert_eq!(times, ["2018-09-01T00:00:00.250Z", "2018-09-01T00:00:01Z"]);
+
+        let mut short_volume = sub_second.clone();
+        short_volume.volume.pop();
+        match write_candles_tradingview_csv(&short_volume, csv_path) {
+            Err(DataLoaderError::LengthMismatch {
+                field: "volume",
+                expected: 2,
+                actual: 1,
+            }) => {}
+            other => panic!("Expected LengthMismatch, got {:?}", other),
+        }
+        assert!(!std::path::Path::new(csv_path).exists());
+    }
+
+    #[test]
+    fn test_field_mut_clipping_feeds_atr() {
+        use crate::indicators::atr::{atr, AtrInput};
+
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let original = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let mut candles = original.clone();
+
+        let body_top: Vec<f64> = candles
+            .open
+            .iter()
+            .zip(&candles.close)
+            .map(|(o, c)| o.max(*c))
+            .collect();
+        let high = candles.field_mut("High").expect("high column");
+        for (h, top) in high.iter_mut().zip(&body_top) {
+            *h = h.min(top * 1.001);
+        }
+        candles.refresh_derived_fields();
+
+        let clipped_high = candles.high.clone();
+        assert!(clipped_high
+            .iter()
+            .zip(&original.high)
+            .any(|(clipped, raw)| clipped < raw));
+        assert_eq!(candles.hl2[0], (clipped_high[0] + candles.low[0]) / 2.0);
+
+        let expected = Candles::new(
+            original.timestamp.clone(),
+            original.open.clone(),
+            clipped_high,
+            original.low.clone(),
+            original.close.clone(),
+            original.volume.clone(),
+        );
+        let clipped_atr = atr(&AtrInput::with_default_candles(&candles))
+            .unwrap()
+            .values;
+        let expected_atr = atr(&AtrInput::with_default_candles(&expected))
+            .unwrap()
+            .values;
+        let original_atr = atr(&AtrInput::with_default_candles(&original))
+            .unwrap()
+            .values;
+        let last = clipped_atr.len() - 1;
+        assert_eq!(clipped_atr[last], expected_atr[last]);
+        assert!(clipped_atr[last] < original_atr[last]);
+
+        assert!(matches!(
+            candles.field_mut("hl2"),
+            Err(DataLoaderError::UnknownField { .. })
+        ));
+    }
 }
@@ -0,0 +1,105 @@
+/// Reports that an indicator could not fill its warmup window.
+///
+/// With the `tracing` feature enabled this emits a `debug` event carrying the indicator
+/// name, the requested period, and the number of valid (non-`NaN`) points available.
+/// Without the feature the call compiles away.
+#[inline(always)]
+pub fn not_enough_valid_data(indicator: &str, period: usize, valid_len: usize) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        indicator,
+        period,
+        valid_len,
+        "not enough valid data for warmup"
+    );
+    #[cfg(not(feature = "tracing"))]
+    let _ = (indicator, period, valid_len);
+}
+
+/// Reports that a base series does not divide evenly into higher-timeframe bars.
+///
+/// With the `tracing` feature enabled this emits a `warn` event carrying the base length,
+/// the resample factor, and the number of leftover base bars. Without the feature the
+/// call compiles away.
+#[inline(always)]
+pub fn partial_resample_bucket(base_len: usize, factor: usize, remainder: usize) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        base_len,
+        factor,
+        remainder,
+        "base bars do not fill the last higher-timeframe bar"
+    );
+    #[cfg(not(feature = "tracing"))]
+    let _ = (base_len, factor, remainder);
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use crate::indicators::sma::{sma, SmaInput, SmaParams};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    struct CountingSubscriber {
+        events: Arc<AtomicUsize>,
+    }
+
+    struct IndicatorVisitor {
+        indicator: Option<String>,
+    }
+
+    impl Visit for IndicatorVisitor {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "indicator" {
+                self.indicator = Some(value.to_string());
+            }
+        }
+
+        fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+    }
+
+    impl Subscriber for CountingSubscriber {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _span: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut visitor = IndicatorVisitor { indicator: None };
+            event.record(&mut visitor);
+            if visitor.indicator.as_deref() == Some("sma") {
+                self.events.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[test]
+    fn test_event_fires_for_over_long_period() {
+        let events = Arc::new(AtomicUsize::new(0));
+        let subscriber = CountingSubscriber {
+            events: events.clone(),
+        };
+
+        let data = [f64::NAN, f64::NAN, 1.0, 2.0, 3.0];
+        tracing::subscriber::with_default(subscriber, || {
+            let input = SmaInput::from_slice(&data, SmaParams { period: Some(4) });
+            assert!(sma(&input).is_err());
+        });
+
+        assert_eq!(events.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,2 +1,7 @@
+pub mod cache;
 pub mod data_loader;
+pub mod diagnostics;
 pub mod math_functions;
+pub mod resample;
+pub mod returns;
+pub mod series;
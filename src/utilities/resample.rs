@@ -0,0 +1,236 @@
+use crate::utilities::data_loader::{Candle, Candles, DataLoaderError};
+use crate::utilities::diagnostics::partial_resample_bucket;
+
+/// Streaming aggregation of base-timeframe candles into a higher timeframe.
+///
+/// Buckets are aligned to the Unix epoch, so a `target_seconds` of 86 400 produces daily
+/// bars that start at UTC midnight. Each emitted candle carries its bucket's start time,
+/// the first open, the highest high, the lowest low, the last close and the summed volume.
+#[derive(Debug, Clone)]
+pub struct TimeframeAggregator {
+    pub target_seconds: i64,
+    current: Option<Candle>,
+    current_bucket: i64,
+}
+
+impl TimeframeAggregator {
+    /// # Panics
+    /// Panics if `target_seconds` is not positive.
+    pub fn new(target_seconds: i64) -> Self {
+        assert!(
+            target_seconds > 0,
+            "TimeframeAggregator: target_seconds must be positive"
+        );
+        Self {
+            target_seconds,
+            current: None,
+            current_bucket: 0,
+        }
+    }
+
+    #[inline]
+    fn bucket_of(&self, timestamp_ms: i64) -> i64 {
+        timestamp_ms.div_euclid(self.target_seconds * 1000)
+    }
+
+    /// Adds a base candle. Returns the completed higher-timeframe candle when `candle`
+    /// falls into a new bucket, otherwise `None`.
+    pub fn push(&mut self, candle: Candle) -> Option<Candle> {
+        let bucket = self.bucket_of(candle.timestamp);
+        match self.current.as_mut() {
+            Some(bar) if bucket == self.current_bucket => {
+                bar.high = bar.high.max(candle.high);
+                bar.low = bar.low.min(candle.low);
+                bar.close = candle.close;
+                bar.volume += candle.volume;
+                None
+            }
+            _ => {
+                let completed = self.current.take();
+                self.current_bucket = bucket;
+                self.current = Some(Candle {
+                    timestamp: bucket * self.target_seconds * 1000,
+                    ..candle
+                });
+                completed
+            }
+        }
+    }
+
+    /// The bar still being built, if any.
+    pub fn pending(&self) -> Option<&Candle> {
+        self.current.as_ref()
+    }
+
+    /// Returns the partially built bar and resets the aggregator.
+    pub fn flush(&mut self) -> Option<Candle> {
+        self.current.take()
+    }
+}
+
+/// Resamples `candles` into epoch-aligned buckets of `bucket_seconds`, grouping bars by
+/// `floor(timestamp / bucket)` rather than by a fixed number of base bars, so gaps in the
+/// feed do not shift later buckets. Buckets with no base bars are skipped. Each bar is
+/// aggregated as in [`TimeframeAggregator`], and the last bucket is included even if it
+/// is incomplete.
+///
+/// Returns `InvalidBucket` for a non-positive `bucket_seconds` and
+/// `NonMonotonicTimestamp` if the timestamps are not strictly increasing.
+pub fn resample_by_duration(
+    candles: &Candles,
+    bucket_seconds: i64,
+) -> Result<Candles, DataLoaderError> {
+    if bucket_seconds <= 0 {
+        return Err(DataLoaderError::InvalidBucket { bucket_seconds });
+    }
+
+    let mut aggregator = TimeframeAggregator::new(bucket_seconds);
+    let mut bars = Vec::new();
+    for i in 0..candles.close.len() {
+        if i > 0 && candles.timestamp[i] <= candles.timestamp[i - 1] {
+            return Err(DataLoaderError::NonMonotonicTimestamp {
+                index: i,
+                previous: candles.timestamp[i - 1],
+                current: candles.timestamp[i],
+            });
+        }
+        if let Some(bar) = aggregator.push(candles.candle_at(i).unwrap()) {
+            bars.push(bar);
+        }
+    }
+    bars.extend(aggregator.flush());
+
+    Ok(Candles::new(
+        bars.iter().map(|b| b.timestamp).collect(),
+        bars.iter().map(|b| b.open).collect(),
+        bars.iter().map(|b| b.high).collect(),
+        bars.iter().map(|b| b.low).collect(),
+        bars.iter().map(|b| b.close).collect(),
+        bars.iter().map(|b| b.volume).collect(),
+    ))
+}
+
+/// Number of complete higher-timeframe bars when grouping `base_len` base bars `factor`
+/// at a time, e.g. before joining a higher-timeframe indicator back onto the base bars.
+///
+/// A remainder is allowed: the leftover base bars belong to an incomplete higher bar that
+/// is not counted, and a warning is reported through [`partial_resample_bucket`]. Returns
+/// `InvalidResampleFactor` when `factor` is zero or larger than `base_len`.
+pub fn validate_resample_factor(base_len: usize, factor: usize) -> Result<usize, DataLoaderError> {
+    if factor == 0 || factor > base_len {
+        return Err(DataLoaderError::InvalidResampleFactor { factor, base_len });
+    }
+    let remainder = base_len % factor;
+    if remainder != 0 {
+        partial_resample_bucket(base_len, factor, remainder);
+    }
+    Ok(base_len / factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::data_loader::read_candles_from_csv;
+
+    #[test]
+    fn test_timeframe_aggregator_daily_from_4h() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let day_ms = 86_400_000;
+
+        let mut aggregator = TimeframeAggregator::new(86_400);
+        let mut daily = Vec::new();
+        for i in 0..60 {
+            if let Some(bar) = aggregator.push(candles.candle_at(i).unwrap()) {
+                daily.push(bar);
+            }
+        }
+
+        assert_eq!(daily.len(), 9);
+        assert!(daily.iter().all(|bar| bar.timestamp % day_ms == 0));
+        for pair in daily.windows(2) {
+            assert_eq!(pair[1].timestamp - pair[0].timestamp, day_ms);
+        }
+
+        let first = &daily[0];
+        assert_eq!(first.timestamp, candles.timestamp[0]);
+        assert_eq!(first.open, candles.open[0]);
+        assert_eq!(first.close, candles.close[5]);
+        let high = candles.high[..6].iter().cloned().fold(f64::MIN, f64::max);
+        let low = candles.low[..6].iter().cloned().fold(f64::MAX, f64::min);
+        let volume: f64 = candles.volume[..6].iter().sum();
+        assert_eq!(first.high, high);
+        assert_eq!(first.low, low);
+        assert!((first.volume - volume).abs() < 1e-9);
+
+        let pending = aggregator.flush().expect("Expected a partial daily bar");
+        assert_eq!(pending.close, candles.close[59]);
+        assert!(aggregator.pending().is_none());
+    }
+
+    #[test]
+    fn test_resample_by_duration_daily() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let day_ms = 86_400_000;
+
+        let daily = resample_by_duration(&candles, 86_400).expect("resample");
+        let first_day = candles.timestamp[0].div_euclid(day_ms);
+        let last_day = candles.timestamp[candles.timestamp.len() - 1].div_euclid(day_ms);
+        assert!(daily.close.len() as i64 <= last_day - first_day + 1);
+        assert!(daily.timestamp.iter().all(|ts| ts % day_ms == 0));
+        assert!(daily.timestamp.windows(2).all(|w| w[1] > w[0]));
+        assert_eq!(daily.timestamp[0], candles.timestamp[0]);
+        assert_eq!(daily.close[0], candles.close[5]);
+        assert_eq!(daily.open[1], candles.open[6]);
+        assert_eq!(*daily.close.last().unwrap(), *candles.close.last().unwrap());
+
+        let total_volume: f64 = candles.volume.iter().sum();
+        let daily_volume: f64 = daily.volume.iter().sum();
+        assert!((total_volume - daily_volume).abs() / total_volume < 1e-9);
+    }
+
+    #[test]
+    fn test_resample_by_duration_skips_gaps() {
+        let hour = 3_600_000;
+        let timestamps = vec![0, hour, 5 * hour, 6 * hour];
+        let closes = vec![1.0, 2.0, 3.0, 4.0];
+        let candles = Candles::new(
+            timestamps,
+            closes.clone(),
+            closes.iter().map(|c| c + 0.5).collect(),
+            closes.iter().map(|c| c - 0.5).collect(),
+            closes,
+            vec![1.0; 4],
+        );
+
+        let resampled = resample_by_duration(&candles, 2 * 3_600).expect("resample");
+        assert_eq!(resampled.timestamp, vec![0, 4 * hour, 6 * hour]);
+        assert_eq!(resampled.close, vec![2.0, 3.0, 4.0]);
+        assert_eq!(resampled.volume, vec![2.0, 1.0, 1.0]);
+
+        assert!(matches!(
+            resample_by_duration(&candles, 0),
+            Err(DataLoaderError::InvalidBucket { bucket_seconds: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_validate_resample_factor() {
+        assert_eq!(validate_resample_factor(100, 6).unwrap(), 16);
+        assert_eq!(validate_resample_factor(96, 6).unwrap(), 16);
+        assert_eq!(validate_resample_factor(6, 6).unwrap(), 1);
+        assert_eq!(validate_resample_factor(100, 1).unwrap(), 100);
+        assert!(matches!(
+            validate_resample_factor(100, 0),
+            Err(DataLoaderError::InvalidResampleFactor {
+                factor: 0,
+                base_len: 100
+            })
+        ));
+        assert!(matches!(
+            validate_resample_factor(5, 6),
+            Err(DataLoaderError::InvalidResampleFactor { .. })
+        ));
+    }
+}
@@ -0,0 +1,251 @@
+use crate::utilities::data_loader::Candles;
+
+/// Simple (arithmetic) returns: `data[i] / data[i - 1] - 1`.
+///
+/// Index 0 is `NaN`, as is any bar whose previous value is zero.
+#[inline]
+pub fn simple_returns(data: &[f64]) -> Vec<f64> {
+    let mut output = vec![f64::NAN; data.len()];
+    for i in 1..data.len() {
+        let prev = data[i - 1];
+        if prev != 0.0 {
+            output[i] = data[i] / prev - 1.0;
+        }
+    }
+    output
+}
+
+/// Log returns: `ln(data[i] / data[i - 1])`.
+///
+/// Index 0 is `NaN`, as is any bar where either price is zero or negative.
+#[inline]
+pub fn log_returns(data: &[f64]) -> Vec<f64> {
+    let mut output = vec![f64::NAN; data.len()];
+    for i in 1..data.len() {
+        let prev = data[i - 1];
+        let curr = data[i];
+        if prev > 0.0 && curr > 0.0 {
+            output[i] = (curr / prev).ln();
+        }
+    }
+    output
+}
+
+/// [`log_returns`] of the candle closes, the usual input for volatility models.
+#[inline]
+pub fn to_log_returns(candles: &Candles) -> Vec<f64> {
+    log_returns(&candles.close)
+}
+
+/// Rolling Sharpe ratio of `returns` (e.g. from [`simple_returns`]) over `period` bars:
+/// the window mean over its sample standard deviation, scaled by `sqrt(annualization)`
+/// (e.g. `365.0 * 6.0` for 4h crypto bars). The risk-free rate is taken as zero.
+///
+/// The first `period - 1` bars are `NaN`, as is any window holding a `NaN` return and
+/// every bar when `period < 2`. A window with zero deviation (within `1e-12` of its sum of
+/// squares) is `±inf` following the sign of its mean, or `NaN` when the mean is zero as well.
+#[inline]
+pub fn rolling_sharpe(returns: &[f64], period: usize, annualization: f64) -> Vec<f64> {
+    rolling_ratio(returns, period, annualization, |sum, sum_sq, _, n| {
+        let mean = sum / n;
+        let spread = sum_sq - sum * mean;
+        // Running sums leave rounding residue behind, so a spread within that noise of
+        // `sum_sq` is a constant window rather than a tiny real deviation.
+        if spread <= sum_sq * 1e-12 {
+            return 0.0;
+        }
+        (spread / (n - 1.0)).sqrt()
+    })
+}
+
+/// Rolling Sortino ratio of `returns` over `period` bars: like [`rolling_sharpe`], but the
+/// denominator is the downside deviation `sqrt(sum(min(r, 0)^2) / period)`, so only
+/// losing bars count as risk.
+///
+/// Warmup and `NaN` handling match [`rolling_sharpe`]. A window without a losing bar has
+/// zero downside deviation, so a window of only positive returns is `+inf`.
+#[inline]
+pub fn rolling_sortino(returns: &[f64], period: usize, annualization: f64) -> Vec<f64> {
+    rolling_ratio(returns, period, annualization, |_, _, downside_sq, n| {
+        (downside_sq / n).sqrt()
+    })
+}
+
+/// Shared rolling driver for [`rolling_sharpe`] and [`rolling_sortino`]. Keeps running
+/// sums of the returns, their squares and their squared losses, and hands them with the
+/// window length to `deviation`. The squared-loss sum is reset to exactly zero once no
+/// losing bar is left in the window, so rounding residue cannot stand in for downside risk.
+#[inline(always)]
+fn rolling_ratio(
+    returns: &[f64],
+    period: usize,
+    annualization: f64,
+    deviation: impl Fn(f64, f64, f64, f64) -> f64,
+) -> Vec<f64> {
+    let len = returns.len();
+    let mut output = vec![f64::NAN; len];
+    if period < 2 || period > len {
+        return output;
+    }
+
+    let scale = annualization.sqrt();
+    let n = period as f64;
+    let (mut sum, mut sum_sq, mut downside_sq) = (0.0, 0.0, 0.0);
+    let (mut nan_count, mut loss_count) = (0usize, 0usize);
+
+    for i in 0..len {
+        let r = returns[i];
+        if r.is_nan() {
+            nan_count += 1;
+        } else {
+            sum += r;
+            sum_sq += r * r;
+            if r < 0.0 {
+                downside_sq += r * r;
+                loss_count += 1;
+            }
+        }
+        if i >= period {
+            let old = returns[i - period];
+            if old.is_nan() {
+                nan_count -= 1;
+            } else {
+                sum -= old;
+                sum_sq -= old * old;
+                if old < 0.0 {
+                    downside_sq -= old * old;
+                    loss_count -= 1;
+                }
+            }
+        }
+        if loss_count == 0 {
+            downside_sq = 0.0;
+        }
+        if i + 1 < period || nan_count > 0 {
+            continue;
+        }
+
+        let mean = sum / n;
+        let dev = deviation(sum, sum_sq, downside_sq.max(0.0), n);
+        output[i] = if dev > 0.0 {
+            mean / dev * scale
+        } else if mean != 0.0 {
+            mean.signum() * f64::INFINITY
+        } else {
+            f64::NAN
+        };
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::data_loader::read_candles_from_csv;
+
+    #[test]
+    fn test_returns_doubling_series() {
+        let data = [1.0, 2.0, 4.0, 8.0];
+        let simple = simple_returns(&data);
+        let log = log_returns(&data);
+
+        assert!(simple[0].is_nan());
+        assert!(log[0].is_nan());
+        for i in 1..data.len() {
+            assert!((simple[i] - 1.0).abs() < 1e-12);
+            assert!((log[i] - std::f64::consts::LN_2).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_returns_zero_and_negative_prices() {
+        let data = [0.0, 5.0, -5.0, 10.0];
+        let simple = simple_returns(&data);
+        let log = log_returns(&data);
+
+        assert!(simple[1].is_nan(), "Return from a zero price should be NaN");
+        assert_eq!(simple[2], -2.0);
+        assert!(log[1].is_nan());
+        assert!(log[2].is_nan());
+        assert!(log[3].is_nan());
+    }
+
+    #[test]
+    fn test_to_log_returns_recovers_close_path() {
+        let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+        let candles = read_candles_from_csv(file_path).expect("Failed to load test candles");
+        let returns = to_log_returns(&candles);
+        assert_eq!(returns.len(), candles.close.len());
+        assert!(returns[0].is_nan());
+
+        let first = candles.close[0];
+        let mut cumulative = 0.0;
+        for (i, (r, &close)) in returns.iter().zip(&candles.close).enumerate().skip(1) {
+            cumulative += r;
+            let rebuilt = first * cumulative.exp();
+            assert!(
+                (rebuilt - close).abs() / close < 1e-9,
+                "Close path diverged at {}: {} vs {}",
+                i,
+                rebuilt,
+                close
+            );
+        }
+    }
+
+    #[test]
+    fn test_rolling_sharpe_sortino_positive_returns() {
+        let returns: Vec<f64> = (0..20)
+            .map(|i| if i % 2 == 0 { 0.01 } else { 0.02 })
+            .collect();
+        let sharpe = rolling_sharpe(&returns, 5, 252.0);
+        let sortino = rolling_sortino(&returns, 5, 252.0);
+
+        assert!(sharpe[..4].iter().all(|v| v.is_nan()));
+        assert!(sortino[..4].iter().all(|v| v.is_nan()));
+        for i in 4..returns.len() {
+            assert!(
+                sharpe[i].is_finite() && sharpe[i] > 0.0,
+                "Sharpe at {i}: {}",
+                sharpe[i]
+            );
+            assert_eq!(sortino[i], f64::INFINITY, "Sortino at {i}");
+        }
+
+        // Window [0.01, 0.02, 0.01, 0.02, 0.01]: mean 0.014, sample std sqrt(0.00003).
+        let expected = 0.014 / 0.00003_f64.sqrt() * 252.0_f64.sqrt();
+        assert!((sharpe[4] - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rolling_sortino_downside_and_nan() {
+        let returns = [f64::NAN, 0.02, -0.01, 0.03, -0.02, 0.01];
+        let sharpe = rolling_sharpe(&returns, 3, 1.0);
+        let sortino = rolling_sortino(&returns, 3, 1.0);
+
+        assert!(sharpe[..3].iter().all(|v| v.is_nan()));
+        assert!(sortino[..3].iter().all(|v| v.is_nan()));
+
+        // Window [0.02, -0.01, 0.03]: mean 0.04 / 3, downside deviation sqrt(0.0001 / 3).
+        let expected = (0.04 / 3.0) / (0.0001_f64 / 3.0).sqrt();
+        assert!((sortino[3] - expected).abs() < 1e-9);
+        assert!(sortino[3] > sharpe[3]);
+
+        assert!(rolling_sharpe(&returns, 1, 1.0).iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_rolling_ratios_after_losses_leave_window() {
+        let mut returns = vec![-0.0267, -0.0269, -0.0281, -0.004, -0.021, -0.0479];
+        returns.extend([0.01; 8]);
+        let sharpe = rolling_sharpe(&returns, 4, 252.0);
+        let sortino = rolling_sortino(&returns, 4, 252.0);
+
+        // Windows from index 9 on hold only the constant 0.01 return.
+        for i in 9..returns.len() {
+            assert_eq!(sortino[i], f64::INFINITY, "Sortino at {i}: {}", sortino[i]);
+            assert_eq!(sharpe[i], f64::INFINITY, "Sharpe at {i}: {}", sharpe[i]);
+        }
+        assert!(sortino[8].is_finite());
+    }
+}
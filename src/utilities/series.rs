@@ -0,0 +1,420 @@
+/// How leading warmup `NaN`s in an indicator output should be presented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WarmupFill {
+    /// Leave the warmup as `NaN` (what every indicator returns).
+    #[default]
+    Nan,
+    /// Back-fill the warmup with the first finite value.
+    Hold,
+    /// Replace the warmup with `0.0`.
+    Zero,
+}
+
+/// Index of the first finite value in `values`, or `values.len()` if there is none.
+#[inline]
+pub fn first_finite_index(values: &[f64]) -> usize {
+    values
+        .iter()
+        .position(|v| v.is_finite())
+        .unwrap_or(values.len())
+}
+
+/// Shared check for the `first_valid` field of indicator outputs. Runs `compute` on the
+/// test candle closes behind seven leading `NaN`s and asserts that the reported
+/// `first_valid` is `7 + warmup`, agrees with [`first_finite_index`], and has only `NaN`s
+/// before it.
+#[cfg(test)]
+pub(crate) fn assert_first_valid_after_nan_prefix<F>(warmup: usize, compute: F)
+where
+    F: Fn(&[f64]) -> (Vec<f64>, usize),
+{
+    let file_path = "src/data/2018-09-01-2024-Bitfinex_Spot-4h.csv";
+    let candles = crate::utilities::data_loader::read_candles_from_csv(file_path)
+        .expect("Failed to load test candles");
+    let mut data = vec![f64::NAN; 7];
+    data.extend_from_slice(&candles.close[..300]);
+
+    let (values, first_valid) = compute(&data);
+    assert_eq!(first_valid, 7 + warmup);
+    assert_eq!(first_valid, first_finite_index(&values));
+    assert!(values[..first_valid].iter().all(|v| v.is_nan()));
+}
+
+/// Replaces the leading `NaN`s of `values` according to `fill`. Only the warmup prefix
+/// is touched; `NaN`s after the first finite value are left as they are. A series with
+/// no finite value is left unchanged under `Hold` and zeroed under `Zero`.
+#[inline]
+pub fn apply_warmup_fill(values: &mut [f64], fill: WarmupFill) {
+    let first_finite = values.iter().position(|v| v.is_finite());
+    let warmup_end = first_finite.unwrap_or(values.len());
+    let fill_value = match fill {
+        WarmupFill::Nan => return,
+        WarmupFill::Hold => match first_finite {
+            Some(idx) => values[idx],
+            None => return,
+        },
+        WarmupFill::Zero => 0.0,
+    };
+    for v in &mut values[..warmup_end] {
+        if v.is_nan() {
+            *v = fill_value;
+        }
+    }
+}
+
+/// Largest-Triangle-Three-Buckets downsampling of the points `(x[i], y[i])` to
+/// `threshold` points, keeping the visual shape of the series. Meant for plot export only.
+///
+/// The first and last points are always kept. When `threshold` is below 3 or not smaller
+/// than the input, the input is returned unchanged. `NaN`s are not skipped, so drop them
+/// beforehand (e.g. the warmup of an indicator). If `x` and `y` differ in length, the
+/// shorter length is used.
+pub fn lttb(x: &[f64], y: &[f64], threshold: usize) -> (Vec<f64>, Vec<f64>) {
+    let len = x.len().min(y.len());
+    if threshold < 3 || threshold >= len {
+        return (x[..len].to_vec(), y[..len].to_vec());
+    }
+
+    let mut out_x = Vec::with_capacity(threshold);
+    let mut out_y = Vec::with_capacity(threshold);
+    out_x.push(x[0]);
+    out_y.push(y[0]);
+
+    let bucket_size = (len - 2) as f64 / (threshold - 2) as f64;
+    let mut selected = 0;
+
+    for bucket in 0..(threshold - 2) {
+        let start = (bucket as f64 * bucket_size) as usize + 1;
+        let end = ((bucket + 1) as f64 * bucket_size) as usize + 1;
+
+        let next_start = end;
+        let next_end = (((bucket + 2) as f64 * bucket_size) as usize + 1).min(len);
+        let (avg_x, avg_y) = if next_start < next_end {
+            let next_count = (next_end - next_start) as f64;
+            let sum_x: f64 = x[next_start..next_end].iter().sum();
+            let sum_y: f64 = y[next_start..next_end].iter().sum();
+            (sum_x / next_count, sum_y / next_count)
+        } else {
+            (x[len - 1], y[len - 1])
+        };
+
+        let (ax, ay) = (x[selected], y[selected]);
+        let mut max_area = -1.0;
+        let mut max_idx = start;
+        for i in start..end {
+            let area = ((ax - avg_x) * (y[i] - ay) - (ax - x[i]) * (avg_y - ay)).abs();
+            if area > max_area {
+                max_area = area;
+                max_idx = i;
+            }
+        }
+
+        out_x.push(x[max_idx]);
+        out_y.push(y[max_idx]);
+        selected = max_idx;
+    }
+
+    out_x.push(x[len - 1]);
+    out_y.push(y[len - 1]);
+    (out_x, out_y)
+}
+
+/// Displaces `values` forward by `offset` bars, as done for the Alligator lines or
+/// Ichimoku spans: `out[i + offset] = values[i]`. The first `offset` slots are `NaN` and
+/// the last `offset` values are dropped, so the length is unchanged.
+#[inline]
+pub fn shift_forward(values: &[f64], offset: usize) -> Vec<f64> {
+    let len = values.len();
+    let offset = offset.min(len);
+    let mut out = vec![f64::NAN; len];
+    out[offset..].copy_from_slice(&values[..len - offset]);
+    out
+}
+
+/// Displaces `values` back by `offset` bars (a negative displacement, e.g. the Ichimoku
+/// lagging span): `out[i] = values[i + offset]`. The first `offset` values are dropped
+/// and the last `offset` slots are `NaN`, so the length is unchanged.
+#[inline]
+pub fn shift_back(values: &[f64], offset: usize) -> Vec<f64> {
+    let len = values.len();
+    let offset = offset.min(len);
+    let mut out = vec![f64::NAN; len];
+    out[..len - offset].copy_from_slice(&values[offset..]);
+    out
+}
+
+/// Sum of each trailing window of `period` values: `out[i] = data[i + 1 - period..=i].sum()`.
+/// The first `period - 1` slots are `NaN`, as is any window containing a `NaN`; the sum
+/// recovers once the `NaN` leaves the window. A `period` of zero or longer than `data`
+/// gives an all-`NaN` result.
+#[inline]
+pub fn rolling_sum(data: &[f64], period: usize) -> Vec<f64> {
+    let len = data.len();
+    let mut out = vec![f64::NAN; len];
+    if period == 0 || period > len {
+        return out;
+    }
+
+    let mut sum = 0.0;
+    let mut nan_count = 0usize;
+    for i in 0..len {
+        let value = data[i];
+        if value.is_nan() {
+            nan_count += 1;
+        } else {
+            sum += value;
+        }
+        if i >= period {
+            let old = data[i - period];
+            if old.is_nan() {
+                nan_count -= 1;
+            } else {
+                sum -= old;
+            }
+        }
+        if i + 1 >= period && nan_count == 0 {
+            out[i] = sum;
+        }
+    }
+    out
+}
+
+/// Fills interior `NaN` gaps by linear interpolation between the values on either side,
+/// treating bars as evenly spaced (e.g. for continuous plotting). Leading and trailing
+/// `NaN`s have only one neighbour and are left as they are.
+#[inline]
+pub fn interpolate_interior_nan(values: &[f64]) -> Vec<f64> {
+    let mut out = values.to_vec();
+    let mut last_valid: Option<usize> = None;
+    for i in 0..values.len() {
+        if values[i].is_nan() {
+            continue;
+        }
+        if let Some(start) = last_valid {
+            if i > start + 1 {
+                let (from, to) = (values[start], values[i]);
+                let step = (to - from) / (i - start) as f64;
+                for (k, value) in out[start + 1..i].iter_mut().enumerate() {
+                    *value = from + step * (k + 1) as f64;
+                }
+            }
+        }
+        last_valid = Some(i);
+    }
+    out
+}
+
+/// Index of the first position where `a` and `b` differ by more than `tol`, or where
+/// only one of them is `NaN`. Equal infinities match. Slices of different lengths
+/// mismatch at the shorter length.
+#[inline]
+pub fn first_slice_mismatch(a: &[f64], b: &[f64], tol: f64) -> Option<usize> {
+    let mismatch = a.iter().zip(b).position(|(&x, &y)| {
+        if x.is_nan() || y.is_nan() {
+            x.is_nan() != y.is_nan()
+        } else {
+            x != y && (x - y).abs() > tol
+        }
+    });
+    match mismatch {
+        Some(idx) => Some(idx),
+        None if a.len() != b.len() => Some(a.len().min(b.len())),
+        None => None,
+    }
+}
+
+/// `true` if `a` and `b` have the same length, agree within `tol` everywhere, and have
+/// `NaN` in exactly the same positions.
+#[inline]
+pub fn approx_eq_slice(a: &[f64], b: &[f64], tol: f64) -> bool {
+    first_slice_mismatch(a, b, tol).is_none()
+}
+
+/// Asserts that two `f64` slices match under [`approx_eq_slice`], reporting the first
+/// mismatching index and values on failure.
+///
+/// ```text
+/// assert_slice_close!(&result.values[start..], &expected, 1e-6);
+/// ```
+#[macro_export]
+macro_rules! assert_slice_close {
+    ($a:expr, $b:expr, $tol:expr $(,)?) => {{
+        let a: &[f64] = &$a[..];
+        let b: &[f64] = &$b[..];
+        if let Some(idx) = $crate::utilities::series::first_slice_mismatch(a, b, $tol) {
+            panic!(
+                "slices differ at index {} (len {} vs {}, tol {}): {:?} vs {:?}",
+                idx,
+                a.len(),
+                b.len(),
+                $tol,
+                a.get(idx),
+                b.get(idx)
+            );
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warmup_series() -> Vec<f64> {
+        let mut values = vec![f64::NAN; 5];
+        values.extend_from_slice(&[3.0, f64::NAN, 4.0]);
+        values
+    }
+
+    #[test]
+    fn test_first_finite_index() {
+        assert_eq!(first_finite_index(&warmup_series()), 5);
+        assert_eq!(first_finite_index(&[1.0, f64::NAN]), 0);
+        assert_eq!(
+            first_finite_index(&[f64::NAN, f64::INFINITY, -2.0]),
+            2,
+            "Infinities are not finite"
+        );
+        assert_eq!(first_finite_index(&[f64::NAN; 3]), 3);
+        assert_eq!(first_finite_index(&[]), 0);
+    }
+
+    #[test]
+    fn test_warmup_fill_policies() {
+        let mut nan = warmup_series();
+        apply_warmup_fill(&mut nan, WarmupFill::Nan);
+        assert!(nan[..5].iter().all(|v| v.is_nan()));
+
+        let mut hold = warmup_series();
+        apply_warmup_fill(&mut hold, WarmupFill::Hold);
+        assert_eq!(&hold[..6], &[3.0; 6]);
+        assert!(hold[6].is_nan(), "Interior NaN should not be filled");
+
+        let mut zero = warmup_series();
+        apply_warmup_fill(&mut zero, WarmupFill::Zero);
+        assert_eq!(&zero[..6], &[0.0, 0.0, 0.0, 0.0, 0.0, 3.0]);
+        assert!(zero[6].is_nan(), "Interior NaN should not be filled");
+        assert_eq!(zero[7], 4.0);
+    }
+
+    #[test]
+    fn test_warmup_fill_all_nan() {
+        let mut hold = vec![f64::NAN; 3];
+        apply_warmup_fill(&mut hold, WarmupFill::Hold);
+        assert!(hold.iter().all(|v| v.is_nan()));
+
+        let mut zero = vec![f64::NAN; 3];
+        apply_warmup_fill(&mut zero, WarmupFill::Zero);
+        assert_eq!(zero, vec![0.0; 3]);
+    }
+
+    #[test]
+    fn test_lttb_length_and_endpoints() {
+        let x: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+        let y: Vec<f64> = x.iter().map(|v| (v / 25.0).sin() * 10.0).collect();
+
+        let (dx, dy) = lttb(&x, &y, 100);
+        assert_eq!(dx.len(), 100);
+        assert_eq!(dy.len(), 100);
+        assert_eq!((dx[0], dy[0]), (x[0], y[0]));
+        assert_eq!((dx[99], dy[99]), (x[999], y[999]));
+        assert!(dx.windows(2).all(|w| w[0] < w[1]), "x must stay ordered");
+
+        let max_y = y.iter().cloned().fold(f64::MIN, f64::max);
+        let kept_max = dy.iter().cloned().fold(f64::MIN, f64::max);
+        assert!(max_y - kept_max < 0.05, "Peaks should be preserved");
+
+        let (same_x, _) = lttb(&x[..10], &y[..10], 50);
+        assert_eq!(same_x, x[..10].to_vec());
+    }
+
+    #[test]
+    fn test_shift_forward_by_three() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let shifted = shift_forward(&values, 3);
+        assert_eq!(shifted.len(), values.len());
+        assert!(shifted[..3].iter().all(|v| v.is_nan()));
+        assert_eq!(&shifted[3..], &[1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_shift_back_by_three() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let shifted = shift_back(&values, 3);
+        assert_eq!(shifted.len(), values.len());
+        assert_eq!(&shifted[..3], &[4.0, 5.0, 6.0]);
+        assert!(shifted[3..].iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_shift_beyond_length_is_all_nan() {
+        let values = [1.0, 2.0];
+        assert!(shift_forward(&values, 5).iter().all(|v| v.is_nan()));
+        assert!(shift_back(&values, 5).iter().all(|v| v.is_nan()));
+        assert_eq!(shift_forward(&values, 0), values.to_vec());
+    }
+
+    #[test]
+    fn test_approx_eq_slice() {
+        let a = [f64::NAN, 1.0, 2.0, f64::INFINITY];
+        let close = [f64::NAN, 1.0 + 1e-9, 2.0 - 1e-9, f64::INFINITY];
+        assert!(approx_eq_slice(&a, &close, 1e-6));
+        assert_slice_close!(a, close, 1e-6);
+
+        let off = [f64::NAN, 1.0, 2.1, f64::INFINITY];
+        assert!(!approx_eq_slice(&a, &off, 1e-6));
+        assert_eq!(first_slice_mismatch(&a, &off, 1e-6), Some(2));
+        assert!(approx_eq_slice(&a, &off, 0.2));
+
+        let misaligned_nan = [1.0, f64::NAN, 2.0, f64::INFINITY];
+        assert_eq!(first_slice_mismatch(&a, &misaligned_nan, 1.0), Some(0));
+        assert_eq!(first_slice_mismatch(&a[..3], &a, 1e-6), Some(3));
+        assert!(!approx_eq_slice(
+            &a,
+            &[f64::NAN, 1.0, 2.0, f64::NEG_INFINITY],
+            1e-6
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "slices differ at index 1")]
+    fn test_assert_slice_close_reports_mismatch() {
+        let expected = [1.0, 2.0, 3.0];
+        assert_slice_close!([1.0, 2.5, 3.0], expected, 1e-3);
+    }
+
+    #[test]
+    fn test_rolling_sum_matches_brute_force() {
+        let mut data: Vec<f64> = (0..60).map(|i| ((i * 37) % 11) as f64 - 4.5).collect();
+        data[20] = f64::NAN;
+        for period in [1, 3, 7] {
+            let fast = rolling_sum(&data, period);
+            let brute: Vec<f64> = (0..data.len())
+                .map(|i| {
+                    if i + 1 < period {
+                        f64::NAN
+                    } else {
+                        data[i + 1 - period..=i].iter().sum()
+                    }
+                })
+                .collect();
+            assert_slice_close!(fast, brute, 1e-9);
+        }
+        assert!(rolling_sum(&data, 0).iter().all(|v| v.is_nan()));
+        assert!(rolling_sum(&data[..3], 4).iter().all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn test_interpolate_interior_nan() {
+        let nan = f64::NAN;
+        let filled = interpolate_interior_nan(&[nan, 1.0, nan, 3.0, 4.0]);
+        assert_slice_close!(filled, [nan, 1.0, 2.0, 3.0, 4.0], 1e-12);
+
+        let filled = interpolate_interior_nan(&[nan, nan, 2.0, nan, nan, 8.0, nan]);
+        assert_slice_close!(filled, [nan, nan, 2.0, 4.0, 6.0, 8.0, nan], 1e-12);
+
+        assert!(interpolate_interior_nan(&[nan, nan])
+            .iter()
+            .all(|v| v.is_nan()));
+        assert!(interpolate_interior_nan(&[]).is_empty());
+    }
+}